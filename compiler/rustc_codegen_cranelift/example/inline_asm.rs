@@ -0,0 +1,84 @@
+// Tests `asm!` operands that need registers allocated for them or that are
+// substituted into the template.
+
+#![feature(asm)]
+
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+fn main() {
+    #[cfg(target_arch = "x86_64")]
+    unsafe {
+        test_reg_class();
+        test_modifier();
+        test_att_syntax();
+        test_xmm_reg();
+        if is_x86_feature_detected!("avx2") {
+            test_ymm_reg();
+        }
+        test_sym();
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+unsafe fn test_reg_class() {
+    let x: u64 = 5;
+    let y: u64;
+    asm!("mov {0}, {1}", "add {0}, {2}", out(reg) y, in(reg) x, const 37);
+    assert_eq!(y, 42);
+
+    let mut a: u64 = 1;
+    let b: u64 = 2;
+    asm!("add {0}, {1}", inout(reg) a, in(reg_abcd) b);
+    assert_eq!(a, 3);
+}
+
+#[cfg(target_arch = "x86_64")]
+unsafe fn test_modifier() {
+    let mut x: u32 = 0xffff_ffff;
+    // Writing to a 32-bit register clears the upper half of the 64-bit one.
+    asm!("add {0:e}, 1", inout(reg) x);
+    assert_eq!(x, 0);
+
+    let mut y: u16 = 0x1234;
+    asm!("xchg {0:l}, {0:h}", inout(reg_abcd) y);
+    assert_eq!(y, 0x3412);
+}
+
+#[cfg(target_arch = "x86_64")]
+unsafe fn test_att_syntax() {
+    let mut x: u64 = 40;
+    asm!("addq ${1}, {0}", inout(reg) x, const 2, options(att_syntax));
+    assert_eq!(x, 42);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+unsafe fn test_xmm_reg() {
+    let a = _mm_set1_epi32(1);
+    let b = _mm_set1_epi32(2);
+    let c: __m128i;
+    asm!("movdqa {0}, {1}", "paddd {0}, {2}", out(xmm_reg) c, in(xmm_reg) a, in(xmm_reg) b);
+    assert_eq!(std::mem::transmute::<_, [i32; 4]>(c), [3; 4]);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn test_ymm_reg() {
+    let a = _mm256_set1_epi32(1);
+    let b = _mm256_set1_epi32(2);
+    let c: __m256i;
+    asm!("vpaddd {0}, {1}, {2}", out(ymm_reg) c, in(ymm_reg) a, in(ymm_reg) b);
+    assert_eq!(std::mem::transmute::<_, [i32; 8]>(c), [3; 8]);
+}
+
+extern "C" fn forty_two() -> u64 {
+    42
+}
+
+#[cfg(target_arch = "x86_64")]
+unsafe fn test_sym() {
+    let x: u64;
+    asm!("call {}", sym forty_two, out("rax") x, clobber_abi("C"));
+    assert_eq!(x, 42);
+}
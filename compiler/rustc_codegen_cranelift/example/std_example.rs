@@ -156,6 +156,15 @@ unsafe fn test_simd() {
     test_mm_extract_epi8();
     test_mm_insert_epi16();
 
+    test_mm_shifts_epi16();
+    test_mm_packus_epi16();
+    test_mm_adds_subs_epu8();
+    test_mm_shuffle_epi8();
+    test_mm_testz_si128();
+    test_mm_clmulepi64_si128();
+    test_mm256_shuffle_epi8();
+    test_mm256_permute2x128_si256();
+
     let mask1 = _mm_movemask_epi8(dbg!(_mm_setr_epi8(255u8 as i8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0)));
     assert_eq!(mask1, 1);
 }
@@ -293,6 +302,148 @@ unsafe fn test_mm_insert_epi16() {
     assert_eq_m128i(r, e);
 }
 
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+unsafe fn test_mm_shifts_epi16() {
+    let a = _mm_setr_epi16(-0x100, 1, 2, 3, 4, 5, 6, 0x7f00);
+    let r = _mm_srli_epi16(a, 4);
+    let e = _mm_setr_epi16(0xff0, 0, 0, 0, 0, 0, 0, 0x7f0);
+    assert_eq_m128i(r, e);
+    let r = _mm_slli_epi16(a, 4);
+    let e = _mm_setr_epi16(-0x1000, 16, 32, 48, 64, 80, 96, -0x1000);
+    assert_eq_m128i(r, e);
+    let r = _mm_srai_epi16(a, 4);
+    let e = _mm_setr_epi16(-0x10, 0, 0, 0, 0, 0, 0, 0x7f0);
+    assert_eq_m128i(r, e);
+    assert_eq_m128i(_mm_srli_epi16(a, 16), _mm_setzero_si128());
+    assert_eq_m128i(_mm_srai_epi16(a, 20), _mm_setr_epi16(-1, 0, 0, 0, 0, 0, 0, 0));
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+unsafe fn test_mm_packus_epi16() {
+    let a = _mm_setr_epi16(-1, 0, 1, 255, 256, 300, 2, 3);
+    let b = _mm_setr_epi16(4, 5, 6, 7, 8, 9, -300, 1000);
+    let r = _mm_packus_epi16(a, b);
+    #[rustfmt::skip]
+    let e = _mm_setr_epi8(
+        0, 0, 1, -1, -1, -1, 2, 3,
+        4, 5, 6, 7, 8, 9, 0, -1,
+    );
+    assert_eq_m128i(r, e);
+
+    let r = _mm_packs_epi16(a, b);
+    #[rustfmt::skip]
+    let e = _mm_setr_epi8(
+        -1, 0, 1, 127, 127, 127, 2, 3,
+        4, 5, 6, 7, 8, 9, -128, 127,
+    );
+    assert_eq_m128i(r, e);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+unsafe fn test_mm_adds_subs_epu8() {
+    let a = _mm_set1_epi8(200u8 as i8);
+    let b = _mm_set1_epi8(100);
+    assert_eq_m128i(_mm_adds_epu8(a, b), _mm_set1_epi8(-1));
+    assert_eq_m128i(_mm_subs_epu8(b, a), _mm_setzero_si128());
+    assert_eq_m128i(_mm_subs_epu8(a, b), _mm_set1_epi8(100));
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "ssse3")]
+unsafe fn test_mm_shuffle_epi8() {
+    #[rustfmt::skip]
+    let a = _mm_setr_epi8(
+        1, 2, 3, 4, 5, 6, 7, 8,
+        9, 10, 11, 12, 13, 14, 15, 16,
+    );
+    #[rustfmt::skip]
+    let b = _mm_setr_epi8(
+        4, 128_u8 as i8, 4, 3,
+        24, 12, 6, 19,
+        12, 5, 5, 10,
+        4, 1, 8, 0,
+    );
+    let expected = _mm_setr_epi8(5, 0, 5, 4, 9, 13, 7, 4, 13, 6, 6, 11, 5, 2, 9, 1);
+    let r = _mm_shuffle_epi8(a, b);
+    assert_eq_m128i(r, expected);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn test_mm256_shuffle_epi8() {
+    #[rustfmt::skip]
+    let a = _mm256_setr_epi8(
+        1, 2, 3, 4, 5, 6, 7, 8,
+        9, 10, 11, 12, 13, 14, 15, 16,
+        17, 18, 19, 20, 21, 22, 23, 24,
+        25, 26, 27, 28, 29, 30, 31, 32,
+    );
+    #[rustfmt::skip]
+    let b = _mm256_setr_epi8(
+        4, 128u8 as i8, 4, 3, 24, 12, 6, 19,
+        12, 5, 5, 10, 4, 1, 8, 0,
+        4, 128u8 as i8, 4, 3, 24, 12, 6, 19,
+        12, 5, 5, 10, 4, 1, 8, 0,
+    );
+    #[rustfmt::skip]
+    let expected = _mm256_setr_epi8(
+        5, 0, 5, 4, 9, 13, 7, 4,
+        13, 6, 6, 11, 5, 2, 9, 1,
+        21, 0, 21, 20, 25, 29, 23, 20,
+        29, 22, 22, 27, 21, 18, 25, 17,
+    );
+    let r = _mm256_shuffle_epi8(a, b);
+    assert_eq_m256i(r, expected);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn test_mm256_permute2x128_si256() {
+    let a = _mm256_setr_epi64x(100, 200, 500, 600);
+    let b = _mm256_setr_epi64x(300, 400, 700, 800);
+    let r = _mm256_permute2x128_si256(a, b, 0b00_01_00_11);
+    let e = _mm256_setr_epi64x(700, 800, 500, 600);
+    assert_eq_m256i(r, e);
+    let r = _mm256_permute2x128_si256(a, b, 0b10_00_00_10);
+    let e = _mm256_setr_epi64x(300, 400, 0, 0);
+    assert_eq_m256i(r, e);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse4.1")]
+unsafe fn test_mm_testz_si128() {
+    let a = _mm_set1_epi8(1);
+    let mask = _mm_set1_epi8(0);
+    assert_eq!(_mm_testz_si128(a, mask), 1);
+    let mask = _mm_set1_epi8(1);
+    assert_eq!(_mm_testz_si128(a, mask), 0);
+    assert_eq!(_mm_testc_si128(a, mask), 1);
+    let mask = _mm_set1_epi8(3);
+    assert_eq!(_mm_testc_si128(a, mask), 0);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "pclmulqdq")]
+unsafe fn test_mm_clmulepi64_si128() {
+    let a = _mm_set_epi64x(0x7fd0cd43a5d9e9ff, 0x0000000000000003);
+    let b = _mm_set_epi64x(0x0000000000000005, 0xff00000000000000);
+    // 0b11 * 0b101 = 0b1111 without carries.
+    let r = _mm_clmulepi64_si128(a, b, 0x10);
+    assert_eq_m128i(r, _mm_set_epi64x(0, 0b1111));
+    // The upper bits of the product end up in the high half.
+    let r = _mm_clmulepi64_si128(a, b, 0x00);
+    assert_eq_m128i(r, _mm_set_epi64x(0x1, 0x0100000000000000));
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn assert_eq_m256i(x: __m256i, y: __m256i) {
+    assert_eq!(std::mem::transmute::<_, [u64; 4]>(x), std::mem::transmute::<_, [u64; 4]>(y))
+}
+
 fn test_checked_mul() {
     let u: Option<u8> = u8::from_str_radix("1000", 10).ok();
     assert_eq!(u, None);
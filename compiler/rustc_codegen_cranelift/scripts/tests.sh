@@ -61,6 +61,14 @@ function base_sysroot_tests() {
     $MY_RUSTC example/std_example.rs --crate-type bin --target "$TARGET_TRIPLE"
     $RUN_WRAPPER ./target/out/std_example arg
 
+    if [[ "$TARGET_TRIPLE" == "x86_64-unknown-linux-gnu" ]]; then
+        echo "[AOT] inline_asm"
+        $MY_RUSTC example/inline_asm.rs --crate-type bin --target "$TARGET_TRIPLE"
+        $RUN_WRAPPER ./target/out/inline_asm
+    else
+        echo "[AOT] inline_asm (skipped)"
+    fi
+
     echo "[AOT] subslice-patterns-const-eval"
     $MY_RUSTC example/subslice-patterns-const-eval.rs --crate-type bin -Cpanic=abort --target "$TARGET_TRIPLE"
     $RUN_WRAPPER ./target/out/subslice-patterns-const-eval
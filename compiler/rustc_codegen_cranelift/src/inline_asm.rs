@@ -5,12 +5,14 @@
 use std::fmt::Write;
 
 use rustc_ast::ast::{InlineAsmOptions, InlineAsmTemplatePiece};
+use rustc_data_structures::fx::FxHashSet;
 use rustc_middle::mir::InlineAsmOperand;
+use rustc_span::Symbol;
 use rustc_target::asm::*;
 
 pub(crate) fn codegen_inline_asm<'tcx>(
     fx: &mut FunctionCx<'_, '_, 'tcx>,
-    _span: Span,
+    span: Span,
     template: &[InlineAsmTemplatePiece],
     operands: &[InlineAsmOperand<'tcx>],
     options: InlineAsmOptions,
@@ -96,170 +98,373 @@ pub(crate) fn codegen_inline_asm<'tcx>(
         crate::trap::trap_unimplemented(fx, "Alloca is not supported");
     }
 
-    let mut slot_size = Size::from_bytes(0);
-    let mut clobbered_regs = Vec::new();
-    let mut inputs = Vec::new();
-    let mut outputs = Vec::new();
+    if fx.tcx.sess.asm_arch != Some(InlineAsmArch::X86_64) {
+        fx.tcx.sess.span_fatal(span, "inline assembly is only supported on x86_64");
+    }
 
-    let mut new_slot = |reg_class: InlineAsmRegClass| {
-        let reg_size = reg_class
-            .supported_types(InlineAsmArch::X86_64)
-            .iter()
-            .map(|(ty, _)| ty.size())
-            .max()
-            .unwrap();
-        let align = rustc_target::abi::Align::from_bytes(reg_size.bytes()).unwrap();
-        slot_size = slot_size.align_to(align);
-        let offset = slot_size;
-        slot_size += reg_size;
-        offset
+    let operands = operands
+        .iter()
+        .map(|operand| match *operand {
+            InlineAsmOperand::In { reg, ref value } => {
+                CInlineAsmOperand::In { reg, value: crate::base::codegen_operand(fx, value) }
+            }
+            InlineAsmOperand::Out { reg, late, place } => CInlineAsmOperand::Out {
+                reg,
+                late,
+                place: place.map(|place| crate::base::codegen_place(fx, place)),
+            },
+            InlineAsmOperand::InOut { reg, late, ref in_value, out_place } => {
+                CInlineAsmOperand::InOut {
+                    reg,
+                    _late: late,
+                    in_value: crate::base::codegen_operand(fx, in_value),
+                    out_place: out_place.map(|place| crate::base::codegen_place(fx, place)),
+                }
+            }
+            InlineAsmOperand::Const { ref value } => {
+                let const_value = crate::constant::mir_operand_get_const_val(
+                    fx,
+                    &Operand::Constant(value.clone()),
+                )
+                .unwrap_or_else(|| span_bug!(span, "asm const cannot be resolved"));
+                let layout = fx.layout_of(fx.monomorphize(value.ty()));
+                let value =
+                    rustc_codegen_ssa::common::asm_const_to_str(fx.tcx, span, const_value, layout);
+                CInlineAsmOperand::Const { value }
+            }
+            InlineAsmOperand::SymFn { ref value } => {
+                let literal = fx.monomorphize(value.literal);
+                if let ty::FnDef(def_id, substs) = *literal.ty().kind() {
+                    let instance = ty::Instance::resolve_for_fn_ptr(
+                        fx.tcx,
+                        ty::ParamEnv::reveal_all(),
+                        def_id,
+                        substs,
+                    )
+                    .unwrap();
+                    let symbol = fx.tcx.symbol_name(instance).name.to_owned();
+                    CInlineAsmOperand::Symbol { symbol }
+                } else {
+                    span_bug!(span, "invalid type for asm sym (fn)");
+                }
+            }
+            InlineAsmOperand::SymStatic { def_id } => {
+                let instance = Instance::mono(fx.tcx, def_id);
+                let symbol = fx.tcx.symbol_name(instance).name.to_owned();
+                CInlineAsmOperand::Symbol { symbol }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut asm_gen = InlineAssemblyGenerator {
+        tcx: fx.tcx,
+        arch: InlineAsmArch::X86_64,
+        template,
+        operands: &operands,
+        options,
+        registers: Vec::new(),
+        stack_slots_clobber: Vec::new(),
+        stack_slots_input: Vec::new(),
+        stack_slots_output: Vec::new(),
+        stack_slot_size: Size::from_bytes(0),
     };
+    asm_gen.allocate_registers();
+    asm_gen.allocate_stack_slots();
+
+    let inline_asm_index = fx.inline_asm_index;
+    fx.inline_asm_index += 1;
+    let asm_name = format!("{}__inline_asm_{}", fx.symbol_name, inline_asm_index);
 
-    // FIXME overlap input and output slots to save stack space
-    for operand in operands {
+    let generated_asm = asm_gen.generate_asm_wrapper(&asm_name);
+    fx.cx.global_asm.push_str(&generated_asm);
+
+    let mut inputs = Vec::new();
+    let mut outputs = Vec::new();
+    for (i, operand) in operands.iter().enumerate() {
         match *operand {
-            InlineAsmOperand::In { reg, ref value } => {
-                let reg = expect_reg(reg);
-                clobbered_regs.push((reg, new_slot(reg.reg_class())));
-                inputs.push((
-                    reg,
-                    new_slot(reg.reg_class()),
-                    crate::base::codegen_operand(fx, value).load_scalar(fx),
-                ));
+            CInlineAsmOperand::In { reg: _, value } => {
+                inputs.push((asm_gen.stack_slots_input[i].unwrap(), value));
             }
-            InlineAsmOperand::Out { reg, late: _, place } => {
-                let reg = expect_reg(reg);
-                clobbered_regs.push((reg, new_slot(reg.reg_class())));
+            CInlineAsmOperand::Out { reg: _, late: _, place } => {
                 if let Some(place) = place {
-                    outputs.push((
-                        reg,
-                        new_slot(reg.reg_class()),
-                        crate::base::codegen_place(fx, place),
-                    ));
+                    outputs.push((asm_gen.stack_slots_output[i].unwrap(), place));
                 }
             }
-            InlineAsmOperand::InOut { reg, late: _, ref in_value, out_place } => {
-                let reg = expect_reg(reg);
-                clobbered_regs.push((reg, new_slot(reg.reg_class())));
-                inputs.push((
-                    reg,
-                    new_slot(reg.reg_class()),
-                    crate::base::codegen_operand(fx, in_value).load_scalar(fx),
-                ));
+            CInlineAsmOperand::InOut { reg: _, _late: _, in_value, out_place } => {
+                inputs.push((asm_gen.stack_slots_input[i].unwrap(), in_value));
                 if let Some(out_place) = out_place {
-                    outputs.push((
-                        reg,
-                        new_slot(reg.reg_class()),
-                        crate::base::codegen_place(fx, out_place),
-                    ));
+                    outputs.push((asm_gen.stack_slots_output[i].unwrap(), out_place));
                 }
             }
-            InlineAsmOperand::Const { value: _ } => todo!(),
-            InlineAsmOperand::SymFn { value: _ } => todo!(),
-            InlineAsmOperand::SymStatic { def_id: _ } => todo!(),
+            CInlineAsmOperand::Const { .. } | CInlineAsmOperand::Symbol { .. } => {}
         }
     }
 
-    let inline_asm_index = fx.inline_asm_index;
-    fx.inline_asm_index += 1;
-    let asm_name = format!("{}__inline_asm_{}", fx.symbol_name, inline_asm_index);
+    call_inline_asm(fx, &asm_name, asm_gen.stack_slot_size, inputs, outputs);
+}
 
-    let generated_asm = generate_asm_wrapper(
-        &asm_name,
-        InlineAsmArch::X86_64,
-        options,
-        template,
-        clobbered_regs,
-        &inputs,
-        &outputs,
-    );
-    fx.cx.global_asm.push_str(&generated_asm);
+/// An [`InlineAsmOperand`] after its values have been codegened and its
+/// constants and symbols have been turned into the text they are replaced with.
+enum CInlineAsmOperand<'tcx> {
+    In {
+        reg: InlineAsmRegOrRegClass,
+        value: CValue<'tcx>,
+    },
+    Out {
+        reg: InlineAsmRegOrRegClass,
+        late: bool,
+        place: Option<CPlace<'tcx>>,
+    },
+    InOut {
+        reg: InlineAsmRegOrRegClass,
+        _late: bool,
+        in_value: CValue<'tcx>,
+        out_place: Option<CPlace<'tcx>>,
+    },
+    Const {
+        value: String,
+    },
+    Symbol {
+        symbol: String,
+    },
+}
 
-    call_inline_asm(fx, &asm_name, slot_size, inputs, outputs);
+impl CInlineAsmOperand<'_> {
+    fn reg(&self) -> Option<InlineAsmRegOrRegClass> {
+        match *self {
+            CInlineAsmOperand::In { reg, .. }
+            | CInlineAsmOperand::Out { reg, .. }
+            | CInlineAsmOperand::InOut { reg, .. } => Some(reg),
+            CInlineAsmOperand::Const { .. } | CInlineAsmOperand::Symbol { .. } => None,
+        }
+    }
 }
 
-fn generate_asm_wrapper(
-    asm_name: &str,
+struct InlineAssemblyGenerator<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
     arch: InlineAsmArch,
+    template: &'a [InlineAsmTemplatePiece],
+    operands: &'a [CInlineAsmOperand<'tcx>],
     options: InlineAsmOptions,
-    template: &[InlineAsmTemplatePiece],
-    clobbered_regs: Vec<(InlineAsmReg, Size)>,
-    inputs: &[(InlineAsmReg, Size, Value)],
-    outputs: &[(InlineAsmReg, Size, CPlace<'_>)],
-) -> String {
-    let mut generated_asm = String::new();
-    writeln!(generated_asm, ".globl {}", asm_name).unwrap();
-    writeln!(generated_asm, ".type {},@function", asm_name).unwrap();
-    writeln!(generated_asm, ".section .text.{},\"ax\",@progbits", asm_name).unwrap();
-    writeln!(generated_asm, "{}:", asm_name).unwrap();
-
-    generated_asm.push_str(".intel_syntax noprefix\n");
-    generated_asm.push_str("    push rbp\n");
-    generated_asm.push_str("    mov rbp,rdi\n");
-
-    // Save clobbered registers
-    if !options.contains(InlineAsmOptions::NORETURN) {
-        // FIXME skip registers saved by the calling convention
-        for &(reg, offset) in &clobbered_regs {
-            save_register(&mut generated_asm, arch, reg, offset);
+    /// The register assigned to each operand, indexed like `operands`.
+    registers: Vec<Option<InlineAsmReg>>,
+    stack_slots_clobber: Vec<Option<Size>>,
+    stack_slots_input: Vec<Option<Size>>,
+    stack_slots_output: Vec<Option<Size>>,
+    stack_slot_size: Size,
+}
+
+impl<'tcx> InlineAssemblyGenerator<'_, 'tcx> {
+    fn allocate_registers(&mut self) {
+        let sess = self.tcx.sess;
+        let map = allocatable_registers(
+            self.arch,
+            |feature| sess.target_features.contains(&Symbol::intern(feature)),
+            &sess.target,
+        );
+        let mut used = FxHashSet::default();
+        let mut registers = vec![None; self.operands.len()];
+
+        // Explicit registers are fixed, so reserve them first.
+        for (i, operand) in self.operands.iter().enumerate() {
+            if let Some(InlineAsmRegOrRegClass::Reg(reg)) = operand.reg() {
+                registers[i] = Some(reg);
+                reg.overlapping_regs(|r| {
+                    used.insert(r);
+                });
+            }
         }
-    }
 
-    // Write input registers
-    for &(reg, offset, _value) in inputs {
-        restore_register(&mut generated_asm, arch, reg, offset);
-    }
+        // FIXME let late outputs share a register with an input
+        for (i, operand) in self.operands.iter().enumerate() {
+            let class = match operand.reg() {
+                Some(InlineAsmRegOrRegClass::RegClass(class)) => class,
+                _ => continue,
+            };
+
+            // Sort the candidates to pick registers deterministically.
+            let mut candidates: Vec<InlineAsmReg> = map
+                .get(&class)
+                .into_iter()
+                .flatten()
+                .copied()
+                .filter(|reg| {
+                    let mut free = true;
+                    reg.overlapping_regs(|r| free &= !used.contains(&r));
+                    free
+                })
+                .collect();
+            candidates.sort_by_key(|reg| reg.name());
+
+            let reg = match candidates.first() {
+                Some(&reg) => reg,
+                None => sess.fatal(&format!(
+                    "no free register of class `{}` left for inline assembly operand",
+                    class.name()
+                )),
+            };
+            registers[i] = Some(reg);
+            reg.overlapping_regs(|r| {
+                used.insert(r);
+            });
+        }
 
-    if options.contains(InlineAsmOptions::ATT_SYNTAX) {
-        generated_asm.push_str(".att_syntax\n");
+        self.registers = registers;
     }
 
-    // The actual inline asm
-    for piece in template {
-        match piece {
-            InlineAsmTemplatePiece::String(s) => {
-                generated_asm.push_str(s);
+    fn allocate_stack_slots(&mut self) {
+        let arch = self.arch;
+        let mut slot_size = Size::from_bytes(0);
+        let mut new_slot = |reg_class: InlineAsmRegClass| {
+            let reg_size =
+                reg_class.supported_types(arch).iter().map(|(ty, _)| ty.size()).max().unwrap();
+            let align = rustc_target::abi::Align::from_bytes(reg_size.bytes()).unwrap();
+            slot_size = slot_size.align_to(align);
+            let offset = slot_size;
+            slot_size += reg_size;
+            offset
+        };
+
+        let mut slots_clobber = vec![None; self.operands.len()];
+        let mut slots_input = vec![None; self.operands.len()];
+        let mut slots_output = vec![None; self.operands.len()];
+
+        // FIXME overlap input and output slots to save stack space
+        for (i, operand) in self.operands.iter().enumerate() {
+            let reg = match self.registers[i] {
+                Some(reg) => reg,
+                None => continue,
+            };
+            let reg_class = reg.reg_class();
+            // The wrapper is called like any other function, so only the registers the
+            // calling convention requires it to preserve have to be saved. This also skips
+            // the clobber-only register classes of `clobber_abi`, which can't be saved.
+            if is_callee_saved(reg) {
+                slots_clobber[i] = Some(new_slot(reg_class));
+            }
+            match *operand {
+                CInlineAsmOperand::In { .. } => slots_input[i] = Some(new_slot(reg_class)),
+                CInlineAsmOperand::Out { place, .. } => {
+                    if place.is_some() {
+                        slots_output[i] = Some(new_slot(reg_class));
+                    }
+                }
+                CInlineAsmOperand::InOut { out_place, .. } => {
+                    // The input is read before the output is written, so both
+                    // can use the same slot.
+                    let slot = new_slot(reg_class);
+                    slots_input[i] = Some(slot);
+                    if out_place.is_some() {
+                        slots_output[i] = Some(slot);
+                    }
+                }
+                CInlineAsmOperand::Const { .. } | CInlineAsmOperand::Symbol { .. } => {}
             }
-            InlineAsmTemplatePiece::Placeholder { operand_idx: _, modifier: _, span: _ } => todo!(),
         }
+
+        self.stack_slots_clobber = slots_clobber;
+        self.stack_slots_input = slots_input;
+        self.stack_slots_output = slots_output;
+        self.stack_slot_size = slot_size;
     }
-    generated_asm.push('\n');
 
-    if options.contains(InlineAsmOptions::ATT_SYNTAX) {
+    fn generate_asm_wrapper(&self, asm_name: &str) -> String {
+        let mut generated_asm = String::new();
+        writeln!(generated_asm, ".globl {}", asm_name).unwrap();
+        writeln!(generated_asm, ".type {},@function", asm_name).unwrap();
+        writeln!(generated_asm, ".section .text.{},\"ax\",@progbits", asm_name).unwrap();
+        writeln!(generated_asm, "{}:", asm_name).unwrap();
+
         generated_asm.push_str(".intel_syntax noprefix\n");
-    }
+        generated_asm.push_str("    push rbp\n");
+        generated_asm.push_str("    mov rbp,rdi\n");
+
+        let with_slots = |slots: &[Option<Size>]| {
+            self.registers
+                .iter()
+                .zip(slots)
+                .filter_map(|(&reg, &slot)| reg.zip(slot))
+                .collect::<Vec<_>>()
+        };
+        let clobbered_regs = with_slots(&self.stack_slots_clobber);
 
-    if !options.contains(InlineAsmOptions::NORETURN) {
-        // Read output registers
-        for &(reg, offset, _place) in outputs {
-            save_register(&mut generated_asm, arch, reg, offset);
+        // Save clobbered registers
+        if !self.options.contains(InlineAsmOptions::NORETURN) {
+            for &(reg, offset) in &clobbered_regs {
+                save_register(&mut generated_asm, self.arch, reg, offset);
+            }
         }
 
-        // Restore clobbered registers
-        for &(reg, offset) in clobbered_regs.iter().rev() {
-            restore_register(&mut generated_asm, arch, reg, offset);
+        // Write input registers
+        for (reg, offset) in with_slots(&self.stack_slots_input) {
+            restore_register(&mut generated_asm, self.arch, reg, offset);
         }
 
-        generated_asm.push_str("    pop rbp\n");
-        generated_asm.push_str("    ret\n");
-    } else {
-        generated_asm.push_str("    ud2\n");
-    }
+        if self.options.contains(InlineAsmOptions::ATT_SYNTAX) {
+            generated_asm.push_str(".att_syntax\n");
+        }
 
-    generated_asm.push_str(".att_syntax\n");
-    writeln!(generated_asm, ".size {name}, .-{name}", name = asm_name).unwrap();
-    generated_asm.push_str(".text\n");
-    generated_asm.push_str("\n\n");
+        // The actual inline asm
+        for piece in self.template {
+            match *piece {
+                InlineAsmTemplatePiece::String(ref s) => {
+                    generated_asm.push_str(s);
+                }
+                InlineAsmTemplatePiece::Placeholder { operand_idx, modifier, span: _ } => {
+                    match self.operands[operand_idx] {
+                        CInlineAsmOperand::Const { ref value } => generated_asm.push_str(value),
+                        CInlineAsmOperand::Symbol { ref symbol } => generated_asm.push_str(symbol),
+                        _ => {
+                            if self.options.contains(InlineAsmOptions::ATT_SYNTAX) {
+                                generated_asm.push('%');
+                            }
+                            self.registers[operand_idx]
+                                .unwrap()
+                                .emit(&mut generated_asm, self.arch, modifier)
+                                .unwrap();
+                        }
+                    }
+                }
+            }
+        }
+        generated_asm.push('\n');
+
+        if self.options.contains(InlineAsmOptions::ATT_SYNTAX) {
+            generated_asm.push_str(".intel_syntax noprefix\n");
+        }
 
-    generated_asm
+        if !self.options.contains(InlineAsmOptions::NORETURN) {
+            // Read output registers
+            for (reg, offset) in with_slots(&self.stack_slots_output) {
+                save_register(&mut generated_asm, self.arch, reg, offset);
+            }
+
+            // Restore clobbered registers
+            for &(reg, offset) in clobbered_regs.iter().rev() {
+                restore_register(&mut generated_asm, self.arch, reg, offset);
+            }
+
+            generated_asm.push_str("    pop rbp\n");
+            generated_asm.push_str("    ret\n");
+        } else {
+            generated_asm.push_str("    ud2\n");
+        }
+
+        generated_asm.push_str(".att_syntax\n");
+        writeln!(generated_asm, ".size {name}, .-{name}", name = asm_name).unwrap();
+        generated_asm.push_str(".text\n");
+        generated_asm.push_str("\n\n");
+
+        generated_asm
+    }
 }
 
 fn call_inline_asm<'tcx>(
     fx: &mut FunctionCx<'_, '_, 'tcx>,
     asm_name: &str,
     slot_size: Size,
-    inputs: Vec<(InlineAsmReg, Size, Value)>,
-    outputs: Vec<(InlineAsmReg, Size, CPlace<'tcx>)>,
+    inputs: Vec<(Size, CValue<'tcx>)>,
+    outputs: Vec<(Size, CPlace<'tcx>)>,
 ) {
     let stack_slot = fx.bcx.func.create_stack_slot(StackSlotData {
         kind: StackSlotKind::ExplicitSlot,
@@ -287,17 +492,19 @@ fn call_inline_asm<'tcx>(
         fx.add_comment(inline_asm_func, asm_name);
     }
 
-    for (_reg, offset, value) in inputs {
-        fx.bcx.ins().stack_store(value, stack_slot, i32::try_from(offset.bytes()).unwrap());
+    // Going through memory handles values of every size, including vectors
+    // that don't fit into a single Cranelift value.
+    for (offset, value) in inputs {
+        let ptr = Pointer::stack_slot(stack_slot).offset_i64(fx, offset.bytes() as i64);
+        CPlace::for_ptr(ptr, value.layout()).write_cvalue(fx, value);
     }
 
     let stack_slot_addr = fx.bcx.ins().stack_addr(fx.pointer_type, stack_slot, 0);
     fx.bcx.ins().call(inline_asm_func, &[stack_slot_addr]);
 
-    for (_reg, offset, place) in outputs {
-        let ty = fx.clif_type(place.layout().ty).unwrap();
-        let value = fx.bcx.ins().stack_load(ty, stack_slot, i32::try_from(offset.bytes()).unwrap());
-        place.write_cvalue(fx, CValue::by_val(value, place.layout()));
+    for (offset, place) in outputs {
+        let ptr = Pointer::stack_slot(stack_slot).offset_i64(fx, offset.bytes() as i64);
+        place.write_cvalue(fx, CValue::by_ref(ptr, place.layout()));
     }
 }
 
@@ -308,10 +515,39 @@ fn expect_reg(reg_or_class: InlineAsmRegOrRegClass) -> InlineAsmReg {
     }
 }
 
+/// Returns `true` if the System V calling convention, which is used to call the function
+/// wrapping the assembly, requires `reg` to be preserved across calls.
+fn is_callee_saved(reg: InlineAsmReg) -> bool {
+    match reg {
+        InlineAsmReg::X86(reg) => matches!(
+            reg,
+            X86InlineAsmReg::bx
+                | X86InlineAsmReg::r12
+                | X86InlineAsmReg::r13
+                | X86InlineAsmReg::r14
+                | X86InlineAsmReg::r15
+        ),
+        _ => true,
+    }
+}
+
+/// Returns the instruction that moves a value of the full width of `reg`
+/// between the register and memory.
+fn x86_mov_instruction(reg: InlineAsmReg) -> &'static str {
+    match reg.reg_class() {
+        InlineAsmRegClass::X86(X86InlineAsmRegClass::xmm_reg) => "movups",
+        InlineAsmRegClass::X86(X86InlineAsmRegClass::ymm_reg)
+        | InlineAsmRegClass::X86(X86InlineAsmRegClass::zmm_reg) => "vmovups",
+        InlineAsmRegClass::X86(X86InlineAsmRegClass::kreg) => "kmovq",
+        _ => "mov",
+    }
+}
+
 fn save_register(generated_asm: &mut String, arch: InlineAsmArch, reg: InlineAsmReg, offset: Size) {
     match arch {
         InlineAsmArch::X86_64 => {
-            write!(generated_asm, "    mov [rbp+0x{:x}], ", offset.bytes()).unwrap();
+            let mov = x86_mov_instruction(reg);
+            write!(generated_asm, "    {} [rbp+0x{:x}], ", mov, offset.bytes()).unwrap();
             reg.emit(generated_asm, InlineAsmArch::X86_64, None).unwrap();
             generated_asm.push('\n');
         }
@@ -327,7 +563,7 @@ fn restore_register(
 ) {
     match arch {
         InlineAsmArch::X86_64 => {
-            generated_asm.push_str("    mov ");
+            write!(generated_asm, "    {} ", x86_mov_instruction(reg)).unwrap();
             reg.emit(generated_asm, InlineAsmArch::X86_64, None).unwrap();
             writeln!(generated_asm, ", [rbp+0x{:x}]", offset.bytes()).unwrap();
         }
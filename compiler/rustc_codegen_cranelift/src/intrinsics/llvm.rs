@@ -81,26 +81,187 @@ pub(crate) fn codegen_llvm_intrinsic_call<'tcx>(
                 bool_to_zero_or_max_uint(fx, res_lane_layout, res_lane)
             });
         };
-        "llvm.x86.sse2.psrli.d", (c a, o imm8) {
-            let imm8 = crate::constant::mir_operand_get_const_val(fx, imm8).expect("llvm.x86.sse2.psrli.d imm8 not const");
-            simd_for_each_lane(fx, a, ret, |fx, _lane_layout, res_lane_layout, lane| {
-                let res_lane = match imm8.try_to_bits(Size::from_bytes(4)).unwrap_or_else(|| panic!("imm8 not scalar: {:?}", imm8)) {
-                    imm8 if imm8 < 32 => fx.bcx.ins().ushr_imm(lane, i64::from(imm8 as u8)),
-                    _ => fx.bcx.ins().iconst(types::I32, 0),
+        "llvm.x86.sse2.psrli.w" | "llvm.x86.sse2.psrli.d" | "llvm.x86.sse2.psrli.q"
+        | "llvm.x86.avx2.psrli.w" | "llvm.x86.avx2.psrli.d" | "llvm.x86.avx2.psrli.q", (c a, o imm8) {
+            let imm8 = crate::constant::mir_operand_get_const_val(fx, imm8).expect("llvm.x86.*.psrli.* imm8 not const");
+            let imm8 = imm8.try_to_bits(Size::from_bytes(4)).unwrap_or_else(|| panic!("imm8 not scalar: {:?}", imm8));
+            simd_for_each_lane(fx, a, ret, |fx, lane_layout, res_lane_layout, lane| {
+                let lane_ty = fx.clif_type(lane_layout.ty).unwrap();
+                let res_lane = if imm8 < u128::from(lane_ty.bits()) {
+                    fx.bcx.ins().ushr_imm(lane, i64::from(imm8 as u8))
+                } else {
+                    fx.bcx.ins().iconst(lane_ty, 0)
                 };
                 CValue::by_val(res_lane, res_lane_layout)
             });
         };
-        "llvm.x86.sse2.pslli.d", (c a, o imm8) {
-            let imm8 = crate::constant::mir_operand_get_const_val(fx, imm8).expect("llvm.x86.sse2.psrli.d imm8 not const");
-            simd_for_each_lane(fx, a, ret, |fx, _lane_layout, res_lane_layout, lane| {
-                let res_lane = match imm8.try_to_bits(Size::from_bytes(4)).unwrap_or_else(|| panic!("imm8 not scalar: {:?}", imm8)) {
-                    imm8 if imm8 < 32 => fx.bcx.ins().ishl_imm(lane, i64::from(imm8 as u8)),
-                    _ => fx.bcx.ins().iconst(types::I32, 0),
+        "llvm.x86.sse2.pslli.w" | "llvm.x86.sse2.pslli.d" | "llvm.x86.sse2.pslli.q"
+        | "llvm.x86.avx2.pslli.w" | "llvm.x86.avx2.pslli.d" | "llvm.x86.avx2.pslli.q", (c a, o imm8) {
+            let imm8 = crate::constant::mir_operand_get_const_val(fx, imm8).expect("llvm.x86.*.pslli.* imm8 not const");
+            let imm8 = imm8.try_to_bits(Size::from_bytes(4)).unwrap_or_else(|| panic!("imm8 not scalar: {:?}", imm8));
+            simd_for_each_lane(fx, a, ret, |fx, lane_layout, res_lane_layout, lane| {
+                let lane_ty = fx.clif_type(lane_layout.ty).unwrap();
+                let res_lane = if imm8 < u128::from(lane_ty.bits()) {
+                    fx.bcx.ins().ishl_imm(lane, i64::from(imm8 as u8))
+                } else {
+                    fx.bcx.ins().iconst(lane_ty, 0)
                 };
                 CValue::by_val(res_lane, res_lane_layout)
             });
         };
+        "llvm.x86.sse2.psrai.w" | "llvm.x86.sse2.psrai.d"
+        | "llvm.x86.avx2.psrai.w" | "llvm.x86.avx2.psrai.d", (c a, o imm8) {
+            let imm8 = crate::constant::mir_operand_get_const_val(fx, imm8).expect("llvm.x86.*.psrai.* imm8 not const");
+            let imm8 = imm8.try_to_bits(Size::from_bytes(4)).unwrap_or_else(|| panic!("imm8 not scalar: {:?}", imm8));
+            simd_for_each_lane(fx, a, ret, |fx, lane_layout, res_lane_layout, lane| {
+                let lane_ty = fx.clif_type(lane_layout.ty).unwrap();
+                // Shifting by the lane width or more fills the lane with the sign bit.
+                let shift = std::cmp::min(imm8, u128::from(lane_ty.bits() - 1));
+                let res_lane = fx.bcx.ins().sshr_imm(lane, shift as i64);
+                CValue::by_val(res_lane, res_lane_layout)
+            });
+        };
+        "llvm.x86.ssse3.pshuf.b.128" | "llvm.x86.avx2.pshuf.b", (c a, c b) {
+            let (lane_count, lane_ty) = a.layout().ty.simd_size_and_type(fx.tcx);
+            let lane_layout = fx.layout_of(lane_ty);
+            let lane_ty = fx.clif_type(lane_ty).unwrap();
+            let (a_ptr, _) = a.force_stack(fx);
+
+            for lane in 0..lane_count {
+                let idx = b.value_lane(fx, lane).load_scalar(fx);
+
+                // Every 128-bit block is shuffled on its own.
+                let block_start = fx.bcx.ins().iconst(fx.pointer_type, (lane & !0xf) as i64);
+                let idx_in_block = fx.bcx.ins().band_imm(idx, 0xf);
+                let idx_in_block = fx.bcx.ins().uextend(fx.pointer_type, idx_in_block);
+                let offset = fx.bcx.ins().iadd(block_start, idx_in_block);
+                let byte = a_ptr.offset_value(fx, offset).load(fx, lane_ty, MemFlags::trusted());
+
+                // The lane is zeroed if the high bit of the index is set.
+                let zero = fx.bcx.ins().iconst(lane_ty, 0);
+                let high_bit = fx.bcx.ins().band_imm(idx, 0x80);
+                let res_lane = fx.bcx.ins().select(high_bit, zero, byte);
+                ret.place_lane(fx, lane).write_cvalue(fx, CValue::by_val(res_lane, lane_layout));
+            }
+        };
+        "llvm.x86.avx2.vperm2i128", (c a, c b, o imm8) {
+            let imm8 = crate::constant::mir_operand_get_const_val(fx, imm8).expect("llvm.x86.avx2.vperm2i128 imm8 not const");
+            let imm8 = imm8.try_to_bits(Size::from_bytes(1)).unwrap_or_else(|| panic!("imm8 not scalar: {:?}", imm8));
+            let (lane_count, lane_ty) = ret.layout().ty.simd_size_and_type(fx.tcx);
+            assert_eq!(lane_count, 4);
+            let lane_layout = fx.layout_of(lane_ty);
+            let lane_ty = fx.clif_type(lane_ty).unwrap();
+
+            // Every nibble of `imm8` selects one of the four 128-bit halves of
+            // `a` and `b`, or zero if its high bit is set.
+            for (half, control) in [imm8 & 0xf, imm8 >> 4].iter().copied().enumerate() {
+                for lane in 0..2 {
+                    let res_lane = if control & 0x8 != 0 {
+                        CValue::by_val(fx.bcx.ins().iconst(lane_ty, 0), lane_layout)
+                    } else {
+                        let src = if control & 0x2 == 0 { a } else { b };
+                        src.value_lane(fx, (control & 0x1) as u64 * 2 + lane)
+                    };
+                    ret.place_lane(fx, half as u64 * 2 + lane).write_cvalue(fx, res_lane);
+                }
+            }
+        };
+        "llvm.x86.sse2.packuswb.128" | "llvm.x86.sse2.packsswb.128" | "llvm.x86.sse2.packssdw.128"
+        | "llvm.x86.sse41.packusdw" | "llvm.x86.avx2.packuswb" | "llvm.x86.avx2.packsswb"
+        | "llvm.x86.avx2.packssdw" | "llvm.x86.avx2.packusdw", (c a, c b) {
+            let (lane_count, lane_ty) = a.layout().ty.simd_size_and_type(fx.tcx);
+            let lane_ty = fx.clif_type(lane_ty).unwrap();
+            let (_, res_lane_ty) = ret.layout().ty.simd_size_and_type(fx.tcx);
+            let res_lane_layout = fx.layout_of(res_lane_ty);
+            let res_signed = matches!(res_lane_ty.kind(), ty::Int(_));
+            let res_lane_ty = fx.clif_type(res_lane_ty).unwrap();
+
+            // The bounds of the result lane type, as values of the input lane type.
+            let res_bits = res_lane_ty.bits();
+            let (min, max) = if res_signed {
+                (-(1i64 << (res_bits - 1)), (1i64 << (res_bits - 1)) - 1)
+            } else {
+                (0, (1i64 << res_bits) - 1)
+            };
+
+            // Both inputs are packed separately for every 128-bit block.
+            let lanes_per_block = 128 / u64::from(lane_ty.bits());
+            for block in 0..lane_count / lanes_per_block {
+                for (i, src) in [a, b].iter().enumerate() {
+                    for lane in 0..lanes_per_block {
+                        let src_lane = src.value_lane(fx, block * lanes_per_block + lane).load_scalar(fx);
+                        let too_small = fx.bcx.ins().icmp_imm(IntCC::SignedLessThan, src_lane, min);
+                        let min_val = fx.bcx.ins().iconst(lane_ty, min);
+                        let res_lane = fx.bcx.ins().select(too_small, min_val, src_lane);
+                        let too_large = fx.bcx.ins().icmp_imm(IntCC::SignedGreaterThan, res_lane, max);
+                        let max_val = fx.bcx.ins().iconst(lane_ty, max);
+                        let res_lane = fx.bcx.ins().select(too_large, max_val, res_lane);
+                        let res_lane = fx.bcx.ins().ireduce(res_lane_ty, res_lane);
+
+                        let res_idx = (block * 2 + i as u64) * lanes_per_block + lane;
+                        ret.place_lane(fx, res_idx).write_cvalue(fx, CValue::by_val(res_lane, res_lane_layout));
+                    }
+                }
+            }
+        };
+        "llvm.x86.sse41.ptestz" | "llvm.x86.sse41.ptestc" | "llvm.x86.avx.ptestz.256"
+        | "llvm.x86.avx.ptestc.256", (c a, c mask) {
+            // `ptestz` checks whether `a & mask` is zero, `ptestc` whether `!a & mask` is.
+            let invert_a = intrinsic.contains("ptestc");
+            let (lane_count, lane_ty) = a.layout().ty.simd_size_and_type(fx.tcx);
+            let lane_ty = fx.clif_type(lane_ty).unwrap();
+
+            let mut acc = fx.bcx.ins().iconst(lane_ty, 0);
+            for lane in 0..lane_count {
+                let a_lane = a.value_lane(fx, lane).load_scalar(fx);
+                let mask_lane = mask.value_lane(fx, lane).load_scalar(fx);
+                let masked = if invert_a {
+                    fx.bcx.ins().band_not(mask_lane, a_lane)
+                } else {
+                    fx.bcx.ins().band(a_lane, mask_lane)
+                };
+                acc = fx.bcx.ins().bor(acc, masked);
+            }
+
+            let is_zero = fx.bcx.ins().icmp_imm(IntCC::Equal, acc, 0);
+            let res = fx.bcx.ins().bint(types::I32, is_zero);
+            ret.write_cvalue(fx, CValue::by_val(res, fx.layout_of(fx.tcx.types.i32)));
+        };
+        "llvm.x86.pclmulqdq", (c a, c b, o imm8) {
+            let imm8 = crate::constant::mir_operand_get_const_val(fx, imm8).expect("llvm.x86.pclmulqdq imm8 not const");
+            let imm8 = imm8.try_to_bits(Size::from_bytes(1)).unwrap_or_else(|| panic!("imm8 not scalar: {:?}", imm8));
+            let (_, lane_ty) = ret.layout().ty.simd_size_and_type(fx.tcx);
+            let lane_layout = fx.layout_of(lane_ty);
+
+            let a_lane = a.value_lane(fx, (imm8 & 0x1) as u64).load_scalar(fx);
+            let b_lane = b.value_lane(fx, ((imm8 >> 4) & 0x1) as u64).load_scalar(fx);
+
+            // Carry-less multiplication: xor `a` shifted by `i` into the
+            // 128-bit result for every bit `i` that is set in `b`.
+            let mut lo = fx.bcx.ins().iconst(types::I64, 0);
+            let mut hi = fx.bcx.ins().iconst(types::I64, 0);
+            for i in 0..64i64 {
+                let bit = fx.bcx.ins().ushr_imm(b_lane, i);
+                let bit = fx.bcx.ins().band_imm(bit, 1);
+                let mask = fx.bcx.ins().ineg(bit);
+
+                let lo_part = fx.bcx.ins().ishl_imm(a_lane, i);
+                let lo_part = fx.bcx.ins().band(lo_part, mask);
+                lo = fx.bcx.ins().bxor(lo, lo_part);
+
+                if i != 0 {
+                    let hi_part = fx.bcx.ins().ushr_imm(a_lane, 64 - i);
+                    let hi_part = fx.bcx.ins().band(hi_part, mask);
+                    hi = fx.bcx.ins().bxor(hi, hi_part);
+                }
+            }
+
+            ret.place_lane(fx, 0).write_cvalue(fx, CValue::by_val(lo, lane_layout));
+            ret.place_lane(fx, 1).write_cvalue(fx, CValue::by_val(hi, lane_layout));
+        };
+        "llvm.x86.sse2.pause", () {
+            // A spin loop hint has no observable effect.
+        };
         "llvm.x86.sse2.storeu.dq", (v mem_addr, c a) {
             // FIXME correctly handle the unalignment
             let dest = CPlace::for_ptr(Pointer::new(mem_addr), a.layout());
@@ -136,12 +297,6 @@ pub(crate) fn codegen_llvm_intrinsic_call<'tcx>(
     }
 }
 
-// llvm.x86.avx2.vperm2i128
-// llvm.x86.ssse3.pshuf.b.128
-// llvm.x86.avx2.pshuf.b
-// llvm.x86.avx2.psrli.w
-// llvm.x86.sse2.psrli.w
-
 fn llvm_add_sub<'tcx>(
     fx: &mut FunctionCx<'_, '_, 'tcx>,
     bin_op: BinOp,
@@ -11,6 +11,7 @@
 use crate::LlvmCodegenBackend;
 use crate::ModuleLlvm;
 use rustc_codegen_ssa::back::link::ensure_removed;
+use rustc_codegen_ssa::back::remarks::OptimizationRemark;
 use rustc_codegen_ssa::back::write::{
     BitcodeSection, CodegenContext, EmitObj, ModuleConfig, TargetMachineFactoryConfig,
     TargetMachineFactoryFn,
@@ -180,7 +181,8 @@ pub fn target_machine_factory(
     let abi = SmallCStr::new(&sess.target.llvm_abiname);
     let trap_unreachable =
         sess.opts.debugging_opts.trap_unreachable.unwrap_or(sess.target.trap_unreachable);
-    let emit_stack_size_section = sess.opts.debugging_opts.emit_stack_sizes;
+    let emit_stack_size_section = sess.opts.debugging_opts.emit_stack_sizes
+        || sess.opts.debugging_opts.emit_stack_sizes_report;
 
     let asm_comments = sess.asm_comments();
     let relax_elf_relocations =
@@ -250,10 +252,26 @@ pub fn new(
         handler: &'a Handler,
         llcx: &'a llvm::Context,
     ) -> Self {
+        let (remark_all_passes, remark_passes) = match &cgcx.remark {
+            Passes::All => (true, Vec::new()),
+            Passes::Some(passes) => {
+                (false, passes.iter().map(|pass| CString::new(pass.as_str()).unwrap()).collect())
+            }
+        };
+        let remark_passes: Vec<*const c_char> =
+            remark_passes.iter().map(|pass| pass.as_ptr()).collect();
+
         let data = Box::into_raw(Box::new((cgcx, handler)));
         unsafe {
             llvm::LLVMRustSetInlineAsmDiagnosticHandler(llcx, inline_asm_handler, data.cast());
-            llvm::LLVMContextSetDiagnosticHandler(llcx, diagnostic_handler, data.cast());
+            llvm::LLVMRustContextConfigureDiagnosticHandler(
+                llcx,
+                diagnostic_handler,
+                data.cast(),
+                remark_all_passes,
+                remark_passes.as_ptr(),
+                remark_passes.len(),
+            );
         }
         DiagnosticHandlers { data, llcx }
     }
@@ -264,7 +282,14 @@ fn drop(&mut self) {
         use std::ptr::null_mut;
         unsafe {
             llvm::LLVMRustSetInlineAsmDiagnosticHandler(self.llcx, inline_asm_handler, null_mut());
-            llvm::LLVMContextSetDiagnosticHandler(self.llcx, diagnostic_handler, null_mut());
+            llvm::LLVMRustContextConfigureDiagnosticHandler(
+                self.llcx,
+                diagnostic_handler,
+                null_mut(),
+                false,
+                std::ptr::null(),
+                0,
+            );
             drop(Box::from_raw(self.data));
         }
     }
@@ -319,15 +344,16 @@ fn report_inline_asm(
             };
 
             if enabled {
-                diag_handler.note_without_error(&format!(
-                    "optimization {} for {} at {}:{}:{}: {}",
-                    opt.kind.describe(),
-                    opt.pass_name,
-                    opt.filename,
-                    opt.line,
-                    opt.column,
-                    opt.message
-                ));
+                let function = String::from_utf8_lossy(llvm::get_value_name(opt.function));
+                // LLVM reports a line of 0 for remarks without a debug location.
+                let location = (opt.line != 0).then(|| (opt.filename, opt.line, opt.column));
+                cgcx.diag_emitter.optimization_remark(OptimizationRemark {
+                    kind: opt.kind.describe(),
+                    pass_name: opt.pass_name,
+                    function: function.into_owned(),
+                    location,
+                    message: opt.message,
+                });
             }
         }
         llvm::diagnostic::PGO(diagnostic_ref) | llvm::diagnostic::Linker(diagnostic_ref) => {
@@ -496,6 +522,9 @@ pub(crate) unsafe fn optimize(
                 Lto::Fat => llvm::OptStage::PreLinkFatLTO,
                 Lto::Thin | Lto::ThinLocal => llvm::OptStage::PreLinkThinLTO,
                 _ if cgcx.opts.cg.linker_plugin_lto.enabled() => llvm::OptStage::PreLinkThinLTO,
+                _ if cgcx.opts.output_types.contains_key(&OutputType::LtoBitcodeBundle) => {
+                    llvm::OptStage::PreLinkThinLTO
+                }
                 _ => llvm::OptStage::PreLinkNoLTO,
             };
             return optimize_with_new_llvm_pass_manager(
@@ -579,7 +608,9 @@ pub(crate) unsafe fn optimize(
                 let opt_level = to_llvm_opt_settings(opt_level).0;
                 let prepare_for_thin_lto = cgcx.lto == Lto::Thin
                     || cgcx.lto == Lto::ThinLocal
-                    || (cgcx.lto != Lto::Fat && cgcx.opts.cg.linker_plugin_lto.enabled());
+                    || (cgcx.lto != Lto::Fat
+                        && (cgcx.opts.cg.linker_plugin_lto.enabled()
+                            || cgcx.opts.output_types.contains_key(&OutputType::LtoBitcodeBundle)));
                 with_llvm_pmb(llmod, config, opt_level, prepare_for_thin_lto, &mut |b| {
                     llvm::LLVMRustAddLastExtensionPasses(
                         b,
@@ -451,6 +451,11 @@ fn get_template_parameters<'ll, 'tcx>(
                 iter::zip(substs, names)
                     .filter_map(|(kind, name)| {
                         if let GenericArgKind::Type(ty) = kind.unpack() {
+                            // Parameters left generic by polymorphization have no type to
+                            // describe, as the instance is shared by all of their types.
+                            if let ty::Param(_) = ty.kind() {
+                                return None;
+                            }
                             let actual_type =
                                 cx.tcx.normalize_erasing_regions(ParamEnv::reveal_all(), ty);
                             let actual_type_metadata =
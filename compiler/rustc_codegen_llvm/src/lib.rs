@@ -297,10 +297,19 @@ fn link(
     ) -> Result<(), ErrorReported> {
         use crate::back::archive::LlvmArchiveBuilder;
         use rustc_codegen_ssa::back::link::link_binary;
+        use rustc_codegen_ssa::back::lto_bundle::write_lto_bitcode_bundle;
 
         // Run the linker on any artifacts that resulted from the LLVM run.
         // This should produce either a finished executable or library.
-        link_binary::<LlvmArchiveBuilder<'_>>(sess, &codegen_results, outputs)
+        link_binary::<LlvmArchiveBuilder<'_>>(sess, &codegen_results, outputs)?;
+
+        write_lto_bitcode_bundle::<LlvmArchiveBuilder<'_>>(
+            sess,
+            &codegen_results,
+            outputs,
+            llvm_util::get_version(),
+        );
+        Ok(())
     }
 }
 
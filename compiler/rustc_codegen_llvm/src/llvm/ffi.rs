@@ -2280,12 +2280,6 @@ pub fn LLVMRustArchiveIteratorNext(
     #[allow(improper_ctypes)]
     pub fn LLVMRustWriteTwineToString(T: &Twine, s: &RustString);
 
-    pub fn LLVMContextSetDiagnosticHandler(
-        C: &Context,
-        Handler: DiagnosticHandler,
-        DiagnosticContext: *mut c_void,
-    );
-
     #[allow(improper_ctypes)]
     pub fn LLVMRustUnpackOptimizationDiagnostic(
         DI: &'a DiagnosticInfo,
@@ -2319,6 +2313,15 @@ pub fn LLVMRustSetInlineAsmDiagnosticHandler(
         CX: *mut c_void,
     );
 
+    pub fn LLVMRustContextConfigureDiagnosticHandler(
+        C: &Context,
+        DiagnosticHandlerCallback: DiagnosticHandler,
+        DiagnosticHandlerContext: *mut c_void,
+        RemarkAllPasses: bool,
+        RemarkPasses: *const *const c_char,
+        RemarkPassesLen: size_t,
+    );
+
     #[allow(improper_ctypes)]
     pub fn LLVMRustUnpackSMDiagnostic(
         d: &SMDiagnostic,
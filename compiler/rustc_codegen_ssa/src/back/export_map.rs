@@ -0,0 +1,146 @@
+//! Export maps, which pin down the symbols exported from a `cdylib` with
+//! `-Z export-map`.
+//!
+//! An export map lists one symbol per line. Empty lines and lines starting
+//! with `#` are ignored. On ELF targets, every symbol can be given a version
+//! as `symbol@@VERSION`, which becomes its default version. Either all or
+//! none of the symbols have a version. Versions are ordered by their first
+//! use, and every version inherits from the one before it:
+//!
+//! ```text
+//! # libfoo's C API
+//! foo_init@@FOO_1
+//! foo_fini@@FOO_1
+//! foo_process@@FOO_2
+//! ```
+
+use rustc_data_structures::fx::FxHashSet;
+use rustc_session::config::CrateType;
+use rustc_session::Session;
+
+use std::fs;
+use std::path::Path;
+
+#[derive(Clone, Debug, PartialEq, Encodable, Decodable)]
+pub struct ExportMap {
+    pub symbols: Vec<ExportedSymbol>,
+}
+
+#[derive(Clone, Debug, PartialEq, Encodable, Decodable)]
+pub struct ExportedSymbol {
+    pub name: String,
+    pub version: Option<String>,
+}
+
+impl ExportMap {
+    /// Reads the export map at `path`, aborting on errors.
+    pub fn load(sess: &Session, path: &Path) -> ExportMap {
+        let src = fs::read_to_string(path).unwrap_or_else(|err| {
+            sess.fatal(&format!("failed to read export map `{}`: {}", path.display(), err))
+        });
+        let map = ExportMap::parse(&src).unwrap_or_else(|(line, msg)| {
+            sess.fatal(&format!("invalid export map `{}:{}`: {}", path.display(), line, msg))
+        });
+        if map.is_versioned()
+            && (sess.target.is_like_osx || sess.target.is_like_windows || sess.target.is_like_wasm)
+        {
+            sess.fatal(&format!(
+                "export map `{}` has symbol versions, which are not supported by the target",
+                path.display()
+            ));
+        }
+        map
+    }
+
+    /// Parses an export map, returning the line number and a message on errors.
+    pub fn parse(src: &str) -> Result<ExportMap, (usize, String)> {
+        let mut symbols: Vec<ExportedSymbol> = Vec::new();
+        let mut seen = FxHashSet::default();
+        for (i, line) in src.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |msg: String| Err((i + 1, msg));
+
+            let (name, version) = match line.split_once("@@") {
+                Some((name, version)) => (name, Some(version)),
+                None => (line, None),
+            };
+            if name.is_empty() || name.contains(|c: char| c.is_whitespace() || c == '@') {
+                return error(format!("expected `symbol` or `symbol@@VERSION`, found `{}`", line));
+            }
+            if let Some(version) = version {
+                if version.is_empty()
+                    || !version.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+                {
+                    return error(format!("invalid symbol version `{}`", version));
+                }
+            }
+            if let Some(first) = symbols.first() {
+                if first.version.is_some() != version.is_some() {
+                    return error(format!(
+                        "`{}` {} a version, unlike the symbols before it; either all or none \
+                         of the symbols must have one",
+                        name,
+                        if version.is_some() { "has" } else { "doesn't have" }
+                    ));
+                }
+            }
+            if !seen.insert(name) {
+                return error(format!("symbol `{}` is listed more than once", name));
+            }
+            symbols.push(ExportedSymbol {
+                name: name.to_string(),
+                version: version.map(|version| version.to_string()),
+            });
+        }
+        Ok(ExportMap { symbols })
+    }
+
+    pub fn is_versioned(&self) -> bool {
+        self.symbols.iter().any(|symbol| symbol.version.is_some())
+    }
+
+    /// Returns the versions in the order they were first used.
+    pub fn versions(&self) -> Vec<&str> {
+        let mut versions = Vec::new();
+        for version in self.symbols.iter().filter_map(|symbol| symbol.version.as_deref()) {
+            if !versions.contains(&version) {
+                versions.push(version);
+            }
+        }
+        versions
+    }
+
+    /// Checks that every symbol of the map is in `exported`, the symbols the
+    /// crate would export without the map, and returns the symbols of the map.
+    pub fn restrict(
+        &self,
+        sess: &Session,
+        crate_type: CrateType,
+        path: &Path,
+        exported: &[String],
+    ) -> Vec<String> {
+        let exported: FxHashSet<&str> = exported.iter().map(|symbol| &symbol[..]).collect();
+        for symbol in &self.symbols {
+            if !exported.contains(&symbol.name[..]) {
+                sess.struct_err(&format!(
+                    "symbol `{}` in export map `{}` is not exported from the {}",
+                    symbol.name,
+                    path.display(),
+                    crate_type
+                ))
+                .help(
+                    "only `#[no_mangle]` and `#[export_name]` items that are reachable from \
+                     outside the crate can be exported",
+                )
+                .emit();
+            }
+        }
+        self.symbols.iter().map(|symbol| symbol.name.clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests;
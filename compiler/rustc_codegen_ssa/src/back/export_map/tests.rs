@@ -0,0 +1,38 @@
+use super::{ExportMap, ExportedSymbol};
+
+fn symbol(name: &str, version: Option<&str>) -> ExportedSymbol {
+    ExportedSymbol { name: name.to_string(), version: version.map(|v| v.to_string()) }
+}
+
+#[test]
+fn test_parse_unversioned() {
+    let map = ExportMap::parse("# comment\nfoo_init\n\n  foo_fini  \n").unwrap();
+    assert_eq!(map.symbols, [symbol("foo_init", None), symbol("foo_fini", None)]);
+    assert!(!map.is_versioned());
+    assert!(map.versions().is_empty());
+}
+
+#[test]
+fn test_parse_versioned() {
+    let map = ExportMap::parse("foo_init@@FOO_1\nfoo_process@@FOO_2\nfoo_fini@@FOO_1\n").unwrap();
+    assert_eq!(
+        map.symbols,
+        [
+            symbol("foo_init", Some("FOO_1")),
+            symbol("foo_process", Some("FOO_2")),
+            symbol("foo_fini", Some("FOO_1")),
+        ]
+    );
+    assert!(map.is_versioned());
+    assert_eq!(map.versions(), ["FOO_1", "FOO_2"]);
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(ExportMap::parse("foo\nfoo bar\n").unwrap_err().0, 2);
+    assert_eq!(ExportMap::parse("foo@FOO_1\n").unwrap_err().0, 1);
+    assert_eq!(ExportMap::parse("foo@@\n").unwrap_err().0, 1);
+    assert_eq!(ExportMap::parse("foo@@FOO 1\n").unwrap_err().0, 1);
+    assert_eq!(ExportMap::parse("foo@@FOO_1\n# comment\nbar\n").unwrap_err().0, 3);
+    assert_eq!(ExportMap::parse("foo\nbar\nfoo\n").unwrap_err().0, 3);
+}
@@ -22,6 +22,7 @@
 use super::archive::{find_library, ArchiveBuilder};
 use super::command::Command;
 use super::linker::{self, Linker};
+use super::linker_errors;
 use super::rpath::{self, RPathConfig};
 use crate::{
     looks_like_rust_object_file, CodegenResults, CompiledModule, CrateInfo, NativeLib,
@@ -877,13 +878,45 @@ fn is_illegal_instruction(_status: &ExitStatus) -> bool {
                 let mut output = prog.stderr.clone();
                 output.extend_from_slice(&prog.stdout);
                 let escaped_output = escape_stdout_stderr_string(&output);
+                let parsed = linker_errors::parse_linker_output(&escaped_output);
+                linker_errors::report_linker_errors(
+                    sess,
+                    &codegen_results.crate_info,
+                    &parsed.errors,
+                );
                 let mut err = sess.struct_err(&format!(
                     "linking with `{}` failed: {}",
                     linker_path.display(),
                     prog.status
                 ));
-                err.note(&format!("{:?}", &cmd)).note(&escaped_output);
-                if escaped_output.contains("undefined reference to") {
+                if !parsed.errors.is_empty() {
+                    // The errors were reported on their own above, so keep
+                    // the command line and the rest of the output out of the
+                    // way. The temporary directory only outlives rustc with
+                    // `-C save-temps`, so the arguments are only written then.
+                    if sess.opts.cg.save_temps {
+                        let args_file = tmpdir.join("linker-args");
+                        let contents = response_file_contents(sess, cmd.get_args());
+                        match fs::write(&args_file, contents) {
+                            Ok(()) => err.note(&format!(
+                                "the linker arguments were written to `{}`, rerun the linker \
+                                 with `{} @{}`",
+                                args_file.display(),
+                                linker_path.display(),
+                                args_file.display()
+                            )),
+                            Err(_) => err.note(&format!("{:?}", &cmd)),
+                        };
+                    } else {
+                        err.note("rerun with `-C save-temps` to keep the linker arguments");
+                    }
+                    if !parsed.other_lines.is_empty() {
+                        err.note(&parsed.other_lines.join("\n"));
+                    }
+                } else {
+                    err.note(&format!("{:?}", &cmd)).note(&escaped_output);
+                }
+                if parsed.errors.is_empty() && escaped_output.contains("undefined reference to") {
                     err.help(
                         "some `extern` functions couldn't be found; some native libraries may \
                          need to be installed or have their path specified",
@@ -1365,28 +1398,8 @@ fn exec_linker(
 
     info!("falling back to passing arguments to linker via an @-file");
     let mut cmd2 = cmd.clone();
-    let mut args = String::new();
-    for arg in cmd2.take_args() {
-        args.push_str(
-            &Escape { arg: arg.to_str().unwrap(), is_like_msvc: sess.target.is_like_msvc }
-                .to_string(),
-        );
-        args.push('\n');
-    }
     let file = tmpdir.join("linker-arguments");
-    let bytes = if sess.target.is_like_msvc {
-        let mut out = Vec::with_capacity((1 + args.len()) * 2);
-        // start the stream with a UTF-16 BOM
-        for c in std::iter::once(0xFEFF).chain(args.encode_utf16()) {
-            // encode in little endian
-            out.push(c as u8);
-            out.push((c >> 8) as u8);
-        }
-        out
-    } else {
-        args.into_bytes()
-    };
-    fs::write(&file, &bytes)?;
+    fs::write(&file, &response_file_contents(sess, &cmd2.take_args()))?;
     cmd2.arg(format!("@{}", file.display()));
     info!("invoking linker {:?}", cmd2);
     let output = cmd2.output();
@@ -1437,7 +1450,10 @@ fn command_line_too_big(err: &io::Error) -> bool {
     fn command_line_too_big(_: &io::Error) -> bool {
         false
     }
+}
 
+/// Returns the contents of an `@`-file passing `args` to the linker.
+fn response_file_contents(sess: &Session, args: &[OsString]) -> Vec<u8> {
     struct Escape<'a> {
         arg: &'a str,
         is_like_msvc: bool,
@@ -1482,6 +1498,28 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             Ok(())
         }
     }
+
+    let mut contents = String::new();
+    for arg in args {
+        contents.push_str(
+            &Escape { arg: arg.to_str().unwrap(), is_like_msvc: sess.target.is_like_msvc }
+                .to_string(),
+        );
+        contents.push('\n');
+    }
+
+    if sess.target.is_like_msvc {
+        let mut out = Vec::with_capacity((1 + contents.len()) * 2);
+        // start the stream with a UTF-16 BOM
+        for c in std::iter::once(0xFEFF).chain(contents.encode_utf16()) {
+            // encode in little endian
+            out.push(c as u8);
+            out.push((c >> 8) as u8);
+        }
+        out
+    } else {
+        contents.into_bytes()
+    }
 }
 
 fn link_output_kind(sess: &Session, crate_type: CrateType) -> LinkOutputKind {
@@ -1772,6 +1810,7 @@ fn linker_with_args<'a, B: ArchiveBuilder<'a>>(
         flavor,
         crt_objects_fallback,
         &codegen_results.crate_info.target_cpu,
+        codegen_results.crate_info.export_map.as_ref(),
     );
     let link_output_kind = link_output_kind(sess, crate_type);
 
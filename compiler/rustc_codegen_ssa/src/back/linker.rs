@@ -1,5 +1,6 @@
 use super::archive;
 use super::command::Command;
+use super::export_map::ExportMap;
 use super::symbol_export;
 use rustc_span::symbol::sym;
 
@@ -43,6 +44,7 @@ pub fn get_linker<'a>(
     flavor: LinkerFlavor,
     self_contained: bool,
     target_cpu: &'a str,
+    export_map: Option<&'a ExportMap>,
 ) -> Box<dyn Linker + 'a> {
     let msvc_tool = windows_registry::find_tool(&sess.opts.target_triple.triple(), "link.exe");
 
@@ -132,17 +134,25 @@ pub fn get_linker<'a>(
             Box::new(MsvcLinker { cmd, sess }) as Box<dyn Linker>
         }
         LinkerFlavor::Em => Box::new(EmLinker { cmd, sess }) as Box<dyn Linker>,
-        LinkerFlavor::Gcc => {
-            Box::new(GccLinker { cmd, sess, target_cpu, hinted_static: false, is_ld: false })
-                as Box<dyn Linker>
-        }
+        LinkerFlavor::Gcc => Box::new(GccLinker {
+            cmd,
+            sess,
+            target_cpu,
+            export_map,
+            hinted_static: false,
+            is_ld: false,
+        }) as Box<dyn Linker>,
 
         LinkerFlavor::Lld(LldFlavor::Ld)
         | LinkerFlavor::Lld(LldFlavor::Ld64)
-        | LinkerFlavor::Ld => {
-            Box::new(GccLinker { cmd, sess, target_cpu, hinted_static: false, is_ld: true })
-                as Box<dyn Linker>
-        }
+        | LinkerFlavor::Ld => Box::new(GccLinker {
+            cmd,
+            sess,
+            target_cpu,
+            export_map,
+            hinted_static: false,
+            is_ld: true,
+        }) as Box<dyn Linker>,
 
         LinkerFlavor::Lld(LldFlavor::Wasm) => Box::new(WasmLd::new(cmd, sess)) as Box<dyn Linker>,
 
@@ -213,6 +223,8 @@ pub struct GccLinker<'a> {
     cmd: Command,
     sess: &'a Session,
     target_cpu: &'a str,
+    /// The export map passed with `-Z export-map`, for its symbol versions.
+    export_map: Option<&'a ExportMap>,
     hinted_static: bool, // Keeps track of the current hinting mode.
     // Link as ld
     is_ld: bool,
@@ -671,6 +683,30 @@ fn export_symbols(&mut self, tmpdir: &Path, crate_type: CrateType, symbols: &[St
             if let Err(e) = res {
                 self.sess.fatal(&format!("failed to write list.def file: {}", e));
             }
+        } else if let Some(map) = self.export_map.filter(|map| map.is_versioned()) {
+            // Write an LD version script with a version node for every version,
+            // each inheriting from the one before it.
+            let res: io::Result<()> = try {
+                let mut f = BufWriter::new(File::create(&path)?);
+                let mut previous = None;
+                for version in map.versions() {
+                    writeln!(f, "{} {{\n  global:", version)?;
+                    for sym in &map.symbols {
+                        if sym.version.as_deref() == Some(version) {
+                            debug!("    {}@@{};", sym.name, version);
+                            writeln!(f, "    {};", sym.name)?;
+                        }
+                    }
+                    match previous {
+                        Some(previous) => writeln!(f, "}} {};", previous)?,
+                        None => writeln!(f, "\n  local:\n    *;\n}};")?,
+                    }
+                    previous = Some(version);
+                }
+            };
+            if let Err(e) = res {
+                self.sess.fatal(&format!("failed to write version script: {}", e));
+            }
         } else {
             // Write an LD version script
             let res: io::Result<()> = try {
@@ -0,0 +1,372 @@
+//! Parsing of the output of a failed link into structured diagnostics.
+//!
+//! The raw output of the linker easily runs to thousands of lines, most of it
+//! repeating the same few errors for every place a symbol is referenced. The
+//! most common errors of GNU ld, gold and lld are recognized here and reported
+//! once each, with the symbols demangled and the crates involved named.
+//! Anything that isn't recognized is still passed on to the user verbatim.
+
+use crate::CrateInfo;
+
+use rustc_data_structures::fx::{FxHashSet, FxIndexMap};
+use rustc_errors::DiagnosticBuilder;
+use rustc_session::Session;
+
+use regex::Regex;
+
+/// The number of locations listed for an error before the rest is summarized.
+const MAX_LOCATIONS: usize = 3;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum LinkerErrorKind {
+    UndefinedSymbol,
+    DuplicateSymbol,
+    MissingLibrary,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct LinkerError {
+    pub kind: LinkerErrorKind,
+    /// The (mangled) symbol, or the library name for `MissingLibrary`.
+    pub name: String,
+    /// Where the symbol is referenced, or where it is defined for
+    /// `DuplicateSymbol`, without duplicates.
+    pub locations: Vec<Location>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Location {
+    /// The object file, possibly as `archive.rlib(member.o)`.
+    pub object: String,
+    /// The function containing the reference, if the linker named it.
+    pub function: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct LinkerOutput<'a> {
+    pub errors: Vec<LinkerError>,
+    /// The lines that weren't recognized as part of an error.
+    pub other_lines: Vec<&'a str>,
+}
+
+/// Splits the combined stdout and stderr of the linker into the errors that
+/// are recognized and the remaining lines.
+pub fn parse_linker_output(output: &str) -> LinkerOutput<'_> {
+    // Messages are prefixed with the linker, e.g. `/usr/bin/ld: `,
+    // `/usr/bin/ld.gold: error: ` or `ld.lld: error: `.
+    let linker_prefix = Regex::new(concat!(
+        r"^(?:\S*/)?(?:[\w.]*-)?(?:ld|ld\.bfd|ld\.gold|ld\.lld|lld|ld64\.lld|lld-link)",
+        r"(?:\.exe)?: (?:error: )?",
+    ))
+    .unwrap();
+    let in_function =
+        Regex::new(r"^(?P<object>.+?): [Ii]n function [`'](?P<function>.+)':$").unwrap();
+    let undefined_reference =
+        Regex::new(r"^(?P<head>.*?):? ?undefined reference to [`'](?P<symbol>.+?)'").unwrap();
+    let gold_function =
+        Regex::new(r"^(?P<object>[^:]+):[^:]*:function (?P<function>.+?): error: ").unwrap();
+    let more_references =
+        Regex::new(r"more undefined references to [`'](?P<symbol>.+?)' follow").unwrap();
+    let multiple_definition = Regex::new(concat!(
+        r"^(?P<head>.*?):? ?multiple definition of [`'](?P<symbol>.+?)'",
+        r"(?:; (?P<first>.+): first defined here)?",
+    ))
+    .unwrap();
+    let previous_definition =
+        Regex::new(r"^(?P<head>.+): (?:first defined|previous definition) here$").unwrap();
+    let lld_error = Regex::new(r"^(?P<what>undefined|duplicate) symbol: (?P<symbol>.+)$").unwrap();
+    let lld_location = Regex::new(r"^>>>\s+(?P<object>\S+):\((?P<function>.+)\)$").unwrap();
+    let missing_library =
+        Regex::new(r"^(?:cannot find|unable to find library) -l ?(?P<library>:?[^\s:]+)").unwrap();
+    let linker_failed = Regex::new(concat!(
+        r"^(?:collect2: error: ld returned \d+ exit status",
+        r"|clang(?:-\d+)?: error: linker command failed with exit code \d+.*)$",
+    ))
+    .unwrap();
+
+    let mut errors = FxIndexMap::default();
+    let mut other_lines = vec![];
+    // The function named by the last `in function` line of GNU ld.
+    let mut context: Option<Location> = None;
+    // The error that the following `>>>` lines of lld belong to.
+    let mut lld_context: Option<(LinkerErrorKind, String)> = None;
+    // The last duplicate symbol, which `first defined here` lines belong to.
+    let mut last_duplicate: Option<String> = None;
+
+    for line in output.lines() {
+        if line.starts_with(">>>") {
+            if let Some((kind, name)) = &lld_context {
+                if let Some(caps) = lld_location.captures(line) {
+                    let function = Some(caps["function"].to_string())
+                        .filter(|function| !function.starts_with('.'));
+                    let location = Location { object: caps["object"].to_string(), function };
+                    add_error(&mut errors, *kind, name, Some(location));
+                }
+                continue;
+            }
+        } else {
+            lld_context = None;
+        }
+
+        let message = linker_prefix.replace(line, "");
+        if let Some(caps) = in_function.captures(&message) {
+            context = Some(Location {
+                object: caps["object"].to_string(),
+                function: Some(caps["function"].to_string()),
+            });
+        } else if let Some(caps) = undefined_reference.captures(&message) {
+            let location = match gold_function.captures(&message) {
+                Some(gold) => Some(Location {
+                    object: gold["object"].to_string(),
+                    function: Some(gold["function"].to_string()),
+                }),
+                None => location_of(&caps["head"], &context),
+            };
+            add_error(&mut errors, LinkerErrorKind::UndefinedSymbol, &caps["symbol"], location);
+        } else if let Some(caps) = more_references.captures(&message) {
+            add_error(&mut errors, LinkerErrorKind::UndefinedSymbol, &caps["symbol"], None);
+        } else if let Some(caps) = multiple_definition.captures(&message) {
+            let symbol = &caps["symbol"];
+            let location = location_of(&caps["head"], &context)
+                .map(|location| Location { function: None, ..location });
+            add_error(&mut errors, LinkerErrorKind::DuplicateSymbol, symbol, location);
+            if let Some(first) = caps.name("first") {
+                let location = location_of(first.as_str(), &None);
+                add_error(&mut errors, LinkerErrorKind::DuplicateSymbol, symbol, location);
+            }
+            last_duplicate = Some(symbol.to_string());
+        } else if let Some(caps) = previous_definition.captures(&message) {
+            match (&last_duplicate, location_of(&caps["head"], &None)) {
+                (Some(symbol), Some(location)) => {
+                    add_error(&mut errors, LinkerErrorKind::DuplicateSymbol, symbol, Some(location))
+                }
+                _ => other_lines.push(line),
+            }
+        } else if let Some(caps) = lld_error.captures(&message) {
+            let kind = match &caps["what"] {
+                "undefined" => LinkerErrorKind::UndefinedSymbol,
+                _ => LinkerErrorKind::DuplicateSymbol,
+            };
+            add_error(&mut errors, kind, &caps["symbol"], None);
+            lld_context = Some((kind, caps["symbol"].to_string()));
+        } else if let Some(caps) = missing_library.captures(&message) {
+            add_error(&mut errors, LinkerErrorKind::MissingLibrary, &caps["library"], None);
+        } else if !linker_failed.is_match(line) && !line.trim().is_empty() {
+            other_lines.push(line);
+        }
+    }
+
+    let errors = errors
+        .into_iter()
+        .map(|((kind, name), locations)| LinkerError { kind, name, locations })
+        .collect();
+    LinkerOutput { errors, other_lines }
+}
+
+fn add_error(
+    errors: &mut FxIndexMap<(LinkerErrorKind, String), Vec<Location>>,
+    kind: LinkerErrorKind,
+    name: &str,
+    location: Option<Location>,
+) {
+    let locations = errors.entry((kind, name.to_string())).or_default();
+    if let Some(location) = location {
+        if !locations.contains(&location) {
+            locations.push(location);
+        }
+    }
+}
+
+/// Returns the location a message is about, given the text in front of it,
+/// e.g. `foo.o:(.text+0x5)` or `foo.c:(.text+0x5)` after an `in function`
+/// line naming `foo.o`.
+fn location_of(head: &str, context: &Option<Location>) -> Option<Location> {
+    let object = head.split(':').next().unwrap_or("");
+    let is_object = object.ends_with(".o") || object.ends_with(".obj") || object.ends_with(')');
+    match context {
+        Some(context) if !is_object || context.object == object => Some(context.clone()),
+        _ if is_object => Some(Location { object: object.to_string(), function: None }),
+        _ => None,
+    }
+}
+
+/// Emits one error for every linker error, naming the crates involved.
+pub fn report_linker_errors(sess: &Session, crate_info: &CrateInfo, errors: &[LinkerError]) {
+    let crates = Crates::new(crate_info);
+    for error in errors {
+        match error.kind {
+            LinkerErrorKind::UndefinedSymbol => report_undefined_symbol(sess, &crates, error),
+            LinkerErrorKind::DuplicateSymbol => report_duplicate_symbol(sess, &crates, error),
+            LinkerErrorKind::MissingLibrary => {
+                report_missing_library(sess, crate_info, &error.name)
+            }
+        }
+    }
+}
+
+fn report_undefined_symbol(sess: &Session, crates: &Crates, error: &LinkerError) {
+    let symbol = demangle(&error.name);
+    let mut err = sess.struct_err(&format!("undefined symbol `{}`", symbol));
+    note_locations(&mut err, crates, &error.locations, "referenced");
+
+    if let Some(krate) = crates.of_symbol(&symbol) {
+        err.note(&format!("`{}` is defined in crate `{}`", symbol, krate));
+        err.help(&format!(
+            "make sure that crate `{}` is linked and was built by the same compiler with the \
+             same options",
+            krate
+        ));
+    } else if symbol != error.name || rustc_demangle::try_demangle(&error.name).is_ok() {
+        err.help("make sure that the crate defining this symbol is linked");
+    } else {
+        let users: FxHashSet<_> =
+            error.locations.iter().filter_map(|location| crates.of_location(location)).collect();
+        let declared_in = match users.iter().next() {
+            Some(krate) if users.len() == 1 => format!(" in crate `{}`", krate),
+            _ => String::new(),
+        };
+        err.help(&format!(
+            "if `{}` is defined in a native library, add `#[link(name = \"...\")]` to the \
+             `extern` block declaring it{}, or pass `-l <library>` to rustc",
+            symbol, declared_in
+        ));
+        err.note(
+            "use the `cargo:rustc-link-lib` directive to specify the native libraries to link \
+             with Cargo (see https://doc.rust-lang.org/cargo/reference/build-scripts.html#cargorustc-link-libkindname)",
+        );
+    }
+    err.emit();
+}
+
+fn report_duplicate_symbol(sess: &Session, crates: &Crates, error: &LinkerError) {
+    let symbol = demangle(&error.name);
+    let mut err = sess.struct_err(&format!("symbol `{}` is defined multiple times", symbol));
+    note_locations(&mut err, crates, &error.locations, "defined");
+    if symbol == error.name && rustc_demangle::try_demangle(&error.name).is_err() {
+        err.help(
+            "only one crate or native library may define an unmangled symbol, e.g. with \
+             `#[no_mangle]` or `#[export_name]`",
+        );
+    } else {
+        err.help(
+            "this can happen if two versions of a crate are built with the same \
+             `-C metadata`",
+        );
+    }
+    err.emit();
+}
+
+fn report_missing_library(sess: &Session, crate_info: &CrateInfo, library: &str) {
+    let mut err = sess.struct_err(&format!("could not find native library `{}`", library));
+    let name = library.trim_start_matches(':');
+    let requested_by = |libs: &[crate::NativeLib]| {
+        libs.iter().any(|lib| lib.name.map_or(false, |lib_name| &*lib_name.as_str() == name))
+    };
+    let mut crates: Vec<_> = crate_info
+        .native_libraries
+        .iter()
+        .filter(|(_, libs)| requested_by(libs))
+        .filter_map(|(cnum, _)| crate_info.crate_name.get(cnum).cloned())
+        .collect();
+    if requested_by(&crate_info.used_libraries) {
+        crates.push(crate_info.local_crate_name.to_string());
+    }
+    crates.sort();
+    crates.dedup();
+    for krate in crates {
+        err.note(&format!(
+            "crate `{}` links to `{}` with `#[link(name = \"{}\")]` or `-l {}`",
+            krate, name, name, name
+        ));
+    }
+    err.help(&format!(
+        "install `{}`, or pass the directory containing it with `-L native=<dir>`",
+        name
+    ));
+    err.emit();
+}
+
+fn note_locations(
+    err: &mut DiagnosticBuilder<'_>,
+    crates: &Crates,
+    locations: &[Location],
+    what: &str,
+) {
+    for location in locations.iter().take(MAX_LOCATIONS) {
+        let object = file_name(&location.object);
+        let krate = match crates.of_location(location) {
+            Some(krate) => format!(" in crate `{}`", krate),
+            None => String::new(),
+        };
+        match &location.function {
+            Some(function) => {
+                err.note(&format!("{} by `{}`{} ({})", what, demangle(function), krate, object))
+            }
+            None => err.note(&format!("{} in {}{}", what, object, krate)),
+        };
+    }
+    if locations.len() > MAX_LOCATIONS {
+        err.note(&format!("... and {} more", locations.len() - MAX_LOCATIONS));
+    }
+}
+
+/// The names of all crates in the crate graph, to recognize them in symbols
+/// and object file names.
+struct Crates {
+    names: FxHashSet<String>,
+    object_name: Regex,
+}
+
+impl Crates {
+    fn new(crate_info: &CrateInfo) -> Self {
+        let mut names: FxHashSet<String> = crate_info.crate_name.values().cloned().collect();
+        names.insert(crate_info.local_crate_name.to_string());
+        // `libfoo-<hash>.rlib(...)` or `foo.foo.<hash>-cgu.0.rcgu.o`.
+        let object_name =
+            Regex::new(r"^(?:lib(?P<rlib>\w+)-\w+\.rlib\(|(?P<object>\w+)\.\w+\.\w+-cgu\.)")
+                .unwrap();
+        Crates { names, object_name }
+    }
+
+    /// The crate that defines a demangled Rust symbol.
+    fn of_symbol<'a>(&'a self, symbol: &str) -> Option<&'a str> {
+        let krate = symbol.trim_start_matches('<').split("::").next()?;
+        self.names.get(krate).map(|krate| &krate[..])
+    }
+
+    fn of_location<'a>(&'a self, location: &Location) -> Option<&'a str> {
+        let function = location.function.as_deref().map(demangle);
+        if let Some(krate) = function.and_then(|function| self.of_symbol(&function)) {
+            return Some(krate);
+        }
+        let caps = self.object_name.captures(file_name(&location.object))?;
+        let krate = caps.name("rlib").or_else(|| caps.name("object"))?.as_str();
+        self.names.get(krate).map(|krate| &krate[..])
+    }
+}
+
+fn file_name(object: &str) -> &str {
+    // Don't split the member name of `archive.rlib(member.o)`.
+    let path = &object[..object.find('(').unwrap_or(object.len())];
+    &object[path.rfind(|c| c == '/' || c == '\\').map_or(0, |slash| slash + 1)..]
+}
+
+/// Demangles a Rust symbol without its hash. Other symbols are returned as-is,
+/// apart from the hash of legacy Rust symbols the linker demangled itself.
+fn demangle(symbol: &str) -> String {
+    match rustc_demangle::try_demangle(symbol) {
+        Ok(demangled) => format!("{:#}", demangled),
+        Err(_) => match symbol.rfind("::h") {
+            Some(hash)
+                if symbol.len() - hash == 19
+                    && symbol[hash + 3..].bytes().all(|b| b.is_ascii_hexdigit()) =>
+            {
+                symbol[..hash].to_string()
+            }
+            _ => symbol.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,149 @@
+use super::{demangle, file_name, parse_linker_output, LinkerError, LinkerErrorKind, Location};
+
+fn location(object: &str, function: Option<&str>) -> Location {
+    Location { object: object.to_string(), function: function.map(|f| f.to_string()) }
+}
+
+fn error(kind: LinkerErrorKind, name: &str, locations: Vec<Location>) -> LinkerError {
+    LinkerError { kind, name: name.to_string(), locations }
+}
+
+#[test]
+fn test_gnu_ld_undefined_reference() {
+    let output = "\
+/usr/bin/ld: /tmp/main.main.a1b2c3-cgu.0.rcgu.o: in function `main::main':
+main.rs:(.text+0x11): undefined reference to `foo_init'
+/usr/bin/ld: main.rs:(.text+0x21): undefined reference to `foo_init'
+/usr/bin/ld: main.rs:(.text+0x31): undefined reference to `foo_fini'
+collect2: error: ld returned 1 exit status
+";
+    let parsed = parse_linker_output(output);
+    let main = location("/tmp/main.main.a1b2c3-cgu.0.rcgu.o", Some("main::main"));
+    assert_eq!(
+        parsed.errors,
+        [
+            error(LinkerErrorKind::UndefinedSymbol, "foo_init", vec![main.clone()]),
+            error(LinkerErrorKind::UndefinedSymbol, "foo_fini", vec![main]),
+        ]
+    );
+    assert!(parsed.other_lines.is_empty());
+}
+
+#[test]
+fn test_gold_undefined_reference() {
+    let output = "\
+main.o:main.c:function main: error: undefined reference to 'foo_init'
+";
+    let parsed = parse_linker_output(output);
+    assert_eq!(
+        parsed.errors,
+        [error(
+            LinkerErrorKind::UndefinedSymbol,
+            "foo_init",
+            vec![location("main.o", Some("main"))]
+        )]
+    );
+}
+
+#[test]
+fn test_lld_undefined_symbol() {
+    let output = "\
+ld.lld: error: undefined symbol: foo_init
+>>> referenced by main.rs:3
+>>>               main.main.a1b2c3-cgu.0.rcgu.o:(main::main::h0123456789abcdef)
+>>> referenced by lib.rs:7
+>>>               libdep-0123abcd.rlib(dep-0123abcd.dep.d4e5f6-cgu.0.rcgu.o):(.text+0x5)
+clang: error: linker command failed with exit code 1 (use -v to see invocation)
+";
+    let parsed = parse_linker_output(output);
+    assert_eq!(
+        parsed.errors,
+        [error(
+            LinkerErrorKind::UndefinedSymbol,
+            "foo_init",
+            vec![
+                location("main.main.a1b2c3-cgu.0.rcgu.o", Some("main::main::h0123456789abcdef")),
+                location("libdep-0123abcd.rlib(dep-0123abcd.dep.d4e5f6-cgu.0.rcgu.o)", None),
+            ]
+        )]
+    );
+    assert!(parsed.other_lines.is_empty());
+}
+
+#[test]
+fn test_duplicate_symbol() {
+    let gnu = "\
+/usr/bin/ld: b.o: in function `shared':
+b.c:(.text+0x0): multiple definition of `shared'; a.o:a.c:(.text+0x0): first defined here
+";
+    let lld = "\
+ld.lld: error: duplicate symbol: shared
+>>> defined at a.c:1
+>>>            a.o:(shared)
+>>> defined at b.c:1
+>>>            b.o:(.text+0x0)
+";
+    let gold = "\
+/usr/bin/ld.gold: error: b.o: multiple definition of 'shared'
+/usr/bin/ld.gold: a.o: previous definition here
+";
+    for (output, first, second) in [(gnu, "b.o", "a.o"), (lld, "a.o", "b.o"), (gold, "b.o", "a.o")]
+    {
+        let parsed = parse_linker_output(output);
+        assert_eq!(parsed.errors.len(), 1, "{}", output);
+        let error = &parsed.errors[0];
+        assert_eq!(error.kind, LinkerErrorKind::DuplicateSymbol);
+        assert_eq!(error.name, "shared");
+        let objects: Vec<_> = error.locations.iter().map(|l| &l.object[..]).collect();
+        assert_eq!(objects, [first, second], "{}", output);
+        assert!(parsed.other_lines.is_empty(), "{}", output);
+    }
+}
+
+#[test]
+fn test_missing_library() {
+    for output in [
+        "/usr/bin/ld: cannot find -lfoo\n",
+        "/usr/bin/ld: cannot find -lfoo: No such file or directory\n",
+        "/usr/bin/ld.gold: error: cannot find -lfoo\n",
+        "ld.lld: error: unable to find library -lfoo\n",
+    ] {
+        let parsed = parse_linker_output(output);
+        assert_eq!(parsed.errors, [error(LinkerErrorKind::MissingLibrary, "foo", vec![])]);
+    }
+    let parsed = parse_linker_output("rust-lld: error: unable to find library -l:libfoo.a\n");
+    assert_eq!(parsed.errors, [error(LinkerErrorKind::MissingLibrary, ":libfoo.a", vec![])]);
+}
+
+#[test]
+fn test_other_lines() {
+    let output = "\
+/usr/bin/ld: warning: something unusual happened
+/usr/bin/ld: cannot find -lfoo
+/usr/bin/ld: world.o: file not recognized: file format not recognized
+";
+    let parsed = parse_linker_output(output);
+    assert_eq!(parsed.errors.len(), 1);
+    assert_eq!(
+        parsed.other_lines,
+        [
+            "/usr/bin/ld: warning: something unusual happened",
+            "/usr/bin/ld: world.o: file not recognized: file format not recognized",
+        ]
+    );
+}
+
+#[test]
+fn test_demangle() {
+    assert_eq!(demangle("_ZN4main4main17h0123456789abcdefE"), "main::main");
+    assert_eq!(demangle("_RNvCs1234_4main4main"), "main::main");
+    assert_eq!(demangle("main::main::h0123456789abcdef"), "main::main");
+    assert_eq!(demangle("foo_init"), "foo_init");
+}
+
+#[test]
+fn test_file_name() {
+    assert_eq!(file_name("/tmp/a.o"), "a.o");
+    assert_eq!(file_name("a.o"), "a.o");
+    assert_eq!(file_name("/tmp/libfoo-1234.rlib(foo.o)"), "libfoo-1234.rlib(foo.o)");
+}
@@ -0,0 +1,177 @@
+//! Writing of the LTO bitcode bundles requested with `--emit=lto-bitcode-bundle`.
+//!
+//! A bundle lets C and C++ build systems include a Rust crate in their own
+//! LTO without setting up `-C linker-plugin-lto`. It is an `ar` archive
+//! with the following members:
+//!
+//! - `lto-bundle.manifest`, a text file with one `key value` pair per line:
+//!   - `format 1`, the version of this format,
+//!   - `crate <name>`,
+//!   - `target <triple>`,
+//!   - `llvm-version <major>.<minor>.<patch>`, the LLVM version that wrote the
+//!     bitcode, which has to match the one of the `clang` importing it,
+//!   - `exports lto-bundle.exports`, and
+//!   - one `module <member>` line per bitcode member.
+//! - `lto-bundle.exports`, the symbols the crate exports to C, one per line.
+//!   They must not be internalized during LTO.
+//! - One LLVM bitcode file with an embedded ThinLTO summary for every codegen
+//!   unit, and one for the allocator shim if the crate has one.
+//!
+//! The archive has a symbol table, so linkers that support LTO can also link
+//! it directly.
+
+use super::archive::ArchiveBuilder;
+use super::symbol_export;
+use super::write::remove_temporary_bitcode;
+use crate::CodegenResults;
+
+use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_middle::middle::exported_symbols::SymbolExportLevel;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::config::OutputFilenames;
+use rustc_session::config::OutputType;
+use rustc_session::Session;
+
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::Builder as TempFileBuilder;
+
+/// The version of the bundle format, written to the manifest.
+pub const FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_NAME: &str = "lto-bundle.manifest";
+const EXPORTS_NAME: &str = "lto-bundle.exports";
+
+/// Returns the symbols of the local crate that are exported to C.
+pub fn exported_symbols(tcx: TyCtxt<'_>) -> Vec<String> {
+    tcx.exported_symbols(LOCAL_CRATE)
+        .iter()
+        .filter(|&&(_, level)| level.is_below_threshold(SymbolExportLevel::C))
+        .map(|&(symbol, _)| {
+            symbol_export::symbol_name_for_instance_in_crate(tcx, symbol, LOCAL_CRATE)
+        })
+        .collect()
+}
+
+/// Packages the bitcode of all codegen units into the bundle. This has to run
+/// after `produce_final_output_artifacts`, which keeps the bitcode around for
+/// it. `llvm_version` is the version of the LLVM that wrote the bitcode.
+pub fn write_lto_bitcode_bundle<'a, B: ArchiveBuilder<'a>>(
+    sess: &'a Session,
+    codegen_results: &CodegenResults,
+    outputs: &OutputFilenames,
+    llvm_version: (u32, u32, u32),
+) {
+    if !outputs.outputs.contains_key(&OutputType::LtoBitcodeBundle) {
+        return;
+    }
+
+    if let Some(clang) = &sess.opts.debugging_opts.lto_bitcode_bundle_clang {
+        check_clang_version(sess, clang, llvm_version);
+    }
+
+    let modules = codegen_results.modules.iter().chain(&codegen_results.allocator_module);
+    let bitcode: Vec<&Path> = modules.filter_map(|module| module.bytecode.as_deref()).collect();
+
+    let tmpdir = TempFileBuilder::new()
+        .prefix("rustc")
+        .tempdir()
+        .unwrap_or_else(|err| sess.fatal(&format!("couldn't create a temp dir: {}", err)));
+
+    let (major, minor, patch) = llvm_version;
+    let mut manifest = String::new();
+    writeln!(manifest, "format {}", FORMAT_VERSION).unwrap();
+    writeln!(manifest, "crate {}", codegen_results.crate_info.local_crate_name).unwrap();
+    writeln!(manifest, "target {}", sess.target.llvm_target).unwrap();
+    writeln!(manifest, "llvm-version {}.{}.{}", major, minor, patch).unwrap();
+    writeln!(manifest, "exports {}", EXPORTS_NAME).unwrap();
+    for path in &bitcode {
+        writeln!(manifest, "module {}", path.file_name().unwrap().to_string_lossy()).unwrap();
+    }
+
+    let mut exports = String::new();
+    for symbol in &codegen_results.crate_info.lto_bitcode_bundle_exports {
+        writeln!(exports, "{}", symbol).unwrap();
+    }
+
+    let manifest_path = tmpdir.path().join(MANIFEST_NAME);
+    let exports_path = tmpdir.path().join(EXPORTS_NAME);
+    for (path, contents) in [(&manifest_path, manifest), (&exports_path, exports)] {
+        if let Err(err) = fs::write(path, contents) {
+            sess.fatal(&format!("failed to write `{}`: {}", path.display(), err));
+        }
+    }
+
+    let out_filename = outputs.path(OutputType::LtoBitcodeBundle);
+    let mut ab = B::new(sess, &out_filename, None);
+    ab.add_file(&manifest_path);
+    ab.add_file(&exports_path);
+    for path in &bitcode {
+        ab.add_file(path);
+    }
+    ab.build();
+
+    if sess.opts.json_artifact_notifications {
+        sess.parse_sess
+            .span_diagnostic
+            .emit_artifact_notification(&out_filename, "lto-bitcode-bundle");
+    }
+
+    // The bitcode was only kept for the bundle, unless it was requested on its
+    // own.
+    remove_temporary_bitcode(
+        sess,
+        &codegen_results.modules,
+        codegen_results.allocator_module.as_ref(),
+    );
+}
+
+/// Errors if the LLVM major version of `clang` differs from the one that wrote
+/// the bitcode, as the bundle couldn't be imported then.
+fn check_clang_version(sess: &Session, clang: &Path, llvm_version: (u32, u32, u32)) {
+    let output = match Command::new(clang).arg("--version").output() {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => sess.fatal(&format!(
+            "`{} --version` failed: {}",
+            clang.display(),
+            String::from_utf8_lossy(&output.stderr)
+        )),
+        Err(err) => sess.fatal(&format!("failed to run `{}`: {}", clang.display(), err)),
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let clang_version = stdout.lines().next().and_then(|line| {
+        let version = line.split("version ").nth(1)?.split_whitespace().next()?;
+        let mut parts = version.split('.').map(|part| part.parse::<u32>().ok());
+        Some((parts.next()??, version.to_string()))
+    });
+
+    let (clang_major, clang_version) = match clang_version {
+        Some(version) => version,
+        None => sess.fatal(&format!(
+            "could not determine the LLVM version of `{}` from `{}`",
+            clang.display(),
+            stdout.trim()
+        )),
+    };
+
+    let (major, minor, patch) = llvm_version;
+    if clang_major != major {
+        sess.struct_err(&format!(
+            "the LTO bitcode bundle can't be imported by `{}`",
+            clang.display()
+        ))
+        .note(&format!(
+            "`{}` is based on LLVM {}, but rustc uses LLVM {}.{}.{}",
+            clang.display(),
+            clang_version,
+            major,
+            minor,
+            patch
+        ))
+        .help(&format!("use a `clang` based on LLVM {}", major))
+        .emit();
+    }
+}
@@ -1,9 +1,15 @@
 pub mod archive;
 pub mod command;
+pub mod export_map;
 pub mod link;
 pub mod linker;
+pub mod linker_errors;
 pub mod lto;
+pub mod lto_bundle;
 pub mod metadata;
+pub mod remarks;
+pub mod reproducible;
 pub mod rpath;
+pub mod stack_sizes;
 pub mod symbol_export;
 pub mod write;
@@ -0,0 +1,99 @@
+//! Reporting of the optimization remarks requested with `-C remark`.
+//!
+//! The remarks of the backend name the functions by their symbols and point
+//! to the debug location of the code they are about. They are reported as
+//! notes, with the symbols demangled and the debug location mapped back to a
+//! span of the crate's source where possible, so that editors can show them
+//! next to the code.
+
+use rustc_session::Session;
+use rustc_span::{BytePos, Span};
+
+use std::cmp;
+use std::path::{Path, PathBuf};
+
+/// An optimization remark, sent by the backend from its worker threads.
+#[derive(Debug)]
+pub struct OptimizationRemark {
+    /// Whether the optimization was applied, missed, etc.
+    pub kind: &'static str,
+    pub pass_name: String,
+    /// The symbol of the function the remark is about.
+    pub function: String,
+    /// The file name, line and (1-based) column of the remark's debug location.
+    pub location: Option<(String, u32, u32)>,
+    pub message: String,
+}
+
+pub fn emit(sess: &Session, remark: OptimizationRemark) {
+    let span = remark
+        .location
+        .as_ref()
+        .and_then(|(filename, line, column)| remark_span(sess, filename, *line, *column));
+    // Mention the location in the message if it's outside of the source map.
+    let at = match (&remark.location, span) {
+        (Some((filename, line, column)), None) => format!(" at {}:{}:{}", filename, line, column),
+        _ => String::new(),
+    };
+    let msg = format!(
+        "optimization {} for {} in `{}`{}: {}",
+        remark.kind,
+        remark.pass_name,
+        demangle_symbols(&remark.function),
+        at,
+        demangle_symbols(&remark.message)
+    );
+
+    let mut diag = sess.struct_note_without_error(&msg);
+    if let Some(span) = span {
+        diag.set_span(span);
+    }
+    diag.emit();
+}
+
+/// Finds the span of a debug location. The file name of a debug location is
+/// made absolute with the working directory, like the debuginfo of the crate
+/// does for local files.
+fn remark_span(sess: &Session, filename: &str, line: u32, column: u32) -> Option<Span> {
+    let path = Path::new(filename);
+    let files = sess.source_map().files();
+    let file = files.iter().find(|file| {
+        let name = PathBuf::from(file.name.prefer_remapped().to_string());
+        if file.is_real_file() && !file.is_imported() {
+            sess.opts.working_dir.remapped_path_if_available().join(name) == path
+        } else {
+            name == path
+        }
+    })?;
+
+    let line_index = (line as usize).checked_sub(1)?;
+    if line_index >= file.lines.len() {
+        return None;
+    }
+    let bounds = file.line_bounds(line_index);
+    let pos = cmp::min(bounds.start + BytePos(column.saturating_sub(1)), bounds.end);
+    Some(Span::with_root_ctxt(pos, pos))
+}
+
+/// Demangles the Rust symbols in the message of a remark.
+fn demangle_symbols(msg: &str) -> String {
+    let is_symbol_char = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '$' || c == '.';
+    let mut demangled = String::with_capacity(msg.len());
+    let mut rest = msg;
+    while let Some(start) = rest.find(is_symbol_char) {
+        demangled.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest.find(|c| !is_symbol_char(c)).unwrap_or(rest.len());
+        let token = &rest[..end];
+        match rustc_demangle::try_demangle(token) {
+            Ok(symbol) => demangled.push_str(&format!("{:#}", symbol)),
+            Err(_) => demangled.push_str(token),
+        }
+        rest = &rest[end..];
+    }
+    demangled.push_str(rest);
+    demangled
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,20 @@
+use super::demangle_symbols;
+
+#[test]
+fn test_demangle_symbols() {
+    assert_eq!(
+        demangle_symbols(
+            "'_ZN4main3foo17h0123456789abcdefE' inlined into '_ZN4main4main17hfedcba9876543210E' \
+             with (cost=-15000, threshold=225) at callsite _ZN4main4main17hfedcba9876543210E:3:5;"
+        ),
+        "'main::foo' inlined into 'main::main' with (cost=-15000, threshold=225) \
+         at callsite main::main:3:5;"
+    );
+    assert_eq!(demangle_symbols("_RNvCs1234_4main3foo"), "main::foo");
+    assert_eq!(
+        demangle_symbols("loop not vectorized: call instruction cannot be vectorized"),
+        "loop not vectorized: call instruction cannot be vectorized"
+    );
+    assert_eq!(demangle_symbols("'memcpy' not inlined"), "'memcpy' not inlined");
+    assert_eq!(demangle_symbols(""), "");
+}
@@ -0,0 +1,145 @@
+//! Comparison of the output artifacts of two compilations of the same crate,
+//! used by `-Z verify-reproducible` to point at the parts of an artifact that
+//! are not reproducible.
+
+use object::read::archive::ArchiveFile;
+use object::{Object, ObjectSection};
+
+/// Describes every difference between two builds of the same artifact. The
+/// result is empty if they are identical.
+///
+/// Archives like rlibs are compared member by member, including the fields of
+/// their member headers, and object files section by section. Everything else,
+/// like standalone metadata, is compared byte by byte.
+pub fn diff_artifacts(first: &[u8], second: &[u8]) -> Vec<String> {
+    let mut differences = Vec::new();
+    diff_data("", first, second, &mut differences);
+    differences
+}
+
+fn diff_data(context: &str, first: &[u8], second: &[u8], differences: &mut Vec<String>) {
+    if first == second {
+        return;
+    }
+
+    if let (Ok(first_archive), Ok(second_archive)) =
+        (ArchiveFile::parse(first), ArchiveFile::parse(second))
+    {
+        return diff_archives(
+            context,
+            (first, &first_archive),
+            (second, &second_archive),
+            differences,
+        );
+    }
+
+    if let (Ok(first_object), Ok(second_object)) =
+        (object::File::parse(first), object::File::parse(second))
+    {
+        return diff_objects(context, &first_object, &second_object, differences);
+    }
+
+    differences.push(format!("{}{}", context, describe_first_difference(first, second)));
+}
+
+fn diff_archives(
+    context: &str,
+    (first_data, first): (&[u8], &ArchiveFile<'_>),
+    (second_data, second): (&[u8], &ArchiveFile<'_>),
+    differences: &mut Vec<String>,
+) {
+    let first_members: Vec<_> = first.members().filter_map(Result::ok).collect();
+    let second_members: Vec<_> = second.members().filter_map(Result::ok).collect();
+
+    let member_names = |members: &[object::read::archive::ArchiveMember<'_>]| {
+        members.iter().map(|member| String::from_utf8_lossy(member.name())).collect::<Vec<_>>()
+    };
+    let first_names = member_names(&first_members);
+    let second_names = member_names(&second_members);
+    if first_names != second_names {
+        differences.push(format!(
+            "{}archive members differ: [{}] vs [{}]",
+            context,
+            first_names.join(", "),
+            second_names.join(", ")
+        ));
+        return;
+    }
+
+    for ((first_member, second_member), name) in
+        first_members.iter().zip(&second_members).zip(&first_names)
+    {
+        let member_context = format!("{}archive member `{}`: ", context, name);
+
+        let (first_header, second_header) = (first_member.header(), second_member.header());
+        let header_fields = [
+            ("timestamp", &first_header.date[..], &second_header.date[..]),
+            ("owner id", &first_header.uid[..], &second_header.uid[..]),
+            ("group id", &first_header.gid[..], &second_header.gid[..]),
+            ("file mode", &first_header.mode[..], &second_header.mode[..]),
+        ];
+        for &(field, first_value, second_value) in &header_fields {
+            if first_value != second_value {
+                differences.push(format!(
+                    "{}{} in the member header differs (`{}` vs `{}`)",
+                    member_context,
+                    field,
+                    String::from_utf8_lossy(first_value).trim_end(),
+                    String::from_utf8_lossy(second_value).trim_end()
+                ));
+            }
+        }
+
+        match (first_member.data(first_data), second_member.data(second_data)) {
+            (Ok(first), Ok(second)) => diff_data(&member_context, first, second, differences),
+            _ => differences.push(format!("{}member data could not be read", member_context)),
+        }
+    }
+}
+
+fn diff_objects<'data>(
+    context: &str,
+    first: &object::File<'data>,
+    second: &object::File<'data>,
+    differences: &mut Vec<String>,
+) {
+    let previous_len = differences.len();
+
+    for section in first.sections() {
+        let name = section.name().unwrap_or("<unnamed>");
+        match second.section_by_name(name) {
+            None => differences
+                .push(format!("{}section `{}` only exists in the first build", context, name)),
+            Some(other) => match (section.data(), other.data()) {
+                (Ok(first), Ok(second)) if first != second => differences.push(format!(
+                    "{}section `{}` differs: {}",
+                    context,
+                    name,
+                    describe_first_difference(first, second)
+                )),
+                _ => {}
+            },
+        }
+    }
+
+    for section in second.sections() {
+        let name = section.name().unwrap_or("<unnamed>");
+        if first.section_by_name(name).is_none() {
+            differences
+                .push(format!("{}section `{}` only exists in the second build", context, name));
+        }
+    }
+
+    if differences.len() == previous_len {
+        // All sections are equal, so the difference is in the layout of the
+        // file, e.g. in its headers or symbol table.
+        differences.push(format!("{}object file headers differ", context));
+    }
+}
+
+fn describe_first_difference(first: &[u8], second: &[u8]) -> String {
+    match first.iter().zip(second).position(|(a, b)| a != b) {
+        Some(offset) => format!("first difference at byte offset {:#x}", offset),
+        None => format!("sizes differ ({} vs {} bytes)", first.len(), second.len()),
+    }
+}
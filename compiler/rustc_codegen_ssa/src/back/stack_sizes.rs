@@ -0,0 +1,316 @@
+//! The report of `-Z emit-stack-sizes-report`.
+//!
+//! The frame size of every function is read from the `.stack_sizes` sections
+//! that LLVM emits with `-Z emit-stack-sizes`. Together with the call graph of
+//! the crate, this gives the worst-case stack usage of every entry point: the
+//! entry function of an executable and every exported function.
+//!
+//! The worst case is unbounded if a recursive function or an indirect call,
+//! through a function pointer or a trait object, can be reached. Functions of
+//! other crates aren't part of the call graph, so their stack usage is
+//! unknown and the worst case is only a lower bound if they are called.
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_middle::mir::mono::{CallTarget, MonoItem};
+use rustc_middle::ty::{Instance, TyCtxt};
+use rustc_session::config::{CrateType, OutputFilenames};
+use rustc_session::Session;
+
+use object::{Object, ObjectSection, ObjectSymbol, RelocationTarget, SymbolKind};
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+/// The functions codegened for the crate and the calls they make, by symbol
+/// name.
+#[derive(Debug, Encodable, Decodable)]
+pub struct CallGraph {
+    pub functions: FxHashMap<String, Vec<Callee>>,
+    /// The symbols of the entry function and the exported functions.
+    pub entry_points: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Encodable, Decodable)]
+pub enum Callee {
+    Symbol(String),
+    Indirect,
+}
+
+impl CallGraph {
+    pub fn new(tcx: TyCtxt<'_>, exported_symbols: &FxHashMap<CrateType, Vec<String>>) -> Self {
+        let mut functions = FxHashMap::default();
+        for cgu in tcx.collect_and_partition_mono_items(()).1 {
+            for mono_item in cgu.items().keys() {
+                if let MonoItem::Fn(instance) = *mono_item {
+                    let callees = tcx
+                        .instance_call_targets(instance)
+                        .iter()
+                        .map(|target| match *target {
+                            CallTarget::Instance(callee) => {
+                                Callee::Symbol(tcx.symbol_name(callee).name.to_string())
+                            }
+                            CallTarget::Indirect => Callee::Indirect,
+                        })
+                        .collect();
+                    functions.insert(tcx.symbol_name(instance).name.to_string(), callees);
+                }
+            }
+        }
+
+        let mut entry_points: Vec<String> = exported_symbols
+            .values()
+            .flatten()
+            .filter(|symbol| functions.contains_key(*symbol))
+            .cloned()
+            .collect();
+        if let Some((def_id, _)) = tcx.entry_fn(()) {
+            entry_points.push(tcx.symbol_name(Instance::mono(tcx, def_id)).name.to_string());
+        }
+        entry_points.sort();
+        entry_points.dedup();
+
+        CallGraph { functions, entry_points }
+    }
+}
+
+/// Writes the report for the object files of the crate to `<crate>.stack-sizes`.
+pub fn write_report<'a>(
+    sess: &Session,
+    call_graph: &CallGraph,
+    objects: impl Iterator<Item = &'a Path>,
+    outputs: &OutputFilenames,
+    crate_name: &str,
+) {
+    let mut frames = FxHashMap::default();
+    for path in objects {
+        let result = fs::read(path)
+            .map_err(|err| err.to_string())
+            .and_then(|data| read_stack_sizes(&data, &mut frames));
+        if let Err(err) = result {
+            sess.warn(&format!("failed to read the stack sizes of `{}`: {}", path.display(), err));
+        }
+    }
+
+    let usages = FxHashMap::default();
+    let report = Report { call_graph, frames: &frames, usages, stack: vec![] }.render(crate_name);
+    let path = outputs.with_extension("stack-sizes");
+    if let Err(err) = fs::write(&path, report) {
+        sess.err(&format!("failed to write `{}`: {}", path.display(), err));
+    }
+}
+
+/// Adds the frame sizes of the `.stack_sizes` sections of an object file to
+/// `frames`. Every entry of such a section is the address of a function,
+/// relocated against the function's symbol, followed by the size of its frame
+/// as ULEB128.
+fn read_stack_sizes(data: &[u8], frames: &mut FxHashMap<String, u64>) -> Result<(), String> {
+    let file = object::File::parse(data).map_err(|err| err.to_string())?;
+    let address_size = if file.is_64() { 8 } else { 4 };
+    for section in file.sections().filter(|section| section.name() == Ok(".stack_sizes")) {
+        let contents = section.data().map_err(|err| err.to_string())?;
+        let relocations: FxHashMap<u64, _> = section.relocations().collect();
+        let mut offset = 0;
+        while offset + address_size <= contents.len() {
+            let relocation = relocations
+                .get(&(offset as u64))
+                .ok_or_else(|| format!("no relocation at offset {:#x}", offset))?;
+            let mut addend = relocation.addend();
+            if relocation.has_implicit_addend() {
+                let bytes = &contents[offset..offset + address_size];
+                addend += read_address(bytes, file.is_little_endian()) as i64;
+            }
+            let name = match relocation.target() {
+                RelocationTarget::Symbol(index) => {
+                    let symbol = file.symbol_by_index(index).map_err(|err| err.to_string())?;
+                    if symbol.kind() == SymbolKind::Section {
+                        // Relocations against local functions may use the
+                        // symbol of their section instead.
+                        file.symbols()
+                            .find(|function| {
+                                function.kind() == SymbolKind::Text
+                                    && function.section_index() == symbol.section_index()
+                                    && function.address() as i64 == addend
+                            })
+                            .and_then(|function| function.name().ok())
+                    } else {
+                        symbol.name().ok()
+                    }
+                }
+                _ => None,
+            };
+            offset += address_size;
+            let (size, len) = read_uleb128(&contents[offset..])
+                .ok_or_else(|| format!("truncated entry at offset {:#x}", offset))?;
+            offset += len;
+            if let Some(name) = name {
+                frames.insert(name.to_string(), size);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn read_address(bytes: &[u8], little_endian: bool) -> u64 {
+    let mut value = 0;
+    for i in 0..bytes.len() {
+        let byte = if little_endian { bytes[bytes.len() - 1 - i] } else { bytes[i] };
+        value = value << 8 | u64::from(byte);
+    }
+    value
+}
+
+/// Returns the value and the length of a ULEB128 number.
+fn read_uleb128(bytes: &[u8]) -> Option<(u64, usize)> {
+    let mut value = 0;
+    for (i, &byte) in bytes.iter().enumerate().take(10) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some((value, i + 1));
+        }
+    }
+    None
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+enum Bound {
+    Bytes(u64),
+    IndirectCall,
+    Recursion,
+}
+
+/// The worst-case stack usage of a function, including its callees.
+#[derive(Clone, Debug)]
+struct Usage {
+    bound: Bound,
+    /// The calls of the worst case, starting at the function. Ends at the
+    /// function that makes the indirect call or the recursive one if the
+    /// usage is unbounded.
+    path: Vec<String>,
+    /// The functions without a known stack usage that may be called.
+    unknown: BTreeSet<String>,
+}
+
+struct Report<'a> {
+    call_graph: &'a CallGraph,
+    frames: &'a FxHashMap<String, u64>,
+    usages: FxHashMap<&'a str, Usage>,
+    /// The functions whose usage is being computed, to detect recursion.
+    stack: Vec<&'a str>,
+}
+
+impl<'a> Report<'a> {
+    fn usage(&mut self, symbol: &'a str) -> Usage {
+        if let Some(usage) = self.usages.get(symbol) {
+            return usage.clone();
+        }
+        if self.stack.contains(&symbol) {
+            return Usage {
+                bound: Bound::Recursion,
+                path: vec![symbol.to_string()],
+                unknown: Default::default(),
+            };
+        }
+
+        let call_graph = self.call_graph;
+        let callees = match call_graph.functions.get(symbol) {
+            Some(callees) => callees,
+            // A function of another crate.
+            None => {
+                let (bytes, unknown) = match self.frames.get(symbol) {
+                    Some(&bytes) => (bytes, BTreeSet::new()),
+                    None => (0, [symbol.to_string()].into_iter().collect()),
+                };
+                let path = vec![symbol.to_string()];
+                return Usage { bound: Bound::Bytes(bytes), path, unknown };
+            }
+        };
+
+        self.stack.push(symbol);
+        let mut worst: Option<Usage> = None;
+        let mut unknown = BTreeSet::new();
+        for callee in callees {
+            let usage = match callee {
+                Callee::Symbol(callee) => self.usage(callee),
+                Callee::Indirect => {
+                    Usage { bound: Bound::IndirectCall, path: vec![], unknown: Default::default() }
+                }
+            };
+            unknown.extend(usage.unknown.iter().cloned());
+            if worst.as_ref().map_or(true, |worst| usage.bound > worst.bound) {
+                worst = Some(usage);
+            }
+        }
+        self.stack.pop();
+
+        // Functions that were inlined everywhere don't have a frame of their own.
+        let frame = self.frames.get(symbol).copied().unwrap_or(0);
+        let mut path = vec![symbol.to_string()];
+        let bound = match worst {
+            Some(worst) => {
+                path.extend(worst.path);
+                match worst.bound {
+                    Bound::Bytes(bytes) => Bound::Bytes(frame + bytes),
+                    bound => bound,
+                }
+            }
+            None => Bound::Bytes(frame),
+        };
+        let usage = Usage { bound, path, unknown };
+        self.usages.insert(symbol, usage.clone());
+        usage
+    }
+
+    fn render(mut self, crate_name: &str) -> String {
+        let mut out = String::new();
+        writeln!(out, "stack usage of crate `{}`, in bytes", crate_name).unwrap();
+
+        writeln!(out, "\nframes:").unwrap();
+        let mut frames: Vec<_> = self
+            .frames
+            .iter()
+            .filter(|(symbol, _)| self.call_graph.functions.contains_key(*symbol))
+            .map(|(symbol, &size)| (size, demangle(symbol)))
+            .collect();
+        frames.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+        for (size, function) in frames {
+            writeln!(out, "{:>10}  {}", size, function).unwrap();
+        }
+
+        writeln!(out, "\nentry points:").unwrap();
+        let call_graph = self.call_graph;
+        for entry_point in &call_graph.entry_points {
+            let usage = self.usage(entry_point);
+            let summary = match usage.bound {
+                Bound::Bytes(bytes) if usage.unknown.is_empty() => bytes.to_string(),
+                Bound::Bytes(bytes) => format!("at least {}", bytes),
+                Bound::IndirectCall => "unbounded (indirect call)".to_string(),
+                Bound::Recursion => "unbounded (recursion)".to_string(),
+            };
+            writeln!(out, "{}: {}", demangle(entry_point), summary).unwrap();
+            for function in &usage.path {
+                let frame = match self.frames.get(function) {
+                    Some(size) => size.to_string(),
+                    None if call_graph.functions.contains_key(function) => "inlined".to_string(),
+                    None => "unknown".to_string(),
+                };
+                writeln!(out, "{:>10}  {}", frame, demangle(function)).unwrap();
+            }
+            if !usage.unknown.is_empty() {
+                let unknown: BTreeSet<_> = usage.unknown.iter().map(|s| demangle(s)).collect();
+                let unknown: Vec<_> = unknown.into_iter().collect();
+                writeln!(out, "    calls functions of unknown stack usage: {}", unknown.join(", "))
+                    .unwrap();
+            }
+        }
+        out
+    }
+}
+
+fn demangle(symbol: &str) -> String {
+    format!("{:#}", rustc_demangle::demangle(symbol))
+}
+
+#[cfg(test)]
+mod tests;
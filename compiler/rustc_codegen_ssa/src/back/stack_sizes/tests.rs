@@ -0,0 +1,95 @@
+use super::{read_address, read_uleb128, Bound, CallGraph, Callee, Report};
+
+use rustc_data_structures::fx::FxHashMap;
+
+fn call_graph(functions: &[(&str, &[&str])], entry_points: &[&str]) -> CallGraph {
+    let functions = functions
+        .iter()
+        .map(|&(name, callees)| {
+            let callees = callees
+                .iter()
+                .map(|&callee| match callee {
+                    "*" => Callee::Indirect,
+                    _ => Callee::Symbol(callee.to_string()),
+                })
+                .collect();
+            (name.to_string(), callees)
+        })
+        .collect();
+    let entry_points = entry_points.iter().map(|s| s.to_string()).collect();
+    CallGraph { functions, entry_points }
+}
+
+fn frames(frames: &[(&str, u64)]) -> FxHashMap<String, u64> {
+    frames.iter().map(|&(name, size)| (name.to_string(), size)).collect()
+}
+
+fn report<'a>(call_graph: &'a CallGraph, frames: &'a FxHashMap<String, u64>) -> Report<'a> {
+    Report { call_graph, frames, usages: FxHashMap::default(), stack: vec![] }
+}
+
+#[test]
+fn test_read_uleb128() {
+    assert_eq!(read_uleb128(&[0x00]), Some((0, 1)));
+    assert_eq!(read_uleb128(&[0x7f, 0xff]), Some((127, 1)));
+    assert_eq!(read_uleb128(&[0x80, 0x01]), Some((128, 2)));
+    assert_eq!(read_uleb128(&[0xe5, 0x8e, 0x26]), Some((624485, 3)));
+    assert_eq!(read_uleb128(&[0x80, 0x80]), None);
+    assert_eq!(read_uleb128(&[]), None);
+}
+
+#[test]
+fn test_read_address() {
+    assert_eq!(read_address(&[0x10, 0x20, 0, 0], true), 0x2010);
+    assert_eq!(read_address(&[0, 0, 0x20, 0x10], false), 0x2010);
+    assert_eq!(read_address(&[1, 0, 0, 0, 0, 0, 0, 0], true), 1);
+}
+
+#[test]
+fn test_worst_case_path() {
+    let graph = call_graph(
+        &[("main", &["small", "large"]), ("small", &[]), ("large", &["leaf"]), ("leaf", &[])],
+        &["main"],
+    );
+    let frames = frames(&[("main", 16), ("small", 200), ("large", 64), ("leaf", 160)]);
+    let usage = report(&graph, &frames).usage("main");
+    assert_eq!(usage.bound, Bound::Bytes(240));
+    assert_eq!(usage.path, ["main", "large", "leaf"]);
+    assert!(usage.unknown.is_empty());
+}
+
+#[test]
+fn test_inlined_and_unknown_functions() {
+    let graph = call_graph(&[("main", &["inlined"]), ("inlined", &["extern"])], &["main"]);
+    let frames = frames(&[("main", 32)]);
+    let usage = report(&graph, &frames).usage("main");
+    assert_eq!(usage.bound, Bound::Bytes(32));
+    assert_eq!(usage.path, ["main", "inlined", "extern"]);
+    assert_eq!(usage.unknown.into_iter().collect::<Vec<_>>(), ["extern"]);
+}
+
+#[test]
+fn test_unbounded() {
+    let graph = call_graph(
+        &[
+            ("recursive", &["helper"]),
+            ("helper", &["recursive"]),
+            ("indirect", &["leaf", "*"]),
+            ("leaf", &[]),
+            ("both", &["indirect", "recursive"]),
+        ],
+        &[],
+    );
+    let frames = frames(&[("recursive", 16), ("helper", 16), ("indirect", 16), ("leaf", 1024)]);
+    let mut report = report(&graph, &frames);
+
+    let usage = report.usage("recursive");
+    assert_eq!(usage.bound, Bound::Recursion);
+    assert_eq!(usage.path, ["recursive", "helper", "recursive"]);
+
+    let usage = report.usage("indirect");
+    assert_eq!(usage.bound, Bound::IndirectCall);
+    assert_eq!(usage.path, ["indirect"]);
+
+    assert_eq!(report.usage("both").bound, Bound::Recursion);
+}
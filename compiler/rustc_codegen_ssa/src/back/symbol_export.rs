@@ -11,9 +11,10 @@
 use rustc_middle::middle::exported_symbols::{
     metadata_symbol_name, ExportedSymbol, SymbolExportLevel,
 };
+use rustc_middle::mir::mono::{Linkage, MonoItem, Visibility};
 use rustc_middle::ty::query::Providers;
 use rustc_middle::ty::subst::{GenericArgKind, SubstsRef};
-use rustc_middle::ty::Instance;
+use rustc_middle::ty::{Instance, InstanceDef};
 use rustc_middle::ty::{SymbolName, TyCtxt};
 use rustc_session::config::CrateType;
 use rustc_target::spec::SanitizerSet;
@@ -215,58 +216,99 @@ fn exported_symbols_provider_local(
         symbols.push((exported_symbol, SymbolExportLevel::Rust));
     }
 
-    if tcx.sess.opts.share_generics() && tcx.local_crate_exports_generics() {
-        use rustc_middle::mir::mono::{Linkage, MonoItem, Visibility};
-        use rustc_middle::ty::InstanceDef;
+    // The monomorphizations that downstream crates can link to have to be exported as well.
+    symbols.extend(tcx.exported_monomorphizations(LOCAL_CRATE).iter().map(|&instance| {
+        let exported_symbol = match instance.def {
+            InstanceDef::Item(def) => ExportedSymbol::Generic(def.did, instance.substs),
+            InstanceDef::DropGlue(_, Some(ty)) => ExportedSymbol::DropGlue(ty),
+            _ => ExportedSymbol::Shim(instance),
+        };
+        (exported_symbol, SymbolExportLevel::Rust)
+    }));
 
-        // Normally, we require that shared monomorphizations are not hidden,
-        // because if we want to re-use a monomorphization from a Rust dylib, it
-        // needs to be exported.
-        // However, on platforms that don't allow for Rust dylibs, having
-        // external linkage is enough for monomorphization to be linked to.
-        let need_visibility = tcx.sess.target.dynamic_linking && !tcx.sess.target.only_cdylib;
+    // Sort so we get a stable incr. comp. hash.
+    symbols.sort_by_cached_key(|s| s.0.symbol_name_for_local_instance(tcx));
 
-        let (_, cgus) = tcx.collect_and_partition_mono_items(());
+    tcx.arena.alloc_from_iter(symbols)
+}
 
-        for (mono_item, &(linkage, visibility)) in cgus.iter().flat_map(|cgu| cgu.items().iter()) {
-            if linkage != Linkage::External {
-                // We can only re-use things with external linkage, otherwise
-                // we'll get a linker error
-                continue;
-            }
+fn exported_monomorphizations_provider_local(
+    tcx: TyCtxt<'tcx>,
+    cnum: CrateNum,
+) -> &'tcx [Instance<'tcx>] {
+    assert_eq!(cnum, LOCAL_CRATE);
 
-            if need_visibility && visibility == Visibility::Hidden {
-                // If we potentially share things from Rust dylibs, they must
-                // not be hidden
-                continue;
-            }
+    if !tcx.sess.opts.output_types.should_codegen()
+        || !tcx.sess.opts.share_generics()
+        || !tcx.local_crate_exports_generics()
+    {
+        return &[];
+    }
 
-            match *mono_item {
-                MonoItem::Fn(Instance { def: InstanceDef::Item(def), substs }) => {
-                    if substs.non_erasable_generics().next().is_some() {
-                        let symbol = ExportedSymbol::Generic(def.did, substs);
-                        symbols.push((symbol, SymbolExportLevel::Rust));
-                    }
-                }
-                MonoItem::Fn(Instance { def: InstanceDef::DropGlue(_, Some(ty)), substs }) => {
-                    // A little sanity-check
-                    debug_assert_eq!(
-                        substs.non_erasable_generics().next(),
-                        Some(GenericArgKind::Type(ty))
-                    );
-                    symbols.push((ExportedSymbol::DropGlue(ty), SymbolExportLevel::Rust));
-                }
-                _ => {
-                    // Any other symbols don't qualify for sharing
-                }
+    // Normally, we require that shared monomorphizations are not hidden,
+    // because if we want to re-use a monomorphization from a Rust dylib, it
+    // needs to be exported.
+    // However, on platforms that don't allow for Rust dylibs, having
+    // external linkage is enough for monomorphization to be linked to.
+    let need_visibility = tcx.sess.target.dynamic_linking && !tcx.sess.target.only_cdylib;
+
+    let (_, cgus) = tcx.collect_and_partition_mono_items(());
+
+    let mut instances = Vec::new();
+    for (mono_item, &(linkage, visibility)) in cgus.iter().flat_map(|cgu| cgu.items().iter()) {
+        if linkage != Linkage::External {
+            // We can only re-use things with external linkage, otherwise
+            // we'll get a linker error
+            continue;
+        }
+
+        if need_visibility && visibility == Visibility::Hidden {
+            // If we potentially share things from Rust dylibs, they must
+            // not be hidden
+            continue;
+        }
+
+        let instance = match *mono_item {
+            MonoItem::Fn(instance) => instance,
+            MonoItem::Static(..) | MonoItem::GlobalAsm(..) => continue,
+        };
+        match instance.def {
+            InstanceDef::Item(_) => {}
+            InstanceDef::DropGlue(_, Some(ty)) => {
+                // A little sanity-check
+                debug_assert_eq!(
+                    instance.substs.non_erasable_generics().next(),
+                    Some(GenericArgKind::Type(ty))
+                );
             }
+            def if def.is_shareable_shim() => {}
+            _ => {
+                // Any other symbols don't qualify for sharing
+                continue;
+            }
+        }
+        if instance.substs.non_erasable_generics().next().is_some() {
+            instances.push(instance);
         }
     }
 
     // Sort so we get a stable incr. comp. hash.
-    symbols.sort_by_cached_key(|s| s.0.symbol_name_for_local_instance(tcx));
+    instances.sort_by_cached_key(|&instance| tcx.symbol_name(instance));
 
-    tcx.arena.alloc_from_iter(symbols)
+    tcx.arena.alloc_from_iter(instances)
+}
+
+/// Returns a stable ID for each crate, used to deterministically select one of the upstream
+/// crates if several export the same monomorphization.
+fn crate_stable_ids(tcx: TyCtxt<'_>) -> IndexVec<CrateNum, Fingerprint> {
+    let cnums = tcx.crates(());
+    let mut cnum_stable_ids = IndexVec::from_elem_n(Fingerprint::ZERO, cnums.len() + 1);
+
+    for &cnum in cnums.iter() {
+        cnum_stable_ids[cnum] = tcx.def_path_hash(DefId { krate: cnum, index: CRATE_DEF_INDEX }).0;
+    }
+
+    cnum_stable_ids
 }
 
 fn upstream_monomorphizations_provider(
@@ -277,34 +319,25 @@ fn upstream_monomorphizations_provider(
 
     let mut instances: DefIdMap<FxHashMap<_, _>> = Default::default();
 
-    let cnum_stable_ids: IndexVec<CrateNum, Fingerprint> = {
-        let mut cnum_stable_ids = IndexVec::from_elem_n(Fingerprint::ZERO, cnums.len() + 1);
-
-        for &cnum in cnums.iter() {
-            cnum_stable_ids[cnum] =
-                tcx.def_path_hash(DefId { krate: cnum, index: CRATE_DEF_INDEX }).0;
-        }
-
-        cnum_stable_ids
-    };
+    let cnum_stable_ids = crate_stable_ids(tcx);
 
     let drop_in_place_fn_def_id = tcx.lang_items().drop_in_place_fn();
 
     for &cnum in cnums.iter() {
-        for (exported_symbol, _) in tcx.exported_symbols(cnum).iter() {
-            let (def_id, substs) = match *exported_symbol {
-                ExportedSymbol::Generic(def_id, substs) => (def_id, substs),
-                ExportedSymbol::DropGlue(ty) => {
+        for instance in tcx.exported_monomorphizations(cnum).iter() {
+            let (def_id, substs) = match instance.def {
+                InstanceDef::Item(def) => (def.did, instance.substs),
+                InstanceDef::DropGlue(..) => {
                     if let Some(drop_in_place_fn_def_id) = drop_in_place_fn_def_id {
-                        (drop_in_place_fn_def_id, tcx.intern_substs(&[ty.into()]))
+                        (drop_in_place_fn_def_id, instance.substs)
                     } else {
                         // `drop_in_place` in place does not exist, don't try
                         // to use it.
                         continue;
                     }
                 }
-                ExportedSymbol::NonGeneric(..) | ExportedSymbol::NoDefId(..) => {
-                    // These are no monomorphizations
+                _ => {
+                    // These are collected by `upstream_shims`
                     continue;
                 }
             };
@@ -349,6 +382,42 @@ fn upstream_drop_glue_for_provider<'tcx>(
     }
 }
 
+fn upstream_shims_provider(tcx: TyCtxt<'_>, (): ()) -> FxHashMap<Instance<'_>, CrateNum> {
+    let cnum_stable_ids = crate_stable_ids(tcx);
+    let mut shims = FxHashMap::default();
+
+    for &cnum in tcx.crates(()).iter() {
+        for &instance in tcx.exported_monomorphizations(cnum).iter() {
+            if !instance.def.is_shareable_shim() {
+                continue;
+            }
+
+            match shims.entry(instance) {
+                Occupied(mut e) => {
+                    // If there are multiple instances available,
+                    // we select one deterministically.
+                    let other_cnum = *e.get();
+                    if cnum_stable_ids[other_cnum] > cnum_stable_ids[cnum] {
+                        e.insert(cnum);
+                    }
+                }
+                Vacant(e) => {
+                    e.insert(cnum);
+                }
+            }
+        }
+    }
+
+    shims
+}
+
+fn upstream_shim_for_provider<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+) -> Option<CrateNum> {
+    tcx.upstream_shims(()).get(&instance).cloned()
+}
+
 fn is_unreachable_local_definition_provider(tcx: TyCtxt<'_>, def_id: LocalDefId) -> bool {
     !tcx.reachable_set(()).contains(&def_id)
 }
@@ -357,9 +426,12 @@ pub fn provide(providers: &mut Providers) {
     providers.reachable_non_generics = reachable_non_generics_provider;
     providers.is_reachable_non_generic = is_reachable_non_generic_provider_local;
     providers.exported_symbols = exported_symbols_provider_local;
+    providers.exported_monomorphizations = exported_monomorphizations_provider_local;
     providers.upstream_monomorphizations = upstream_monomorphizations_provider;
     providers.is_unreachable_local_definition = is_unreachable_local_definition_provider;
     providers.upstream_drop_glue_for = upstream_drop_glue_for_provider;
+    providers.upstream_shims = upstream_shims_provider;
+    providers.upstream_shim_for = upstream_shim_for_provider;
     providers.wasm_import_module_map = wasm_import_module_map;
 }
 
@@ -430,6 +502,11 @@ pub fn symbol_name_for_instance_in_crate<'tcx>(
             Instance::resolve_drop_in_place(tcx, ty),
             instantiating_crate,
         ),
+        ExportedSymbol::Shim(instance) => rustc_symbol_mangling::symbol_name_for_instance_in_crate(
+            tcx,
+            instance,
+            instantiating_crate,
+        ),
         ExportedSymbol::NoDefId(symbol_name) => symbol_name.to_string(),
     }
 }
@@ -1,5 +1,7 @@
 use super::link::{self, ensure_removed};
 use super::lto::{self, SerializedModule};
+use super::remarks::{self, OptimizationRemark};
+use super::stack_sizes;
 use super::symbol_export::symbol_name_for_instance_in_crate;
 
 use crate::{
@@ -133,6 +135,7 @@ macro_rules! if_regular {
         let opt_level_and_size = if_regular!(Some(sess.opts.optimize), None);
 
         let save_temps = sess.opts.cg.save_temps;
+        let emit_bundle = sess.opts.output_types.contains_key(&OutputType::LtoBitcodeBundle);
 
         let should_emit_obj = sess.opts.output_types.contains_key(&OutputType::Exe)
             || match kind {
@@ -205,8 +208,10 @@ macro_rules! if_regular {
             ),
             emit_no_opt_bc: if_regular!(save_temps, false),
             emit_bc: if_regular!(
-                save_temps || sess.opts.output_types.contains_key(&OutputType::Bitcode),
                 save_temps
+                    || emit_bundle
+                    || sess.opts.output_types.contains_key(&OutputType::Bitcode),
+                save_temps || (emit_bundle && kind == ModuleKind::Allocator)
             ),
             emit_ir: if_regular!(
                 sess.opts.output_types.contains_key(&OutputType::LlvmAssembly),
@@ -579,7 +584,11 @@ fn produce_final_output_artifacts(
                 user_wants_objects = true;
                 copy_if_one_unit(OutputType::Object, true);
             }
-            OutputType::Mir | OutputType::Metadata | OutputType::Exe | OutputType::DepInfo => {}
+            OutputType::Mir
+            | OutputType::Metadata
+            | OutputType::Exe
+            | OutputType::DepInfo
+            | OutputType::LtoBitcodeBundle => {}
         }
     }
 
@@ -601,20 +610,8 @@ fn produce_final_output_artifacts(
         // needed for building an rlib, then we must remove .#module-name#.bc as
         // well.
 
-        // Specific rules for keeping .#module-name#.bc:
-        //  - If the user requested bitcode (`user_wants_bitcode`), and
-        //    codegen_units > 1, then keep it.
-        //  - If the user requested bitcode but codegen_units == 1, then we
-        //    can toss .#module-name#.bc because we copied it to .bc earlier.
-        //  - If we're not building an rlib and the user didn't request
-        //    bitcode, then delete .#module-name#.bc.
-        // If you change how this works, also update back::link::link_rlib,
-        // where .#module-name#.bc files are (maybe) deleted after making an
-        // rlib.
         let needs_crate_object = crate_output.outputs.contains_key(&OutputType::Exe);
 
-        let keep_numbered_bitcode = user_wants_bitcode && sess.codegen_units() > 1;
-
         let keep_numbered_objects =
             needs_crate_object || (user_wants_objects && sess.codegen_units() > 1);
 
@@ -630,12 +627,6 @@ fn produce_final_output_artifacts(
                     ensure_removed(sess.diagnostic(), path);
                 }
             }
-
-            if let Some(ref path) = module.bytecode {
-                if !keep_numbered_bitcode {
-                    ensure_removed(sess.diagnostic(), path);
-                }
-            }
         }
 
         if !user_wants_bitcode {
@@ -644,12 +635,16 @@ fn produce_final_output_artifacts(
                     ensure_removed(sess.diagnostic(), &path);
                 }
             }
+        }
 
-            if let Some(ref allocator_module) = compiled_modules.allocator_module {
-                if let Some(ref path) = allocator_module.bytecode {
-                    ensure_removed(sess.diagnostic(), path);
-                }
-            }
+        // The LTO bitcode bundle is written when linking, which removes the
+        // bitcode afterwards.
+        if !crate_output.outputs.contains_key(&OutputType::LtoBitcodeBundle) {
+            remove_temporary_bitcode(
+                sess,
+                &compiled_modules.modules,
+                compiled_modules.allocator_module.as_ref(),
+            );
         }
     }
 
@@ -660,6 +655,44 @@ fn produce_final_output_artifacts(
     // These are used in linking steps and will be cleaned up afterward.
 }
 
+/// Removes the `.#module-name#.bc` files of the given modules, unless the user
+/// asked for them.
+pub fn remove_temporary_bitcode(
+    sess: &Session,
+    modules: &[CompiledModule],
+    allocator_module: Option<&CompiledModule>,
+) {
+    if sess.opts.cg.save_temps {
+        return;
+    }
+
+    // Specific rules for keeping .#module-name#.bc:
+    //  - If the user requested bitcode, and codegen_units > 1, then keep it.
+    //  - If the user requested bitcode but codegen_units == 1, then we
+    //    can toss .#module-name#.bc because we copied it to .bc earlier.
+    //  - If we're not building an rlib and the user didn't request
+    //    bitcode, then delete .#module-name#.bc.
+    // If you change how this works, also update back::link::link_rlib,
+    // where .#module-name#.bc files are (maybe) deleted after making an
+    // rlib.
+    let user_wants_bitcode = sess.opts.output_types.contains_key(&OutputType::Bitcode);
+    let keep_numbered_bitcode = user_wants_bitcode && sess.codegen_units() > 1;
+
+    if !keep_numbered_bitcode {
+        for module in modules {
+            if let Some(ref path) = module.bytecode {
+                ensure_removed(sess.diagnostic(), path);
+            }
+        }
+    }
+
+    if !user_wants_bitcode {
+        if let Some(path) = allocator_module.and_then(|module| module.bytecode.as_ref()) {
+            ensure_removed(sess.diagnostic(), path);
+        }
+    }
+}
+
 pub enum WorkItem<B: WriteBackendMethods> {
     /// Optimize a newly codegened, totally unoptimized module.
     Optimize(ModuleCodegen<B::Module>),
@@ -769,7 +802,10 @@ pub fn compute_per_cgu_lto_type(
     // If the linker does LTO, we don't have to do it. Note that we
     // keep doing full LTO, if it is requested, as not to break the
     // assumption that the output will be a single module.
-    let linker_does_lto = opts.cg.linker_plugin_lto.enabled();
+    // The same goes for the LTO bitcode bundle, which is the input of an
+    // LTO done by someone else.
+    let linker_does_lto = opts.cg.linker_plugin_lto.enabled()
+        || opts.output_types.contains_key(&OutputType::LtoBitcodeBundle);
 
     // When we're automatically doing ThinLTO for multi-codegen-unit
     // builds we don't actually want to LTO the allocator modules if
@@ -1673,6 +1709,7 @@ fn drop(&mut self) {
 enum SharedEmitterMessage {
     Diagnostic(Diagnostic),
     InlineAsmError(u32, String, Level, Option<(String, Vec<InnerSpan>)>),
+    OptimizationRemark(OptimizationRemark),
     AbortIfErrors,
     Fatal(String),
 }
@@ -1703,6 +1740,10 @@ pub fn inline_asm_error(
         drop(self.sender.send(SharedEmitterMessage::InlineAsmError(cookie, msg, level, source)));
     }
 
+    pub fn optimization_remark(&self, remark: OptimizationRemark) {
+        drop(self.sender.send(SharedEmitterMessage::OptimizationRemark(remark)));
+    }
+
     pub fn fatal(&self, msg: &str) {
         drop(self.sender.send(SharedEmitterMessage::Fatal(msg.to_string())));
     }
@@ -1783,6 +1824,9 @@ pub fn check(&self, sess: &Session, blocking: bool) {
 
                     err.emit();
                 }
+                Ok(SharedEmitterMessage::OptimizationRemark(remark)) => {
+                    remarks::emit(sess, remark);
+                }
                 Ok(SharedEmitterMessage::AbortIfErrors) => {
                     sess.abort_if_errors();
                 }
@@ -1831,6 +1875,16 @@ pub fn join(self, sess: &Session) -> (CodegenResults, FxHashMap<WorkProductId, W
 
         let work_products =
             copy_all_cgu_workproducts_to_incr_comp_cache_dir(sess, &compiled_modules);
+        // The report needs the object files, which may be removed below.
+        if let Some(call_graph) = &self.crate_info.stack_sizes_call_graph {
+            stack_sizes::write_report(
+                sess,
+                call_graph,
+                compiled_modules.modules.iter().filter_map(|module| module.object.as_deref()),
+                &self.output_filenames,
+                &self.crate_info.local_crate_name.as_str(),
+            );
+        }
         produce_final_output_artifacts(sess, &compiled_modules, &self.output_filenames);
 
         // FIXME: time_llvm_passes support - does this use a global context or
@@ -1,3 +1,5 @@
+use crate::back::export_map::ExportMap;
+use crate::back::stack_sizes::CallGraph;
 use crate::back::write::{
     compute_per_cgu_lto_type, start_async_codegen, submit_codegened_module_to_llvm,
     submit_post_lto_module_to_llvm, submit_pre_lto_module_to_llvm, ComputedLtoType, OngoingCodegen,
@@ -26,7 +28,7 @@
 use rustc_middle::ty::query::Providers;
 use rustc_middle::ty::{self, Instance, Ty, TyCtxt};
 use rustc_session::cgu_reuse_tracker::CguReuse;
-use rustc_session::config::{self, EntryFnType};
+use rustc_session::config::{self, CrateType, EntryFnType};
 use rustc_session::Session;
 use rustc_span::symbol::sym;
 use rustc_target::abi::{Align, VariantIdx};
@@ -779,12 +781,39 @@ fn drop(&mut self) {
 
 impl CrateInfo {
     pub fn new(tcx: TyCtxt<'_>, target_cpu: String) -> CrateInfo {
-        let exported_symbols = tcx
+        let export_map = tcx
+            .sess
+            .opts
+            .debugging_opts
+            .export_map
+            .as_ref()
+            .map(|path| (path, ExportMap::load(tcx.sess, path)));
+        let exported_symbols: FxHashMap<_, _> = tcx
             .sess
             .crate_types()
             .iter()
-            .map(|&c| (c, crate::back::linker::exported_symbols(tcx, c)))
+            .map(|&c| {
+                let symbols = crate::back::linker::exported_symbols(tcx, c);
+                match &export_map {
+                    Some((path, map)) if matches!(c, CrateType::Cdylib | CrateType::Staticlib) => {
+                        (c, map.restrict(tcx.sess, c, path, &symbols))
+                    }
+                    _ => (c, symbols),
+                }
+            })
             .collect();
+        let stack_sizes_call_graph = tcx
+            .sess
+            .opts
+            .debugging_opts
+            .emit_stack_sizes_report
+            .then(|| CallGraph::new(tcx, &exported_symbols));
+        let lto_bitcode_bundle_exports =
+            if tcx.sess.opts.output_types.contains_key(&config::OutputType::LtoBitcodeBundle) {
+                crate::back::lto_bundle::exported_symbols(tcx)
+            } else {
+                Vec::new()
+            };
         let local_crate_name = tcx.crate_name(LOCAL_CRATE);
         let crate_attrs = tcx.hir().attrs(rustc_hir::CRATE_HIR_ID);
         let subsystem = tcx.sess.first_attr_value_str_by_name(crate_attrs, sym::windows_subsystem);
@@ -831,6 +860,9 @@ pub fn new(tcx: TyCtxt<'_>, target_cpu: String) -> CrateInfo {
             missing_lang_items: Default::default(),
             dependency_formats: tcx.dependency_formats(()),
             windows_subsystem,
+            lto_bitcode_bundle_exports,
+            export_map: export_map.map(|(_, map)| map),
+            stack_sizes_call_graph,
         };
         let lang_items = tcx.lang_items();
 
@@ -151,6 +151,12 @@ pub struct CrateInfo {
     pub missing_lang_items: FxHashMap<CrateNum, Vec<LangItem>>,
     pub dependency_formats: Lrc<Dependencies>,
     pub windows_subsystem: Option<String>,
+    /// The symbols listed in the LTO bitcode bundle, if one is emitted.
+    pub lto_bitcode_bundle_exports: Vec<String>,
+    /// The export map passed with `-Z export-map`, if any.
+    pub export_map: Option<back::export_map::ExportMap>,
+    /// The call graph for `-Z emit-stack-sizes-report`.
+    pub stack_sizes_call_graph: Option<back::stack_sizes::CallGraph>,
 }
 
 #[derive(Encodable, Decodable)]
@@ -162,6 +162,18 @@ fn struct_generic(
                 for frame_info in &self.stacktrace {
                     err.span_label(frame_info.span, frame_info.to_string());
                 }
+                // Show the values the arguments and variables of the frames had.
+                for frame_info in &self.stacktrace {
+                    let values: Vec<_> = frame_info
+                        .args
+                        .iter()
+                        .map(|arg| format!("argument `{}`", arg))
+                        .chain(frame_info.locals.iter().map(|local| format!("local `{}`", local)))
+                        .collect();
+                    if !values.is_empty() {
+                        err.note(&format!("{}, with {}", frame_info, values.join(", ")));
+                    }
+                }
             }
             // Let the caller finish the job.
             emit(err)
@@ -299,6 +299,11 @@ pub fn eval_to_allocation_raw_provider<'tcx>(
 
     let is_static = tcx.is_static(def.did);
 
+    let profile = tcx.sess.opts.debugging_opts.const_eval_profile;
+    if profile {
+        tcx.sess.code_stats.start_const_eval();
+    }
+
     let mut ecx = InterpCx::new(
         tcx,
         tcx.def_span(def.did),
@@ -310,7 +315,7 @@ pub fn eval_to_allocation_raw_provider<'tcx>(
     );
 
     let res = ecx.load_mir(cid.instance.def, cid.promoted);
-    match res.and_then(|body| eval_body_using_ecx(&mut ecx, cid, &body)) {
+    let result = match res.and_then(|body| eval_body_using_ecx(&mut ecx, cid, &body)) {
         Err(error) => {
             let err = ConstEvalErr::new(&ecx, error, None);
             // Some CTFE errors raise just a lint, not a hard error; see
@@ -396,5 +401,18 @@ pub fn eval_to_allocation_raw_provider<'tcx>(
                 Ok(ConstAlloc { alloc_id, ty: mplace.layout.ty })
             }
         }
+    };
+
+    if profile {
+        // Unlike `GlobalId::display`, this keeps the substs of generic associated constants.
+        let instance = with_no_trimmed_paths(|| cid.instance.to_string());
+        let item = match cid.promoted {
+            Some(promoted) => format!("{}::{:?}", instance, promoted),
+            None => instance,
+        };
+        let allocated_bytes = ecx.memory.allocated_bytes().bytes();
+        tcx.sess.code_stats.record_const_eval_cost(item, ecx.steps, allocated_bytes);
     }
+
+    result
 }
@@ -43,6 +43,9 @@ pub struct InterpCx<'mir, 'tcx, M: Machine<'mir, 'tcx>> {
 
     /// The recursion limit (cached from `tcx.recursion_limit(())`)
     pub recursion_limit: Limit,
+
+    /// The number of statements and terminators that have been evaluated.
+    pub steps: u64,
 }
 
 // The Phantomdata exists to prevent this type from being `Send`. If it were sent across a thread
@@ -125,12 +128,19 @@ pub struct Frame<'mir, 'tcx, Tag: Provenance = AllocId, Extra = ()> {
     pub(super) loc: Result<mir::Location, Span>,
 }
 
+/// The number of innermost frames of an interpreter backtrace whose values are rendered.
+const STACKTRACE_FRAMES_WITH_VALUES: usize = 8;
+
 /// What we store about a frame in an interpreter backtrace.
 #[derive(Debug)]
 pub struct FrameInfo<'tcx> {
     pub instance: ty::Instance<'tcx>,
     pub span: Span,
     pub lint_root: Option<hir::HirId>,
+    /// The rendered values of the arguments and the initialized user variables of the frame,
+    /// as `name = value`. Only captured for the innermost frames of the local crate.
+    pub args: Vec<String>,
+    pub locals: Vec<String>,
 }
 
 /// Unwind information.
@@ -401,6 +411,7 @@ pub fn new(
             param_env,
             memory: Memory::new(tcx, memory_extra),
             recursion_limit: tcx.recursion_limit(),
+            steps: 0,
         }
     }
 
@@ -930,11 +941,12 @@ pub fn dump_place(&'a self, place: Place<M::PointerTag>) -> PlacePrinter<'a, 'mi
     #[must_use]
     pub fn generate_stacktrace(&self) -> Vec<FrameInfo<'tcx>> {
         let mut frames = Vec::new();
-        for frame in self
+        for (i, frame) in self
             .stack()
             .iter()
             .rev()
             .skip_while(|frame| frame.instance.def.requires_caller_location(*self.tcx))
+            .enumerate()
         {
             let lint_root = frame.current_source_info().and_then(|source_info| {
                 match &frame.body.source_scopes[source_info.scope].local_data {
@@ -943,8 +955,15 @@ pub fn generate_stacktrace(&self) -> Vec<FrameInfo<'tcx>> {
                 }
             });
             let span = frame.current_span();
-
-            frames.push(FrameInfo { span, instance: frame.instance, lint_root });
+            // The values of the frames of other crates are mostly library internals.
+            let (args, locals) =
+                if i < STACKTRACE_FRAMES_WITH_VALUES && frame.instance.def_id().is_local() {
+                    self.render_frame_values(frame)
+                } else {
+                    (Vec::new(), Vec::new())
+                };
+
+            frames.push(FrameInfo { span, instance: frame.instance, lint_root, args, locals });
         }
         trace!("generate stacktrace: {:#?}", frames);
         frames
@@ -111,7 +111,13 @@ fn intern_shallow<'rt, 'mir, 'tcx, M: CompileTimeMachine<'mir, 'tcx, const_eval:
     // Set allocation mutability as appropriate. This is used by LLVM to put things into
     // read-only memory, and also by Miri when evaluating other globals that
     // access this one.
-    if let InternMode::Static(mutability) = mode {
+    if kind == MemoryKind::Machine(const_eval::MemoryKind::Heap) {
+        // Heap allocations become immutable static memory, whatever the mutability of the
+        // global and of the type. Validation makes sure that they are never mutated or freed
+        // at runtime.
+        alloc.mutability = Mutability::Not;
+        alloc.heap = true;
+    } else if let InternMode::Static(mutability) = mode {
         // For this, we need to take into account `UnsafeCell`. When `ty` is `None`, we assume
         // no interior mutability.
         let frozen = ty.map_or(true, |ty| ty.is_freeze(ecx.tcx, ecx.param_env));
@@ -359,11 +365,17 @@ pub fn intern_const_alloc_recursive<M: CompileTimeMachine<'mir, 'tcx, const_eval
 
     let mut todo: Vec<_> = leftover_allocations.iter().cloned().collect();
     while let Some(alloc_id) = todo.pop() {
-        if let Some((_, mut alloc)) = ecx.memory.alloc_map.remove(&alloc_id) {
+        if let Some((kind, mut alloc)) = ecx.memory.alloc_map.remove(&alloc_id) {
             // We can't call the `intern_shallow` method here, as its logic is tailored to safe
             // references and a `leftover_allocations` set (where we only have a todo-list here).
             // So we hand-roll the interning logic here again.
             match intern_kind {
+                // Heap allocations are usually only referenced by raw pointers, e.g. in a `Vec`.
+                // Like in `intern_shallow`, they become immutable.
+                _ if kind == MemoryKind::Machine(const_eval::MemoryKind::Heap) => {
+                    alloc.mutability = Mutability::Not;
+                    alloc.heap = true;
+                }
                 // Statics may contain mutable allocations even behind relocations.
                 // Even for immutable statics it would be ok to have mutable allocations behind
                 // raw pointers, e.g. for `static FOO: *const AtomicUsize = &AtomicUsize::new(42)`.
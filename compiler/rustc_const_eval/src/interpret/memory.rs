@@ -109,6 +109,10 @@ pub struct Memory<'mir, 'tcx, M: Machine<'mir, 'tcx>> {
     // FIXME: this should not be public, but interning currently needs access to it
     pub(super) dead_alloc_map: FxHashMap<AllocId, (Size, Align)>,
 
+    /// The total size of the allocations that have been made, including the ones that
+    /// have been deallocated since.
+    allocated: Size,
+
     /// Extra data added by the machine.
     pub extra: M::MemoryExtra,
 
@@ -147,6 +151,7 @@ pub fn new(tcx: TyCtxt<'tcx>, extra: M::MemoryExtra) -> Self {
             alloc_map: M::MemoryMap::default(),
             extra_fn_ptr_map: FxHashMap::default(),
             dead_alloc_map: FxHashMap::default(),
+            allocated: Size::ZERO,
             extra,
             tcx,
         }
@@ -233,11 +238,17 @@ pub fn allocate_with(
             M::GLOBAL_KIND.map(MemoryKind::Machine),
             "dynamically allocating global memory"
         );
+        self.allocated += alloc.size();
         let alloc = M::init_allocation_extra(self, id, Cow::Owned(alloc), Some(kind));
         self.alloc_map.insert(id, (kind, alloc.into_owned()));
         M::tag_alloc_base_pointer(self, Pointer::from(id))
     }
 
+    /// The total size of the allocations made through this memory, for profiling.
+    pub fn allocated_bytes(&self) -> Size {
+        self.allocated
+    }
+
     pub fn reallocate(
         &mut self,
         ptr: Pointer<Option<M::PointerTag>>,
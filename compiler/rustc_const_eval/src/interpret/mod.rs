@@ -9,6 +9,7 @@
 mod operand;
 mod operator;
 mod place;
+mod render;
 mod step;
 mod terminator;
 mod traits;
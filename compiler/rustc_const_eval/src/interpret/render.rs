@@ -0,0 +1,233 @@
+//! Rendering of the values of a stack frame, for the stacktraces of errors.
+//!
+//! The values are rendered similar to their `Debug` output, but truncated: only the first
+//! elements of arrays and the outer levels of nested values are shown.
+
+use rustc_hir::def::CtorKind;
+use rustc_middle::mir;
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::{self, FloatTy};
+
+use super::{
+    FnVal, Frame, GlobalAlloc, Immediate, InterpCx, InterpResult, LocalValue, Machine, OpTy,
+    Provenance,
+};
+
+/// The number of variables of a frame that are rendered, besides its arguments.
+const MAX_LOCALS: usize = 8;
+/// How deep nested values and references are rendered.
+const MAX_DEPTH: usize = 3;
+/// The number of elements of an array or slice that are rendered.
+const MAX_ELEMENTS: u64 = 8;
+/// The length after which a rendered value is cut off.
+const MAX_LEN: usize = 80;
+
+impl<'mir, 'tcx: 'mir, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
+    /// Renders the arguments and the initialized user variables of a frame as `name = value`.
+    pub(super) fn render_frame_values(
+        &self,
+        frame: &Frame<'mir, 'tcx, M::PointerTag, M::FrameExtra>,
+    ) -> (Vec<String>, Vec<String>) {
+        let mut args = Vec::new();
+        let mut locals = Vec::new();
+        for var in &frame.body.var_debug_info {
+            let local = match var.value {
+                mir::VarDebugInfoContents::Place(place) if place.projection.is_empty() => {
+                    place.local
+                }
+                _ => continue,
+            };
+            let is_arg = local.index() <= frame.body.arg_count;
+            if !is_arg && locals.len() == MAX_LOCALS {
+                continue;
+            }
+            // Variables that are dead or not initialized yet have no value to show.
+            if !matches!(frame.locals[local].value, LocalValue::Live(_)) {
+                continue;
+            }
+            let op = match self.access_local(frame, local, None) {
+                Ok(op) => op,
+                Err(_) => continue,
+            };
+            let rendered = format!("{} = {}", var.name, self.render_value(&op));
+            if is_arg {
+                args.push(rendered);
+            } else {
+                locals.push(rendered);
+            }
+        }
+        (args, locals)
+    }
+
+    fn render_value(&self, op: &OpTy<'tcx, M::PointerTag>) -> String {
+        let mut out = String::new();
+        self.write_value(&mut out, op, 0);
+        if out.len() > MAX_LEN {
+            let mut end = MAX_LEN;
+            while !out.is_char_boundary(end) {
+                end -= 1;
+            }
+            out.truncate(end);
+            out.push_str("..");
+        }
+        out
+    }
+
+    fn write_value(&self, out: &mut String, op: &OpTy<'tcx, M::PointerTag>, depth: usize) {
+        if let Err(err) = self.try_write_value(out, op, depth) {
+            out.push_str(match err.kind() {
+                err_ub!(InvalidUninitBytes(_)) => "<uninit>",
+                _ => "<invalid>",
+            });
+        }
+    }
+
+    fn try_write_value(
+        &self,
+        out: &mut String,
+        op: &OpTy<'tcx, M::PointerTag>,
+        depth: usize,
+    ) -> InterpResult<'tcx> {
+        match *op.layout.ty.kind() {
+            ty::Bool => out.push_str(&self.read_scalar(op)?.to_bool()?.to_string()),
+            ty::Char => out.push_str(&format!("{:?}", self.read_scalar(op)?.to_char()?)),
+            ty::Int(_) => {
+                let size = op.layout.size;
+                let bits = self.read_scalar(op)?.check_init()?.to_bits(size)?;
+                out.push_str(&(size.sign_extend(bits) as i128).to_string());
+            }
+            ty::Uint(_) => {
+                let bits = self.read_scalar(op)?.check_init()?.to_bits(op.layout.size)?;
+                out.push_str(&bits.to_string());
+            }
+            ty::Float(FloatTy::F32) => out.push_str(&self.read_scalar(op)?.to_f32()?.to_string()),
+            ty::Float(FloatTy::F64) => out.push_str(&self.read_scalar(op)?.to_f64()?.to_string()),
+            ty::RawPtr(_) => {
+                // Only render the address of wide pointers.
+                let (Immediate::Scalar(ptr) | Immediate::ScalarPair(ptr, _)) =
+                    *self.read_immediate(op)?;
+                out.push_str(&format!("{:?}", self.scalar_to_ptr(ptr.check_init()?)));
+            }
+            ty::FnPtr(_) => {
+                let ptr = self.scalar_to_ptr(self.read_scalar(op)?.check_init()?);
+                match self.memory.get_fn(ptr)? {
+                    FnVal::Instance(instance) => {
+                        out.push_str(&with_no_trimmed_paths(|| instance.to_string()))
+                    }
+                    FnVal::Other(_) => out.push_str(".."),
+                }
+            }
+            ty::Ref(_, _, mutbl) => {
+                let place = self.ref_to_mplace(&self.read_immediate(op)?)?;
+                // Render string slices like literals.
+                if let ty::Str = place.layout.ty.kind() {
+                    let s = self.read_str(&place)?;
+                    match s.char_indices().nth(MAX_LEN) {
+                        Some((end, _)) => out.push_str(&format!("{:?}..", &s[..end])),
+                        None => out.push_str(&format!("{:?}", s)),
+                    }
+                    return Ok(());
+                }
+                out.push('&');
+                out.push_str(mutbl.prefix_str());
+                // Don't evaluate a static just to render it.
+                let alloc_id = place.ptr.provenance.map(Provenance::get_alloc_id);
+                if let Some(GlobalAlloc::Static(def_id)) =
+                    alloc_id.and_then(|alloc_id| self.tcx.get_global_alloc(alloc_id))
+                {
+                    out.push_str(&with_no_trimmed_paths(|| self.tcx.def_path_str(def_id)));
+                } else if depth == MAX_DEPTH {
+                    out.push_str("..");
+                } else {
+                    self.write_value(out, &place.into(), depth + 1);
+                }
+            }
+            _ if depth == MAX_DEPTH => out.push_str(".."),
+            ty::Array(..) | ty::Slice(_) => {
+                let len = match op.try_as_mplace() {
+                    Ok(mplace) => mplace.len(self)?,
+                    Err(_) => op.layout.fields.count() as u64,
+                };
+                out.push('[');
+                for i in 0..len.min(MAX_ELEMENTS) {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    self.write_value(out, &self.operand_index(op, i)?, depth + 1);
+                }
+                if len > MAX_ELEMENTS {
+                    out.push_str(", ..");
+                }
+                out.push(']');
+            }
+            ty::Tuple(fields) => {
+                out.push('(');
+                for i in 0..fields.len() {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    self.write_value(out, &self.operand_field(op, i)?, depth + 1);
+                }
+                if fields.len() == 1 {
+                    out.push(',');
+                }
+                out.push(')');
+            }
+            ty::Adt(adt, _) if adt.is_enum() => {
+                let (_, index) = self.read_discriminant(op)?;
+                let variant = &adt.variants[index];
+                out.push_str(&format!("{}::{}", self.tcx.item_name(adt.did), variant.ident));
+                self.write_fields(out, &self.operand_downcast(op, index)?, variant, depth)?;
+            }
+            ty::Adt(adt, _) => {
+                out.push_str(&self.tcx.item_name(adt.did).to_string());
+                let variant = adt.non_enum_variant();
+                // The private fields of types of other crates are implementation details, and
+                // the fields of unions would have to be guessed.
+                let private = !adt.did.is_local()
+                    && variant.fields.iter().any(|field| field.vis != ty::Visibility::Public);
+                if adt.is_union() || private {
+                    out.push_str(match variant.ctor_kind {
+                        CtorKind::Fn => "(..)",
+                        CtorKind::Fictive => " { .. }",
+                        CtorKind::Const => "",
+                    });
+                } else {
+                    self.write_fields(out, op, variant, depth)?;
+                }
+            }
+            _ => out.push_str(".."),
+        }
+        Ok(())
+    }
+
+    fn write_fields(
+        &self,
+        out: &mut String,
+        op: &OpTy<'tcx, M::PointerTag>,
+        variant: &ty::VariantDef,
+        depth: usize,
+    ) -> InterpResult<'tcx> {
+        let (open, close) = match variant.ctor_kind {
+            CtorKind::Fn => ("(", ")"),
+            CtorKind::Fictive => (" { ", " }"),
+            CtorKind::Const => return Ok(()),
+        };
+        if variant.fields.is_empty() {
+            out.push_str(if variant.ctor_kind == CtorKind::Fn { "()" } else { " {}" });
+            return Ok(());
+        }
+        out.push_str(open);
+        for (i, field) in variant.fields.iter().enumerate() {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            if variant.ctor_kind == CtorKind::Fictive {
+                out.push_str(&format!("{}: ", field.ident));
+            }
+            self.write_value(out, &self.operand_field(op, i)?, depth + 1);
+        }
+        out.push_str(close);
+        Ok(())
+    }
+}
@@ -56,6 +56,7 @@ pub fn step(&mut self) -> InterpResult<'tcx, bool> {
                 return Ok(true);
             }
         };
+        self.steps += 1;
         let basic_block = &self.body().basic_blocks()[loc.block];
 
         let old_frames = self.frame_idx();
@@ -11,8 +11,8 @@
 use rustc_data_structures::fx::FxHashSet;
 use rustc_hir as hir;
 use rustc_middle::mir::interpret::InterpError;
-use rustc_middle::ty;
 use rustc_middle::ty::layout::{LayoutOf, TyAndLayout};
+use rustc_middle::ty::{self, Ty};
 use rustc_span::symbol::{sym, Symbol};
 use rustc_target::abi::{Abi, Scalar as ScalarAbi, Size, VariantIdx, Variants, WrappingRange};
 
@@ -20,7 +20,7 @@
 
 use super::{
     alloc_range, CheckInAllocMsg, GlobalAlloc, InterpCx, InterpResult, MPlaceTy, Machine,
-    MemPlaceMeta, OpTy, ScalarMaybeUninit, ValueVisitor,
+    MemPlaceMeta, OpTy, Pointer, ScalarMaybeUninit, ValueVisitor,
 };
 
 macro_rules! throw_validation_failure {
@@ -208,6 +208,12 @@ struct ValidityVisitor<'rt, 'mir, 'tcx, M: Machine<'mir, 'tcx>> {
     ref_tracking: Option<&'rt mut RefTracking<MPlaceTy<'tcx, M::PointerTag>, Vec<PathElem>>>,
     /// `None` indicates this is not validating for CTFE (but for runtime).
     ctfe_mode: Option<CtfeValidationMode>,
+    /// Whether the values we validate may be dropped at runtime, freeing the heap memory they
+    /// own: the top-level value of a `const` is copied for each use, and values in mutable
+    /// memory may be replaced.
+    may_drop: bool,
+    /// The outermost value that needs to be dropped, if we are inside of one and it may be.
+    owner: Option<Ty<'tcx>>,
     ecx: &'rt InterpCx<'mir, 'tcx, M>,
 }
 
@@ -286,6 +292,22 @@ fn aggregate_field_path_elem(&mut self, layout: TyAndLayout<'tcx>, field: usize)
         }
     }
 
+    /// Whether `ptr` points to heap memory made during const evaluation, during CTFE. The value
+    /// has been interned by then, so this includes the heap memory of other globals.
+    fn is_heap_pointer(&self, ptr: Pointer<Option<M::PointerTag>>) -> bool {
+        if self.ctfe_mode.is_none() {
+            return false;
+        }
+        let alloc_id = match self.ecx.memory.ptr_try_get_alloc(ptr) {
+            Ok((alloc_id, ..)) => alloc_id,
+            Err(_) => return false,
+        };
+        match self.ecx.tcx.get_global_alloc(alloc_id) {
+            Some(GlobalAlloc::Memory(alloc)) => alloc.heap,
+            _ => false,
+        }
+    }
+
     fn with_elem<R>(
         &mut self,
         elem: PathElem,
@@ -427,6 +449,32 @@ fn check_safe_pointer(
             err_ub!(PointerUseAfterFree(..)) =>
                 { "a dangling {} (use-after-free)", kind },
         );
+        // Heap memory in the final value of a global is immutable at runtime, and must never
+        // be freed.
+        if self.is_heap_pointer(place.ptr) {
+            if let ty::Ref(_, _, hir::Mutability::Mut) = value.layout.ty.kind() {
+                throw_validation_failure!(self.path,
+                    { "a mutable reference to a heap allocation" }
+                    expected { "a shared reference, as heap allocations are immutable at runtime" }
+                );
+            }
+            if !place.layout.ty.is_freeze(self.ecx.tcx, self.ecx.param_env) {
+                throw_validation_failure!(self.path,
+                    { "a {} to a heap allocation with interior mutability", kind }
+                    expected { "a frozen type, as heap allocations are immutable at runtime" }
+                );
+            }
+            if value.layout.ty.is_box() {
+                if let Some(owner) = self.owner.or(self.may_drop.then(|| value.layout.ty)) {
+                    throw_validation_failure!(self.path,
+                        {
+                            "a heap allocation owned by `{}`, which may be dropped at runtime",
+                            owner
+                        }
+                    );
+                }
+            }
+        }
         // Recursive checking
         if let Some(ref mut ref_tracking) = self.ref_tracking {
             // Proceed recursively even for ZST, no reason to skip them!
@@ -547,6 +595,16 @@ fn try_visit_primitive(
                 if place.layout.is_unsized() {
                     self.check_wide_ptr_meta(place.meta, place.layout)?;
                 }
+                if let Some(owner) = self.owner {
+                    if self.is_heap_pointer(place.ptr) {
+                        throw_validation_failure!(self.path,
+                            {
+                                "a heap allocation owned by `{}`, which may be dropped at runtime",
+                                owner
+                            }
+                        );
+                    }
+                }
                 Ok(true)
             }
             ty::Ref(_, ty, mutbl) => {
@@ -765,8 +823,19 @@ fn visit_value(&mut self, op: &OpTy<'tcx, M::PointerTag>) -> InterpResult<'tcx>
             }
         }
 
+        // Remember the outermost value that owns the heap memory it points to, if it may be
+        // dropped at runtime.
+        let owner = self.owner;
+        if self.may_drop
+            && self.owner.is_none()
+            && op.layout.ty.needs_drop(*self.ecx.tcx, self.ecx.param_env)
+        {
+            self.owner = Some(op.layout.ty);
+        }
+
         // Recursively walk the value at its type.
         self.walk_value(op)?;
+        self.owner = owner;
 
         // *After* all of this, check the ABI.  We need to check the ABI to handle
         // types like `NonNull` where the `Scalar` info is more restrictive than what
@@ -913,8 +982,24 @@ fn validate_operand_internal(
     ) -> InterpResult<'tcx> {
         trace!("validate_operand_internal: {:?}, {:?}", *op, op.layout.ty);
 
+        let may_drop = match ctfe_mode {
+            Some(CtfeValidationMode::Const { inner, .. }) => !inner,
+            // The values of statics are only ever dropped if they are replaced.
+            Some(CtfeValidationMode::Regular) => op
+                .try_as_mplace()
+                .ok()
+                .and_then(|mplace| self.memory.ptr_try_get_alloc(mplace.ptr).ok())
+                .and_then(|(alloc_id, ..)| self.tcx.get_global_alloc(alloc_id))
+                .map_or(false, |alloc| match alloc {
+                    GlobalAlloc::Memory(alloc) => alloc.mutability == hir::Mutability::Mut,
+                    _ => false,
+                }),
+            None => false,
+        };
+
         // Construct a visitor
-        let mut visitor = ValidityVisitor { path, ref_tracking, ctfe_mode, ecx: self };
+        let mut visitor =
+            ValidityVisitor { path, ref_tracking, ctfe_mode, may_drop, owner: None, ecx: self };
 
         // Run it.
         match visitor.visit_value(&op) {
@@ -942,6 +1027,9 @@ fn validate_operand_internal(
     /// `constant` controls whether this must satisfy the rules for constants:
     /// - no pointers to statics.
     /// - no `UnsafeCell` or non-ZST `&mut`.
+    ///
+    /// In all modes, heap memory must not be reachable in a way that lets it be mutated or
+    /// freed at runtime.
     #[inline(always)]
     pub fn const_validate_operand(
         &self,
@@ -54,6 +54,7 @@
 
 pub mod args;
 pub mod pretty;
+mod reproducible;
 
 /// Exit status code used for successful compilation and help output.
 pub const EXIT_SUCCESS: i32 = 0;
@@ -289,6 +290,7 @@ fn run_compiler(
 
     interface::run_compiler(config, |compiler| {
         let sess = compiler.session();
+        let mut reproducible_artifacts = Vec::new();
         let should_stop = RustcDefaultCalls::print_crate_info(
             &***compiler.codegen_backend(),
             sess,
@@ -410,6 +412,17 @@ fn run_compiler(
                 sess.code_stats.print_type_sizes();
             }
 
+            if sess.opts.debugging_opts.const_eval_profile {
+                sess.code_stats.print_const_eval_profile();
+            }
+
+            if sess.opts.debugging_opts.verify_reproducible {
+                let outputs = queries.prepare_outputs()?.peek();
+                let crate_name = queries.crate_name()?.peek();
+                reproducible_artifacts =
+                    reproducible::output_artifacts(sess, &outputs, &crate_name);
+            }
+
             let linker = queries.linker()?;
             Ok(Some(linker))
         })?;
@@ -419,6 +432,11 @@ fn run_compiler(
             linker.link()?
         }
 
+        if !reproducible_artifacts.is_empty() {
+            let _timer = sess.timer("verify_reproducible");
+            reproducible::verify(sess, &args, &reproducible_artifacts)?;
+        }
+
         if sess.opts.debugging_opts.perf_stats {
             sess.print_perf_stats();
         }
@@ -1346,6 +1364,7 @@ pub(super) fn install() {}
 }
 
 pub fn main() -> ! {
+    reproducible::reverse_env_if_requested();
     let start_time = Instant::now();
     let start_rss = get_resident_set_size();
     init_rustc_env_logger();
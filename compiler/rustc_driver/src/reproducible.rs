@@ -0,0 +1,381 @@
+//! Implementation of `-Z verify-reproducible`.
+//!
+//! After the crate has been compiled, the compiler is run a second time with
+//! the same arguments but under different conditions, and every output
+//! artifact of the two compilations is compared. The second compilation
+//!
+//! - writes its outputs into a different directory,
+//! - runs in a different working directory, in which the relative paths of the
+//!   command line are linked to the files they refer to,
+//! - gets its environment variables in reverse order,
+//! - runs with a different `-Z threads` and without the jobserver, so both
+//!   the front end and code generation use a different number of threads, and
+//! - runs in a different process, so it gets different `HashMap` seeds.
+
+use rustc_codegen_ssa::back::reproducible::diff_artifacts;
+use rustc_errors::ErrorReported;
+use rustc_session::config::{OutputFilenames, OutputType};
+use rustc_session::output::{filename_for_metadata, out_filename};
+use rustc_session::Session;
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+/// The maximum number of differences reported for a single artifact.
+const MAX_REPORTED_DIFFERENCES: usize = 10;
+
+/// Set for the second compilation, which then reverses the order of its environment variables.
+const REVERSE_ENV_VAR: &str = "RUSTC_VERIFY_REPRODUCIBLE_REVERSE_ENV";
+
+/// Reverses the order of the environment variables of this process if it is the second
+/// compilation of `-Z verify-reproducible`.
+///
+/// `Command` passes the environment to the child process sorted by name, so it can't reverse
+/// it itself. This must run before any other thread is started.
+pub fn reverse_env_if_requested() {
+    if env::var_os(REVERSE_ENV_VAR).is_none() {
+        return;
+    }
+    env::remove_var(REVERSE_ENV_VAR);
+    let vars: Vec<_> = env::vars_os().collect();
+    for (key, _) in &vars {
+        env::remove_var(key);
+    }
+    // Variables that are set are appended to the environment.
+    for (key, value) in vars.into_iter().rev() {
+        env::set_var(key, value);
+    }
+}
+
+/// Returns the paths of all artifacts written by this compilation that should
+/// be reproducible.
+pub fn output_artifacts(
+    sess: &Session,
+    outputs: &OutputFilenames,
+    crate_name: &str,
+) -> Vec<PathBuf> {
+    let mut artifacts = Vec::new();
+    for output_type in sess.opts.output_types.keys() {
+        match *output_type {
+            OutputType::Exe => {
+                for &crate_type in sess.crate_types().iter() {
+                    artifacts.push(out_filename(sess, crate_type, outputs, crate_name));
+                }
+            }
+            OutputType::Metadata => {
+                artifacts.push(filename_for_metadata(sess, crate_name, outputs));
+            }
+            // The dep-info file records the paths of the outputs, which always
+            // differ between the two compilations.
+            OutputType::DepInfo => {}
+            output_type => artifacts.push(outputs.path(output_type)),
+        }
+    }
+    artifacts
+}
+
+/// Compiles the crate a second time and reports every artifact that differs
+/// from the one produced by the first compilation.
+pub fn verify(sess: &Session, args: &[String], artifacts: &[PathBuf]) -> Result<(), ErrorReported> {
+    if sess.opts.incremental.is_some() {
+        sess.warn("`-Z verify-reproducible` has no effect on incremental builds");
+        return Ok(());
+    }
+
+    let temp_dir = match tempfile::Builder::new().prefix("rustc-reproducible").tempdir() {
+        Ok(temp_dir) => temp_dir,
+        Err(err) => {
+            sess.err(&format!("failed to create a temporary directory: {}", err));
+            return Err(ErrorReported);
+        }
+    };
+    let out_dir = temp_dir.path().join("out");
+    let work_dir = temp_dir.path().join("cwd");
+    for dir in [&out_dir, &work_dir] {
+        if let Err(err) = fs::create_dir(dir) {
+            sess.err(&format!("failed to create directory `{}`: {}", dir.display(), err));
+            return Err(ErrorReported);
+        }
+    }
+    let original_work_dir = env::current_dir().unwrap_or_default();
+
+    // Link each relative path into the new working directory, so that it still refers to the
+    // same file and is recorded the same way. The paths that can't be linked, like those that
+    // start with `..`, are made absolute instead.
+    let program = env::current_exe().unwrap_or_else(|_| PathBuf::from(&args[0]));
+    let mut absolutized = Vec::new();
+    let args = map_paths(&redirect_outputs(&args[1..], &out_dir), |path| {
+        if path.is_absolute() || link_relative_path(path, &original_work_dir, &work_dir).is_ok() {
+            return None;
+        }
+        absolutized.push(path.display().to_string());
+        Some(original_work_dir.join(path))
+    });
+
+    let mut command = Command::new(program);
+    command.args(args);
+    // Later `-Z` flags override earlier ones.
+    command.arg("-Zverify-reproducible=no");
+    // Optimization fuel requires a single thread.
+    let debugging_opts = &sess.opts.debugging_opts;
+    if debugging_opts.fuel.is_none() && debugging_opts.print_fuel.is_none() {
+        let threads = if debugging_opts.threads == 1 { 2 } else { 1 };
+        command.arg(format!("-Zthreads={}", threads));
+    }
+    command.current_dir(&work_dir);
+
+    for jobserver_var in ["CARGO_MAKEFLAGS", "MAKEFLAGS", "MFLAGS"] {
+        command.env_remove(jobserver_var);
+    }
+    command.env(REVERSE_ENV_VAR, "1");
+
+    let output = match command.output() {
+        Ok(output) => output,
+        Err(err) => {
+            sess.err(&format!("failed to run the second compilation: {}", err));
+            return Err(ErrorReported);
+        }
+    };
+    if !output.status.success() {
+        sess.struct_err("the second compilation for `-Z verify-reproducible` failed")
+            .note(&String::from_utf8_lossy(&output.stderr))
+            .emit();
+        return Err(ErrorReported);
+    }
+
+    if !absolutized.is_empty() {
+        sess.note_without_error(&format!(
+            "`-Z verify-reproducible` made the relative paths {} absolute for the second \
+             compilation, which may cause differences of its own",
+            absolutized.iter().map(|path| format!("`{}`", path)).collect::<Vec<_>>().join(", ")
+        ));
+    }
+
+    for first_path in artifacts {
+        let second_path = match first_path.file_name() {
+            Some(file_name) => out_dir.join(file_name),
+            None => continue,
+        };
+        let (first, second) = match (fs::read(first_path), fs::read(&second_path)) {
+            (Ok(first), Ok(second)) => (first, second),
+            (Err(err), _) | (_, Err(err)) => {
+                sess.err(&format!(
+                    "failed to read output artifact `{}`: {}",
+                    first_path.display(),
+                    err
+                ));
+                continue;
+            }
+        };
+
+        let differences = diff_artifacts(&first, &second);
+        if differences.is_empty() {
+            continue;
+        }
+
+        let mut diag = sess
+            .struct_err(&format!("output artifact `{}` is not reproducible", first_path.display()));
+        for difference in differences.iter().take(MAX_REPORTED_DIFFERENCES) {
+            diag.note(difference);
+        }
+        if differences.len() > MAX_REPORTED_DIFFERENCES {
+            diag.note(&format!(
+                "and {} more differences",
+                differences.len() - MAX_REPORTED_DIFFERENCES
+            ));
+        }
+        if contains_path(&first, &original_work_dir) && contains_path(&second, &work_dir) {
+            diag.help(
+                "the artifact contains the working directory, \
+                 consider using `--remap-path-prefix` or `-Z remap-cwd-prefix`",
+            );
+        }
+        if let Some(first_out_dir) = first_path.parent() {
+            if contains_path(&first, first_out_dir) && contains_path(&second, &out_dir) {
+                diag.help("the artifact contains the path of the output directory");
+            }
+        }
+        diag.emit();
+    }
+
+    sess.compile_status()
+}
+
+/// Points the outputs of the second compilation to `out_dir`.
+fn redirect_outputs(args: &[String], out_dir: &Path) -> Vec<String> {
+    let mut redirected = Vec::with_capacity(args.len() + 2);
+    let mut has_out_dir = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        redirected.push(arg.clone());
+        match &arg[..] {
+            "--out-dir" => {
+                args.next();
+                redirected.push(out_dir.display().to_string());
+                has_out_dir = true;
+            }
+            "-o" => {
+                if let Some(file_name) = args.next().and_then(|path| Path::new(path).file_name()) {
+                    redirected.push(out_dir.join(file_name).display().to_string());
+                }
+                has_out_dir = true;
+            }
+            arg if arg.starts_with("-o") => {
+                if let Some(file_name) = Path::new(&arg[2..]).file_name() {
+                    *redirected.last_mut().unwrap() =
+                        format!("-o{}", out_dir.join(file_name).display());
+                }
+                has_out_dir = true;
+            }
+            arg if arg.starts_with("--out-dir=") => {
+                *redirected.last_mut().unwrap() = format!("--out-dir={}", out_dir.display());
+                has_out_dir = true;
+            }
+            _ => {}
+        }
+    }
+    if !has_out_dir {
+        redirected.push("--out-dir".to_string());
+        redirected.push(out_dir.display().to_string());
+    }
+    redirected
+}
+
+/// Calls `f` on every input or search path on the command line, and replaces the path with
+/// the one `f` returns, if any.
+fn map_paths(args: &[String], mut f: impl FnMut(&Path) -> Option<PathBuf>) -> Vec<String> {
+    let mut map = |path: &str| match f(Path::new(path)) {
+        Some(path) => path.display().to_string(),
+        None => path.to_string(),
+    };
+    let mut mapped = Vec::with_capacity(args.len());
+    let mut args = args.iter().map(|arg| &arg[..]);
+    while let Some(arg) = args.next() {
+        let arg = match arg {
+            "-L" | "-C" | "--codegen" | "-Z" | "--extern" | "--target" => {
+                mapped.push(arg.to_string());
+                match args.next() {
+                    Some(value) => map_option(arg, value, &mut map),
+                    None => break,
+                }
+            }
+            // Options that take a value that is not a path.
+            "-o" | "--out-dir" | "--cfg" | "--crate-type" | "--crate-name" | "--edition"
+            | "--emit" | "--print" | "--cap-lints" | "--error-format" | "--json" | "--color"
+            | "-A" | "-W" | "-D" | "-F" | "-l" => {
+                mapped.push(arg.to_string());
+                match args.next() {
+                    Some(value) => value.to_string(),
+                    None => break,
+                }
+            }
+            _ if arg.starts_with("--extern=") => {
+                format!("--extern={}", map_option("--extern", &arg[9..], &mut map))
+            }
+            _ if arg.starts_with("--codegen=") => {
+                format!("--codegen={}", map_option("-C", &arg[10..], &mut map))
+            }
+            _ if arg.starts_with("-L") || arg.starts_with("-C") || arg.starts_with("-Z") => {
+                format!("{}{}", &arg[..2], map_option(&arg[..2], &arg[2..], &mut map))
+            }
+            // The input file. `-` reads from stdin, which is not a path.
+            _ if arg.starts_with('-') => arg.to_string(),
+            _ => map(arg),
+        };
+        mapped.push(arg);
+    }
+    mapped
+}
+
+/// Maps the paths in the value of `option`.
+fn map_option(option: &str, value: &str, map: &mut impl FnMut(&str) -> String) -> String {
+    match option {
+        // Strips the kind from a `-L [KIND=]PATH` argument.
+        "-L" => match value.split_once('=') {
+            Some((kind, path)) if !kind.contains(std::path::is_separator) => {
+                format!("{}={}", kind, map(path))
+            }
+            _ => map(value),
+        },
+        "-C" | "--codegen" | "-Z" => map_codegen_option(value, map),
+        // `--extern NAME[=PATH]`.
+        "--extern" => match value.split_once('=') {
+            Some((name, path)) => format!("{}={}", name, map(path)),
+            None => value.to_string(),
+        },
+        // A target can be given as the path to a JSON target specification.
+        "--target" if value.ends_with(".json") => map(value),
+        _ => value.to_string(),
+    }
+}
+
+/// Maps the path in the value of a `-C` or `-Z` option, if it has one.
+fn map_codegen_option(option: &str, map: &mut impl FnMut(&str) -> String) -> String {
+    // The options whose value is the path of an input file.
+    const PATH_OPTIONS: &[&str] = &[
+        "cgu_partitioning_profile",
+        "export_map",
+        "lint_config",
+        "profile_sample_use",
+        "profile_use",
+    ];
+    // The options whose value is a program, which is only looked up relative to the working
+    // directory if it contains a path separator.
+    const PROGRAM_OPTIONS: &[&str] = &["linker", "lto_bitcode_bundle_clang"];
+
+    let (name, value) = match option.split_once('=') {
+        Some((name, value)) => (name, value),
+        None => return option.to_string(),
+    };
+    let normalized_name = name.replace('-', "_");
+    if PATH_OPTIONS.contains(&&normalized_name[..])
+        || (PROGRAM_OPTIONS.contains(&&normalized_name[..])
+            && value.contains(std::path::is_separator))
+    {
+        format!("{}={}", name, map(value))
+    } else {
+        option.to_string()
+    }
+}
+
+/// Links the first component of the relative `path` into `work_dir`, so that `path` refers to
+/// the same file from `work_dir` as from `original_work_dir`.
+fn link_relative_path(path: &Path, original_work_dir: &Path, work_dir: &Path) -> io::Result<()> {
+    let first = match path.components().find(|component| *component != Component::CurDir) {
+        Some(Component::Normal(first)) => first,
+        _ => return Err(io::Error::new(io::ErrorKind::Other, "not a plain relative path")),
+    };
+    let link = work_dir.join(first);
+    if link.symlink_metadata().is_ok() {
+        // Linked for another path already.
+        return Ok(());
+    }
+    symlink(&original_work_dir.join(first), &link)
+}
+
+#[cfg(unix)]
+fn symlink(target: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn symlink(target: &Path, link: &Path) -> io::Result<()> {
+    if target.is_dir() {
+        std::os::windows::fs::symlink_dir(target, link)
+    } else {
+        std::os::windows::fs::symlink_file(target, link)
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn symlink(_target: &Path, _link: &Path) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Other, "symbolic links are not supported"))
+}
+
+fn contains_path(haystack: &[u8], path: &Path) -> bool {
+    let path = path.to_string_lossy();
+    !path.is_empty() && haystack.windows(path.len()).any(|window| window == path.as_bytes())
+}
@@ -681,6 +681,14 @@ pub fn set(&self, features: &mut Features, span: Span) {
     /// Allows using the `non_exhaustive_omitted_patterns` lint.
     (active, non_exhaustive_omitted_patterns_lint, "1.57.0", Some(89554), None),
 
+    /// Allows using `#[on_unimplemented(..)]` on the traits of user crates.
+    /// The name was used before 1.40.0 for what is now `#[rustc_on_unimplemented]`.
+    // FIXME: open a tracking issue before proposing to stabilize the attribute.
+    (active, on_unimplemented, "1.57.0", None, None),
+
+    /// Allows asserting the layout of types with `#[assert_layout(..)]`.
+    (active, assert_layout, "1.57.0", None, None),
+
     // -------------------------------------------------------------------------
     // feature-group-end: actual feature gates
     // -------------------------------------------------------------------------
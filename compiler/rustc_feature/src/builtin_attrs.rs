@@ -216,6 +216,16 @@ macro_rules! experimental {
         ),
     ),
 
+    // Diagnostics:
+    gated!(
+        on_unimplemented, Normal,
+        template!(
+            List: r#"/*opt*/ message = "...", /*opt*/ label = "...", /*opt*/ note = "...""#,
+            NameValueStr: "label"
+        ),
+        experimental!(on_unimplemented)
+    ),
+
     // Crate properties:
     ungated!(crate_name, CrateLevel, template!(NameValueStr: "name")),
     ungated!(crate_type, CrateLevel, template!(NameValueStr: "bin|lib|...")),
@@ -343,6 +353,11 @@ macro_rules! experimental {
         "`default_method_body_is_const` is a temporary placeholder for declaring default bodies \
         as `const`, which may be removed or renamed in the future."
     ),
+    gated!(
+        assert_layout, Normal,
+        template!(List: "size = N, align = N, offset(field = N, ...), niche = true|false"),
+        experimental!(assert_layout)
+    ),
 
     // ==========================================================================
     // Internal attributes: Stability, deprecation, and unsafe:
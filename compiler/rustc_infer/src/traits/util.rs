@@ -224,9 +224,22 @@ fn elaborate(&mut self, obligation: &PredicateObligation<'tcx>) {
 
                             Component::UnresolvedInferenceVariable(_) => None,
 
-                            Component::Projection(_) | Component::EscapingProjection(_) => {
+                            Component::Projection(projection) => {
+                                // `Foo<<T as Trait>::Assoc<'a>>: 'b` implies
+                                // `<T as Trait>::Assoc<'a>: 'b`. This is how
+                                // the `where Self: 'b` bound of a generic
+                                // associated type makes the associated types
+                                // stored in `Self` outlive `'b`.
+                                let ty =
+                                    tcx.mk_projection(projection.item_def_id, projection.substs);
+                                Some(ty::PredicateKind::TypeOutlives(ty::OutlivesPredicate(
+                                    ty, r_min,
+                                )))
+                            }
+
+                            Component::EscapingProjection(_) => {
                                 // We can probably do more here. This
-                                // corresponds to a case like `<T as
+                                // corresponds to a case like `for<'a> <T as
                                 // Foo<'a>>::U: 'b`.
                                 None
                             }
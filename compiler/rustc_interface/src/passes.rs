@@ -229,6 +229,7 @@ pub fn register_plugins<'a>(
             registrar(&mut registry);
         }
     });
+    lint_store.load_lint_config(sess, &krate.attrs);
 
     Ok((krate, lint_store))
 }
@@ -975,6 +976,8 @@ fn analysis(tcx: TyCtxt<'_>, (): ()) -> Result<()> {
         );
     });
 
+    sess.time("unused_pub_items_checking", || rustc_passes::dead::check_unused_pub_items(tcx));
+
     Ok(())
 }
 
@@ -353,13 +353,10 @@ pub fn link(self) -> Result<()> {
         // any more, we can finalize it (which involves renaming it)
         rustc_incremental::finalize_session_directory(&self.sess, self.crate_hash);
 
-        if !self
-            .sess
-            .opts
-            .output_types
-            .keys()
-            .any(|&i| i == OutputType::Exe || i == OutputType::Metadata)
-        {
+        // The LTO bitcode bundle is written by the backend's `link` as well.
+        if !self.sess.opts.output_types.keys().any(|&i| {
+            i == OutputType::Exe || i == OutputType::Metadata || i == OutputType::LtoBitcodeBundle
+        }) {
             return Ok(());
         }
 
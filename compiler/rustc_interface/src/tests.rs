@@ -636,6 +636,7 @@ macro_rules! untracked {
     untracked!(ast_json, true);
     untracked!(ast_json_noexpand, true);
     untracked!(borrowck, String::from("other"));
+    untracked!(const_eval_profile, true);
     untracked!(deduplicate_diagnostics, false);
     untracked!(dep_tasks, true);
     untracked!(dont_buffer_diagnostics, true);
@@ -645,6 +646,7 @@ macro_rules! untracked {
     untracked!(dump_mir_dir, String::from("abc"));
     untracked!(dump_mir_exclude_pass_number, true);
     untracked!(dump_mir_graphviz, true);
+    untracked!(dump_trait_selection, Some(String::from("abc")));
     untracked!(emit_future_incompat_report, true);
     untracked!(emit_stack_sizes, true);
     untracked!(future_incompat_test, true);
@@ -658,6 +660,7 @@ macro_rules! untracked {
     untracked!(link_native_libraries, false);
     untracked!(llvm_time_trace, true);
     untracked!(ls, true);
+    untracked!(lto_bitcode_bundle_clang, Some(PathBuf::from("clang")));
     untracked!(macro_backtrace, true);
     untracked!(meta_stats, true);
     untracked!(nll_facts, true);
@@ -672,7 +675,9 @@ macro_rules! untracked {
     untracked!(profile_closures, true);
     untracked!(print_link_args, true);
     untracked!(print_llvm_passes, true);
+    untracked!(print_mono_item_groups, true);
     untracked!(print_mono_items, Some(String::from("abc")));
+    untracked!(print_polymorphization_stats, true);
     untracked!(print_type_sizes, true);
     untracked!(proc_macro_backtrace, true);
     untracked!(query_dep_graph, true);
@@ -683,6 +688,7 @@ macro_rules! untracked {
     untracked!(span_debug, true);
     untracked!(span_free_formats, true);
     untracked!(strip, Strip::Debuginfo);
+    untracked!(suggest_imports_from_all_crates, true);
     untracked!(terminal_width, Some(80));
     untracked!(threads, 99);
     untracked!(time, true);
@@ -695,6 +701,7 @@ macro_rules! untracked {
     untracked!(unstable_options, true);
     untracked!(validate_mir, true);
     untracked!(verbose, true);
+    untracked!(verify_reproducible, true);
 
     macro_rules! tracked {
         ($name: ident, $non_default_value: expr) => {
@@ -712,6 +719,7 @@ macro_rules! tracked {
     tracked!(assume_incomplete_release, true);
     tracked!(asm_comments, true);
     tracked!(binary_dep_depinfo, true);
+    tracked!(cgu_partitioning_profile, Some(PathBuf::from("abc")));
     tracked!(chalk, true);
     tracked!(codegen_backend, Some("abc".to_string()));
     tracked!(crate_attr, vec!["abc".to_string()]);
@@ -719,6 +727,8 @@ macro_rules! tracked {
     tracked!(debug_macros, true);
     tracked!(dep_info_omit_d_target, true);
     tracked!(dual_proc_macros, true);
+    tracked!(emit_stack_sizes_report, true);
+    tracked!(export_map, Some(PathBuf::from("exports.map")));
     tracked!(fewer_names, Some(true));
     tracked!(force_overflow_checks, Some(true));
     tracked!(force_unstable_if_unmarked, true);
@@ -748,6 +758,7 @@ macro_rules! tracked {
     tracked!(partially_uninit_const_threshold, Some(123));
     tracked!(plt, Some(true));
     tracked!(polonius, true);
+    tracked!(polymorphize, Some(true));
     tracked!(precise_enum_drop_elaboration, false);
     tracked!(print_fuel, Some("abc".to_string()));
     tracked!(profile, true);
@@ -775,6 +786,7 @@ macro_rules! tracked {
     tracked!(trap_unreachable, Some(false));
     tracked!(treat_err_as_bug, NonZeroUsize::new(1));
     tracked!(unleash_the_miri_inside_of_you, true);
+    tracked!(unused_pub_items, true);
     tracked!(use_ctors_section, Some(true));
     tracked!(verify_llvm_ir, true);
     tracked!(wasi_exec_model, Some(WasiExecModel::Reactor));
@@ -787,6 +799,7 @@ macro_rules! tracked_no_crate_hash {
             assert_non_crate_hash_different(&reference, &opts);
         };
     }
+    tracked_no_crate_hash!(lint_config, Some(PathBuf::from("lints.toml")));
     tracked_no_crate_hash!(no_codegen, true);
 }
 
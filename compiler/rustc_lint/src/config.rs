@@ -0,0 +1,324 @@
+//! Lint levels read from a configuration file given with `-Z lint-config=<path>`.
+//!
+//! The file is a TOML document of the following shape:
+//!
+//! ```toml
+//! [lints]
+//! missing_docs = "warn"
+//! unused = "deny"
+//! "clippy::needless_return" = "allow"
+//!
+//! [[overrides]]
+//! paths = ["src/generated"]
+//! lints = { missing_docs = "allow", dead_code = "allow" }
+//! ```
+//!
+//! The `[lints]` table applies to the whole crate, as if each entry was passed on the command
+//! line. Flags that actually are passed on the command line take precedence over it. Entries
+//! are applied in the order they appear in the file, so a lint can be set after its group to
+//! override the group's level.
+//!
+//! Each `[[overrides]]` table applies its `lints` to every item that is defined in a source
+//! file below one of its `paths`. Relative paths are resolved against the directory that
+//! contains the configuration file. Lint attributes within the matching files still take
+//! precedence over the overrides.
+//!
+//! The configuration file is loaded into the `SourceMap`, so diagnostics about the lint levels
+//! it sets point into it, and it is recorded in the dep-info file like any other source file.
+//!
+//! The file is read and its lint names are resolved once per session, when the `LintStore` is
+//! complete. Problems with the lint names are only reported by the early lint pass, the same
+//! way as problems with the lint names of attributes.
+
+use crate::context::{parse_lint_and_tool_name, CheckLintNameResult, LintStore};
+use rustc_ast as ast;
+use rustc_data_structures::sync::Lrc;
+use rustc_session::lint::{Level, LintId};
+use rustc_session::parse::feature_err;
+use rustc_session::Session;
+use rustc_span::{BytePos, FileName, SourceFile, Span, Symbol};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use toml::Spanned;
+
+type RawLintTable = BTreeMap<Spanned<String>, Spanned<String>>;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawLintConfig {
+    #[serde(default)]
+    lints: RawLintTable,
+    #[serde(default)]
+    overrides: Vec<RawPathOverride>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawPathOverride {
+    paths: Vec<String>,
+    #[serde(default)]
+    lints: RawLintTable,
+}
+
+/// A single `lint = "level"` entry of the configuration file.
+#[derive(Debug)]
+pub struct ConfigLint {
+    /// The lint name as written in the file, possibly prefixed with a tool name.
+    pub name: String,
+    pub level: Level,
+    /// The span of the whole entry within the configuration file.
+    pub span: Span,
+    /// The lints the name resolves to, empty if it names no lint that rustc knows about.
+    pub ids: Vec<LintId>,
+    /// The problem with the lint name, if any, to be reported by `report_lint_names`.
+    problem: Option<LintNameProblem>,
+}
+
+#[derive(Debug)]
+enum LintNameProblem {
+    /// A tool lint that is known under the given new name.
+    Deprecated(String),
+    UnknownTool(String),
+    /// A renamed or removed lint, with the warning message and the new name, if any.
+    Renamed(String, Option<String>),
+    /// An unknown lint, with a suggestion for the lint that was meant.
+    Unknown(Option<Symbol>),
+}
+
+impl ConfigLint {
+    fn new(
+        store: &LintStore,
+        sess: &Session,
+        crate_attrs: &[ast::Attribute],
+        name: String,
+        level: Level,
+        span: Span,
+    ) -> ConfigLint {
+        let (tool_name, lint_name) = parse_lint_and_tool_name(&name);
+        let (ids, problem) = match store.check_lint_name(sess, lint_name, tool_name, crate_attrs) {
+            CheckLintNameResult::Ok(ids) => (ids.to_vec(), None),
+            CheckLintNameResult::Tool(Ok(ids)) => (ids.to_vec(), None),
+            CheckLintNameResult::Tool(Err((Some(ids), new_name))) => {
+                (ids.to_vec(), Some(LintNameProblem::Deprecated(new_name)))
+            }
+            // The tool does not know this lint, or this is not a build with that tool. Either
+            // way, reporting it is the responsibility of the tool.
+            CheckLintNameResult::Tool(Err((None, _))) => (Vec::new(), None),
+            CheckLintNameResult::NoTool => {
+                let tool_name = tool_name.unwrap().to_string();
+                (Vec::new(), Some(LintNameProblem::UnknownTool(tool_name)))
+            }
+            CheckLintNameResult::Warning(msg, new_name) => {
+                // Apply the new lint instead of ignoring the entry, as we do for attributes.
+                let ids = match &new_name {
+                    Some(new_name) => {
+                        match store.check_lint_name(sess, new_name, None, crate_attrs) {
+                            CheckLintNameResult::Ok(ids) => ids.to_vec(),
+                            _ => Vec::new(),
+                        }
+                    }
+                    None => Vec::new(),
+                };
+                (ids, Some(LintNameProblem::Renamed(msg, new_name)))
+            }
+            CheckLintNameResult::NoLint(suggestion) => {
+                (Vec::new(), Some(LintNameProblem::Unknown(suggestion)))
+            }
+        };
+        ConfigLint { name, level, span, ids, problem }
+    }
+
+    fn report(&self, sess: &Session) {
+        match &self.problem {
+            None => {}
+            Some(LintNameProblem::Deprecated(new_name)) => {
+                sess.struct_span_warn(
+                    self.span,
+                    &format!(
+                        "lint name `{}` is deprecated and may not have an effect in the future",
+                        self.name
+                    ),
+                )
+                .note(&format!("use `{}` instead", new_name))
+                .emit();
+            }
+            Some(LintNameProblem::UnknownTool(tool_name)) => {
+                sess.struct_span_err(
+                    self.span,
+                    &format!("unknown tool name `{}` found in scoped lint", tool_name),
+                )
+                .emit();
+            }
+            Some(LintNameProblem::Renamed(msg, new_name)) => {
+                let mut err = sess.struct_span_warn(self.span, msg);
+                if let Some(new_name) = new_name {
+                    err.note(&format!("use `{}` instead", new_name));
+                }
+                err.emit();
+            }
+            Some(LintNameProblem::Unknown(suggestion)) => {
+                let mut err =
+                    sess.struct_span_warn(self.span, &format!("unknown lint: `{}`", self.name));
+                if let Some(suggestion) = suggestion {
+                    err.help(&format!("did you mean: `{}`", suggestion));
+                }
+                err.emit();
+            }
+        }
+        for id in &self.ids {
+            if let Some(feature) = id.lint.feature_gate {
+                if !sess.features_untracked().enabled(feature) {
+                    feature_err(
+                        &sess.parse_sess,
+                        feature,
+                        self.span,
+                        &format!("the `{}` lint is unstable", id.lint.name_lower()),
+                    )
+                    .emit();
+                }
+            }
+        }
+    }
+}
+
+/// An `[[overrides]]` table of the configuration file.
+#[derive(Debug)]
+pub struct PathOverride {
+    /// Absolute paths; a source file matches if it is below any of these.
+    paths: Vec<PathBuf>,
+    pub lints: Vec<ConfigLint>,
+}
+
+impl PathOverride {
+    fn matches(&self, path: &Path) -> bool {
+        self.paths.iter().any(|prefix| path.starts_with(prefix))
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct LintConfig {
+    pub lints: Vec<ConfigLint>,
+    pub overrides: Vec<PathOverride>,
+}
+
+impl LintConfig {
+    /// Returns the overrides that apply to items defined in `file`.
+    pub fn overrides_for<'a>(
+        &'a self,
+        sess: &Session,
+        file: &SourceFile,
+    ) -> impl Iterator<Item = &'a PathOverride> + 'a {
+        let path = match &file.name {
+            FileName::Real(name) => name.local_path().map(|path| absolute_path(sess, path)),
+            _ => None,
+        };
+        self.overrides.iter().filter(move |o| path.as_ref().map_or(false, |path| o.matches(path)))
+    }
+
+    /// Reports unknown, renamed and unstable lints named by the file. This is done once, by the
+    /// early lint pass, which also reports them for lint attributes.
+    pub fn report_lint_names(&self, sess: &Session) {
+        let override_lints = self.overrides.iter().flat_map(|o| &o.lints);
+        for lint in self.lints.iter().chain(override_lints) {
+            lint.report(sess);
+        }
+    }
+}
+
+fn absolute_path(sess: &Session, path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        sess.opts.working_dir.local_path_if_available().join(path)
+    }
+}
+
+/// Reads the file given with `-Z lint-config`, if any, and resolves the lint names in it. Problems
+/// with the file are reported as errors, in which case no lint levels are taken from it at all.
+pub fn load_lint_config(
+    sess: &Session,
+    store: &LintStore,
+    crate_attrs: &[ast::Attribute],
+) -> Option<Lrc<LintConfig>> {
+    let path = sess.opts.debugging_opts.lint_config.as_ref()?;
+    let file = match sess.source_map().load_file(path) {
+        Ok(file) => file,
+        Err(e) => {
+            sess.err(&format!(
+                "failed to read lint configuration file `{}`: {}",
+                path.display(),
+                e
+            ));
+            return None;
+        }
+    };
+    let src = file.src.as_ref().expect("source of a freshly loaded file is available");
+    let span = |lo: usize, hi: usize| {
+        Span::with_root_ctxt(
+            file.start_pos + BytePos(lo as u32),
+            file.start_pos + BytePos(hi as u32),
+        )
+    };
+
+    let raw: RawLintConfig = match toml::from_str(src) {
+        Ok(raw) => raw,
+        Err(e) => {
+            let mut err = sess.struct_err(&format!("invalid lint configuration file: {}", e));
+            if let Some((line, col)) = e.line_col() {
+                if let Some(&line_start) = file.lines.get(line) {
+                    let pos = line_start + BytePos(col as u32);
+                    err.set_span(Span::with_root_ctxt(pos, pos));
+                }
+            }
+            err.emit();
+            return None;
+        }
+    };
+
+    let mut has_errors = false;
+    let mut lower_table = |table: RawLintTable| {
+        let mut lints: Vec<_> = table
+            .into_iter()
+            .filter_map(|(name, level)| {
+                let entry_span = span(name.start(), level.end());
+                match Level::from_str(level.get_ref()) {
+                    Some(lvl) => Some(ConfigLint::new(
+                        store,
+                        sess,
+                        crate_attrs,
+                        name.into_inner(),
+                        lvl,
+                        entry_span,
+                    )),
+                    None => {
+                        sess.struct_span_err(
+                            span(level.start(), level.end()),
+                            &format!("invalid lint level `{}`", level.get_ref()),
+                        )
+                        .note("expected one of `allow`, `warn`, `deny` or `forbid`")
+                        .emit();
+                        has_errors = true;
+                        None
+                    }
+                }
+            })
+            .collect();
+        // TOML tables are unordered, but lint levels are applied in the order they are written.
+        lints.sort_by_key(|lint| lint.span.lo());
+        lints
+    };
+
+    let lints = lower_table(raw.lints);
+    let config_dir = absolute_path(sess, path.parent().unwrap_or_else(|| Path::new("")));
+    let overrides = raw
+        .overrides
+        .into_iter()
+        .map(|o| PathOverride {
+            paths: o.paths.iter().map(|p| config_dir.join(p)).collect(),
+            lints: lower_table(o.lints),
+        })
+        .collect();
+
+    if has_errors { None } else { Some(Lrc::new(LintConfig { lints, overrides })) }
+}
@@ -16,11 +16,12 @@
 
 use self::TargetLint::*;
 
+use crate::config::{load_lint_config, LintConfig};
 use crate::levels::{is_known_lint_tool, LintLevelsBuilder};
 use crate::passes::{EarlyLintPassObject, LateLintPassObject};
 use rustc_ast as ast;
 use rustc_data_structures::fx::FxHashMap;
-use rustc_data_structures::sync;
+use rustc_data_structures::sync::{self, Lrc};
 use rustc_errors::{
     add_elided_lifetime_in_path_suggestion, struct_span_err, Applicability, SuggestionStyle,
 };
@@ -72,6 +73,9 @@ pub struct LintStore {
 
     /// Map of registered lint groups to what lints they expand to.
     lint_groups: FxHashMap<&'static str, LintGroup>,
+
+    /// The contents of the `-Z lint-config` file, if one was given.
+    pub(crate) lint_config: Option<Lrc<LintConfig>>,
 }
 
 /// The target of the `by_name` map, which accounts for renaming/deprecation.
@@ -137,9 +141,16 @@ pub fn new() -> LintStore {
             late_module_passes: vec![],
             by_name: Default::default(),
             lint_groups: Default::default(),
+            lint_config: None,
         }
     }
 
+    /// Reads the `-Z lint-config` file, if any. This has to be called once all lints and tools
+    /// are registered, as the lint names in the file are resolved right away.
+    pub fn load_lint_config(&mut self, sess: &Session, crate_attrs: &[ast::Attribute]) {
+        self.lint_config = load_lint_config(sess, self, crate_attrs);
+    }
+
     pub fn get_lints<'t>(&'t self) -> &'t [&'static Lint] {
         &self.lints
     }
@@ -65,6 +65,18 @@ fn with_lint_attrs<F>(&mut self, id: ast::NodeId, attrs: &'a [ast::Attribute], f
         self.context.builder.pop(push);
     }
 
+    /// Merge the lint levels of any `-Z lint-config` path overrides for the
+    /// source file containing `span`, call the provided function, then reset
+    /// the lints in effect to their previous state.
+    fn with_path_overrides<F>(&mut self, span: Span, f: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let push = self.context.builder.push_path_overrides(span);
+        f(self);
+        self.context.builder.pop(push);
+    }
+
     fn enter_attrs(&mut self, attrs: &'a [ast::Attribute]) {
         debug!("early context: enter_attrs({:?})", attrs);
         run_early_pass!(self, enter_lint_attrs, attrs);
@@ -85,18 +97,22 @@ fn visit_param(&mut self, param: &'a ast::Param) {
     }
 
     fn visit_item(&mut self, it: &'a ast::Item) {
-        self.with_lint_attrs(it.id, &it.attrs, |cx| {
-            run_early_pass!(cx, check_item, it);
-            ast_visit::walk_item(cx, it);
-            run_early_pass!(cx, check_item_post, it);
+        self.with_path_overrides(it.span, |cx| {
+            cx.with_lint_attrs(it.id, &it.attrs, |cx| {
+                run_early_pass!(cx, check_item, it);
+                ast_visit::walk_item(cx, it);
+                run_early_pass!(cx, check_item_post, it);
+            })
         })
     }
 
     fn visit_foreign_item(&mut self, it: &'a ast::ForeignItem) {
-        self.with_lint_attrs(it.id, &it.attrs, |cx| {
-            run_early_pass!(cx, check_foreign_item, it);
-            ast_visit::walk_foreign_item(cx, it);
-            run_early_pass!(cx, check_foreign_item_post, it);
+        self.with_path_overrides(it.span, |cx| {
+            cx.with_lint_attrs(it.id, &it.attrs, |cx| {
+                run_early_pass!(cx, check_foreign_item, it);
+                ast_visit::walk_foreign_item(cx, it);
+                run_early_pass!(cx, check_foreign_item_post, it);
+            })
         })
     }
 
@@ -254,17 +270,19 @@ fn visit_poly_trait_ref(&mut self, t: &'a ast::PolyTraitRef, m: &'a ast::TraitBo
     }
 
     fn visit_assoc_item(&mut self, item: &'a ast::AssocItem, ctxt: ast_visit::AssocCtxt) {
-        self.with_lint_attrs(item.id, &item.attrs, |cx| match ctxt {
-            ast_visit::AssocCtxt::Trait => {
-                run_early_pass!(cx, check_trait_item, item);
-                ast_visit::walk_assoc_item(cx, item, ctxt);
-                run_early_pass!(cx, check_trait_item_post, item);
-            }
-            ast_visit::AssocCtxt::Impl => {
-                run_early_pass!(cx, check_impl_item, item);
-                ast_visit::walk_assoc_item(cx, item, ctxt);
-                run_early_pass!(cx, check_impl_item_post, item);
-            }
+        self.with_path_overrides(item.span, |cx| {
+            cx.with_lint_attrs(item.id, &item.attrs, |cx| match ctxt {
+                ast_visit::AssocCtxt::Trait => {
+                    run_early_pass!(cx, check_trait_item, item);
+                    ast_visit::walk_assoc_item(cx, item, ctxt);
+                    run_early_pass!(cx, check_trait_item_post, item);
+                }
+                ast_visit::AssocCtxt::Impl => {
+                    run_early_pass!(cx, check_impl_item, item);
+                    ast_visit::walk_assoc_item(cx, item, ctxt);
+                    run_early_pass!(cx, check_impl_item_post, item);
+                }
+            })
         });
     }
 
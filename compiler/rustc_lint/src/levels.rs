@@ -1,8 +1,10 @@
+use crate::config::LintConfig;
 use crate::context::{CheckLintNameResult, LintStore};
 use crate::late::unerased_lint_store;
 use rustc_ast as ast;
 use rustc_ast_pretty::pprust;
 use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::sync::Lrc;
 use rustc_errors::{struct_span_err, Applicability, DiagnosticBuilder};
 use rustc_hir as hir;
 use rustc_hir::{intravisit, HirId, CRATE_HIR_ID};
@@ -22,7 +24,7 @@
 use rustc_session::parse::feature_err;
 use rustc_session::Session;
 use rustc_span::symbol::{sym, Symbol};
-use rustc_span::{source_map::MultiSpan, Span, DUMMY_SP};
+use rustc_span::{source_map::MultiSpan, BytePos, Span, DUMMY_SP};
 use tracing::debug;
 
 fn lint_levels(tcx: TyCtxt<'_>, (): ()) -> LintLevelMap {
@@ -50,10 +52,15 @@ pub struct LintLevelsBuilder<'s> {
     warn_about_weird_lints: bool,
     store: &'s LintStore,
     crate_attrs: &'s [ast::Attribute],
+    /// The contents of the `-Z lint-config` file, if one was given.
+    config: Option<Lrc<LintConfig>>,
+    /// The start of the source file whose path overrides are currently applied, if any.
+    cur_file: Option<BytePos>,
 }
 
 pub struct BuilderPush {
     prev: LintStackIndex,
+    prev_file: Option<BytePos>,
     pub changed: bool,
 }
 
@@ -72,7 +79,14 @@ pub fn new(
             warn_about_weird_lints,
             store,
             crate_attrs,
+            config: store.lint_config.clone(),
+            cur_file: None,
         };
+        if warn_about_weird_lints {
+            if let Some(config) = &builder.config {
+                config.report_lint_names(sess);
+            }
+        }
         builder.process_command_line(sess, store);
         assert_eq!(builder.sets.list.len(), 1);
         builder
@@ -103,6 +117,21 @@ fn process_command_line(&mut self, sess: &Session, store: &LintStore) {
             }
         }
 
+        // Lints from the configuration file have a lower precedence than those given on the
+        // command line, which are allowed to override them.
+        if let Some(config) = self.config.clone() {
+            let mut config_specs = FxHashMap::default();
+            for lint in &config.lints {
+                let src = LintLevelSource::ConfigFile(Symbol::intern(&lint.name), lint.span);
+                for &id in &lint.ids {
+                    config_specs.insert(id, (lint.level, src));
+                }
+            }
+            for (id, level_src) in config_specs {
+                specs.entry(id).or_insert(level_src);
+            }
+        }
+
         self.cur = self.sets.list.push(LintSet { specs, parent: COMMAND_LINE });
     }
 
@@ -136,6 +165,7 @@ fn insert_spec(
                     LintLevelSource::Default => false,
                     LintLevelSource::Node(symbol, _, _) => self.store.is_lint_group(symbol),
                     LintLevelSource::CommandLine(symbol, _) => self.store.is_lint_group(symbol),
+                    LintLevelSource::ConfigFile(symbol, _) => self.store.is_lint_group(symbol),
                 };
                 debug!(
                     "fcw_warning={:?}, specs.get(&id) = {:?}, old_src={:?}, id_name={:?}",
@@ -160,6 +190,9 @@ fn insert_spec(
                         LintLevelSource::CommandLine(_, _) => {
                             diag_builder.note("`forbid` lint level was set on command line");
                         }
+                        LintLevelSource::ConfigFile(_, forbid_source_span) => {
+                            diag_builder.span_label(forbid_source_span, "`forbid` level set here");
+                        }
                     }
                     diag_builder.emit();
                 };
@@ -514,7 +547,44 @@ pub(crate) fn push(
             self.cur = self.sets.list.push(LintSet { specs, parent: prev });
         }
 
-        BuilderPush { prev, changed: prev != self.cur }
+        BuilderPush { prev, prev_file: self.cur_file, changed: prev != self.cur }
+    }
+
+    /// Pushes the lint levels of the `-Z lint-config` path overrides that match the source
+    /// file `span` is in. This should be called for items before pushing their attributes,
+    /// so that the attributes take precedence.
+    ///
+    /// Don't forget to call `pop`!
+    pub(crate) fn push_path_overrides(&mut self, span: Span) -> BuilderPush {
+        let prev = self.cur;
+        let prev_file = self.cur_file;
+        let config = match &self.config {
+            Some(config) if !config.overrides.is_empty() && !span.is_dummy() => config.clone(),
+            _ => return BuilderPush { prev, prev_file, changed: false },
+        };
+
+        let file = self.sess.source_map().lookup_source_file(span.lo());
+        // Nested items usually live in the same file as their parent, whose overrides
+        // are already in effect.
+        if self.cur_file == Some(file.start_pos) {
+            return BuilderPush { prev, prev_file, changed: false };
+        }
+        self.cur_file = Some(file.start_pos);
+
+        let mut specs = FxHashMap::default();
+        for path_override in config.overrides_for(self.sess, &file) {
+            for lint in &path_override.lints {
+                let src = LintLevelSource::ConfigFile(Symbol::intern(&lint.name), lint.span);
+                for &id in &lint.ids {
+                    self.insert_spec(&mut specs, id, (lint.level, src));
+                }
+            }
+        }
+        if !specs.is_empty() {
+            self.cur = self.sets.list.push(LintSet { specs, parent: prev });
+        }
+
+        BuilderPush { prev, prev_file, changed: prev != self.cur }
     }
 
     /// Checks if the lint is gated on a feature that is not enabled.
@@ -535,6 +605,7 @@ fn check_gated_lint(&self, lint_id: LintId, span: Span) {
     /// Called after `push` when the scope of a set of attributes are exited.
     pub fn pop(&mut self, push: BuilderPush) {
         self.cur = push.prev;
+        self.cur_file = push.prev_file;
     }
 
     /// Find the lint level for a lint.
@@ -599,6 +670,18 @@ fn with_lint_attrs<F>(&mut self, id: hir::HirId, f: F)
         f(self);
         self.levels.pop(push);
     }
+
+    fn with_path_overrides<F>(&mut self, id: hir::HirId, span: Span, f: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let push = self.levels.push_path_overrides(span);
+        if push.changed {
+            self.levels.register_id(id);
+        }
+        f(self);
+        self.levels.pop(push);
+    }
 }
 
 impl<'tcx> intravisit::Visitor<'tcx> for LintLevelMapBuilder<'_, 'tcx> {
@@ -615,14 +698,18 @@ fn visit_param(&mut self, param: &'tcx hir::Param<'tcx>) {
     }
 
     fn visit_item(&mut self, it: &'tcx hir::Item<'tcx>) {
-        self.with_lint_attrs(it.hir_id(), |builder| {
-            intravisit::walk_item(builder, it);
+        self.with_path_overrides(it.hir_id(), it.span, |builder| {
+            builder.with_lint_attrs(it.hir_id(), |builder| {
+                intravisit::walk_item(builder, it);
+            });
         });
     }
 
     fn visit_foreign_item(&mut self, it: &'tcx hir::ForeignItem<'tcx>) {
-        self.with_lint_attrs(it.hir_id(), |builder| {
-            intravisit::walk_foreign_item(builder, it);
+        self.with_path_overrides(it.hir_id(), it.span, |builder| {
+            builder.with_lint_attrs(it.hir_id(), |builder| {
+                intravisit::walk_foreign_item(builder, it);
+            })
         })
     }
 
@@ -669,14 +756,18 @@ fn visit_arm(&mut self, a: &'tcx hir::Arm<'tcx>) {
     }
 
     fn visit_trait_item(&mut self, trait_item: &'tcx hir::TraitItem<'tcx>) {
-        self.with_lint_attrs(trait_item.hir_id(), |builder| {
-            intravisit::walk_trait_item(builder, trait_item);
+        self.with_path_overrides(trait_item.hir_id(), trait_item.span, |builder| {
+            builder.with_lint_attrs(trait_item.hir_id(), |builder| {
+                intravisit::walk_trait_item(builder, trait_item);
+            });
         });
     }
 
     fn visit_impl_item(&mut self, impl_item: &'tcx hir::ImplItem<'tcx>) {
-        self.with_lint_attrs(impl_item.hir_id(), |builder| {
-            intravisit::walk_impl_item(builder, impl_item);
+        self.with_path_overrides(impl_item.hir_id(), impl_item.span, |builder| {
+            builder.with_lint_attrs(impl_item.hir_id(), |builder| {
+                intravisit::walk_impl_item(builder, impl_item);
+            });
         });
     }
 }
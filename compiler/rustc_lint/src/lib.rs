@@ -45,6 +45,7 @@
 
 mod array_into_iter;
 pub mod builtin;
+mod config;
 mod context;
 mod early;
 mod enum_intrinsics_non_enums;
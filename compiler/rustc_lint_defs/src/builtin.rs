@@ -437,6 +437,46 @@
     crate_level_only
 }
 
+declare_lint! {
+    /// The `unused_pub_items` lint detects public items of upstream crates
+    /// that no crate in the crate graph uses. It is only checked with
+    /// `-Z unused-pub-items`.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore (needs extern crate)
+    /// // compiled with `-Z unused-pub-items`, like its dependency `parser`
+    /// fn main() {}
+    /// ```
+    ///
+    /// This will produce:
+    ///
+    /// ```text
+    /// warning: public function `parser::parse_legacy` is never used outside of `parser`
+    ///  --> parser/src/lib.rs:3:1
+    ///   |
+    /// 3 | pub fn parse_legacy() {}
+    ///   | ^^^^^^^^^^^^^^^^^^^^^
+    ///   |
+    ///   = note: `#[warn(unused_pub_items)]` on by default
+    /// ```
+    ///
+    /// ### Explanation
+    ///
+    /// The `dead_code` lint only looks at a single crate, so it has to treat
+    /// every public item as used. When the whole crate graph is known, as is
+    /// the case when compiling an executable, a public item that no crate
+    /// uses is likely to be dead code as well.
+    ///
+    /// The lint is reported for the crate that is being linked, so its level
+    /// is controlled by the attributes at the root of that crate, or on the
+    /// command line.
+    pub UNUSED_PUB_ITEMS,
+    Warn,
+    "public items of upstream crates that no crate in the crate graph uses",
+    crate_level_only
+}
+
 declare_lint! {
     /// The `unused_qualifications` lint detects unnecessarily qualified
     /// names.
@@ -2969,6 +3009,7 @@
         UNUSED_IMPORTS,
         UNUSED_EXTERN_CRATES,
         UNUSED_CRATE_DEPENDENCIES,
+        UNUSED_PUB_ITEMS,
         UNUSED_QUALIFICATIONS,
         UNKNOWN_LINTS,
         UNUSED_VARIABLES,
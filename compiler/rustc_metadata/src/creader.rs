@@ -178,7 +178,11 @@ fn push_dependencies_in_postorder(&self, deps: &mut Vec<CrateNum>, cnum: CrateNu
     crate fn crate_dependencies_in_postorder(&self, cnum: CrateNum) -> Vec<CrateNum> {
         let mut deps = Vec::new();
         if cnum == LOCAL_CRATE {
-            self.iter_crate_data(|cnum, _| self.push_dependencies_in_postorder(&mut deps, cnum));
+            self.iter_crate_data(|cnum, data| {
+                if data.dep_kind() != CrateDepKind::DiagnosticsOnly {
+                    self.push_dependencies_in_postorder(&mut deps, cnum);
+                }
+            });
         } else {
             self.push_dependencies_in_postorder(&mut deps, cnum);
         }
@@ -557,7 +561,9 @@ fn maybe_resolve_crate<'b>(
             match self.load(&mut locator)? {
                 Some(res) => (res, None),
                 None => {
-                    dep_kind = CrateDepKind::MacrosOnly;
+                    if dep_kind != CrateDepKind::DiagnosticsOnly {
+                        dep_kind = CrateDepKind::MacrosOnly;
+                    }
                     match self.load_proc_macro(&mut locator, path_kind, host_hash)? {
                         Some(res) => res,
                         None => return Err(locator.into_error(root.cloned())),
@@ -569,7 +575,7 @@ fn maybe_resolve_crate<'b>(
         match result {
             (LoadResult::Previous(cnum), None) => {
                 let data = self.cstore.get_crate_data(cnum);
-                if data.is_proc_macro_crate() {
+                if data.is_proc_macro_crate() && dep_kind != CrateDepKind::DiagnosticsOnly {
                     dep_kind = CrateDepKind::MacrosOnly;
                 }
                 data.update_dep_kind(|data_dep_kind| cmp::max(data_dep_kind, dep_kind));
@@ -653,6 +659,7 @@ fn resolve_crate_deps(
                 dep.name, dep.hash, dep.extra_filename
             );
             let dep_kind = match dep_kind {
+                CrateDepKind::DiagnosticsOnly => CrateDepKind::DiagnosticsOnly,
                 CrateDepKind::MacrosOnly => CrateDepKind::MacrosOnly,
                 _ => dep.kind,
             };
@@ -1048,6 +1055,13 @@ pub fn process_path_extern(&mut self, name: Symbol, span: Span) -> CrateNum {
     pub fn maybe_process_path_extern(&mut self, name: Symbol) -> Option<CrateNum> {
         self.maybe_resolve_crate(name, CrateDepKind::Explicit, None).ok()
     }
+
+    /// Loads a crate only so that its items can be suggested in diagnostics, without making it a
+    /// dependency of the current crate. Must be called once all the actual dependencies have
+    /// been loaded, so that the crates loaded here are not mistaken for one of them.
+    pub fn load_crate_for_diagnostics(&mut self, name: Symbol) -> Option<CrateNum> {
+        self.maybe_resolve_crate(name, CrateDepKind::DiagnosticsOnly, None).ok()
+    }
 }
 
 fn global_allocator_spans(sess: &Session, krate: &ast::Crate) -> Vec<Span> {
@@ -1508,6 +1508,12 @@ fn get_missing_lang_items(&self, tcx: TyCtxt<'tcx>) -> &'tcx [lang_items::LangIt
         }
     }
 
+    fn get_used_upstream_items(&self, tcx: TyCtxt<'tcx>) -> Option<&'tcx [DefId]> {
+        self.root
+            .used_upstream_items
+            .map(|items| &*tcx.arena.alloc_from_iter(items.decode((self, tcx))))
+    }
+
     fn get_fn_param_names(&self, tcx: TyCtxt<'tcx>, id: DefIndex) -> &'tcx [Ident] {
         let param_names = match self.kind(id) {
             EntryKind::Fn(data) | EntryKind::ForeignFn(data) => data.decode(self).param_names,
@@ -1530,6 +1536,14 @@ fn exported_symbols(
         }
     }
 
+    fn exported_monomorphizations(&self, tcx: TyCtxt<'tcx>) -> &'tcx [ty::Instance<'tcx>] {
+        if self.root.is_proc_macro_crate() {
+            &[]
+        } else {
+            tcx.arena.alloc_from_iter(self.root.exported_monomorphizations.decode((self, tcx)))
+        }
+    }
+
     fn get_rendered_const(&self, id: DefIndex) -> String {
         match self.kind(id) {
             EntryKind::AnonConst(_, data)
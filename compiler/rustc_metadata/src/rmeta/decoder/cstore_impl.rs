@@ -3,6 +3,7 @@
 use crate::native_libs;
 
 use rustc_ast as ast;
+use rustc_attr as attr;
 use rustc_data_structures::stable_map::FxHashMap;
 use rustc_hir::def::{CtorKind, DefKind};
 use rustc_hir::def_id::{CrateNum, DefId, DefIdMap, CRATE_DEF_INDEX, LOCAL_CRATE};
@@ -12,7 +13,7 @@
 use rustc_middle::middle::stability::DeprecationEntry;
 use rustc_middle::ty::query::Providers;
 use rustc_middle::ty::{self, TyCtxt, Visibility};
-use rustc_session::cstore::{CrateSource, CrateStore, ForeignModule};
+use rustc_session::cstore::{CrateDepKind, CrateSource, CrateStore, ForeignModule};
 use rustc_session::utils::NativeLibKind;
 use rustc_session::{Session, StableCrateId};
 use rustc_span::hygiene::{ExpnHash, ExpnId};
@@ -211,6 +212,7 @@ fn into_args(self) -> (DefId, DefId) {
     defined_lang_items => { cdata.get_lang_items(tcx) }
     diagnostic_items => { cdata.get_diagnostic_items() }
     missing_lang_items => { cdata.get_missing_lang_items(tcx) }
+    used_upstream_items => { cdata.get_used_upstream_items(tcx) }
 
     missing_extern_crate_item => {
         let r = matches!(*cdata.extern_crate.borrow(), Some(extern_crate) if !extern_crate.is_direct());
@@ -229,6 +231,8 @@ fn into_args(self) -> (DefId, DefId) {
         syms
     }
 
+    exported_monomorphizations => { cdata.exported_monomorphizations(tcx) }
+
     crate_extern_paths => { cdata.source().paths().cloned().collect() }
     expn_that_defined => { cdata.get_expn_that_defined(def_id.index, tcx.sess) }
 }
@@ -352,6 +356,9 @@ pub fn provide(providers: &mut Providers) {
                 .alloc_slice(&CStore::from_tcx(tcx).crate_dependencies_in_postorder(LOCAL_CRATE))
         },
         crates: |tcx, ()| tcx.arena.alloc_slice(&CStore::from_tcx(tcx).crates_untracked()),
+        crates_for_diagnostics: |tcx, ()| {
+            tcx.arena.alloc_slice(&CStore::from_tcx(tcx).crates_for_diagnostics_untracked())
+        },
 
         ..*providers
     };
@@ -376,6 +383,10 @@ pub fn visibility_untracked(&self, def: DefId) -> Visibility {
         self.get_crate_data(def.krate).get_visibility(def.index)
     }
 
+    pub fn stability_untracked(&self, def: DefId) -> Option<attr::Stability> {
+        self.get_crate_data(def.krate).get_stability(def.index)
+    }
+
     pub fn item_children_untracked(&self, def_id: DefId, sess: &Session) -> Vec<Export> {
         let mut result = vec![];
         self.get_crate_data(def_id.krate).each_child_of_item(
@@ -435,6 +446,18 @@ pub fn def_kind(&self, def: DefId) -> DefKind {
     }
 
     pub fn crates_untracked(&self) -> Vec<CrateNum> {
+        let mut result = vec![];
+        self.iter_crate_data(|cnum, data| {
+            if data.dep_kind() != CrateDepKind::DiagnosticsOnly {
+                result.push(cnum);
+            }
+        });
+        result
+    }
+
+    /// Like `crates_untracked`, but also returns the crates that were only loaded for
+    /// diagnostics.
+    pub fn crates_for_diagnostics_untracked(&self) -> Vec<CrateNum> {
         let mut result = vec![];
         self.iter_crate_data(|cnum, _| result.push(cnum));
         result
@@ -668,8 +668,11 @@ fn encode_crate_root(&mut self) -> Lazy<CrateRoot<'tcx>> {
         i = self.position();
         let exported_symbols = tcx.exported_symbols(LOCAL_CRATE);
         let exported_symbols = self.encode_exported_symbols(&exported_symbols);
+        let exported_monomorphizations = self.encode_exported_monomorphizations();
         let exported_symbols_bytes = self.position() - i;
 
+        let used_upstream_items = self.encode_used_upstream_items();
+
         // Encode the hygiene data,
         // IMPORTANT: this *must* be the last thing that we encode (other than `SourceMap`). The process
         // of encoding other items (e.g. `optimized_mir`) may cause us to load
@@ -727,6 +730,8 @@ fn encode_crate_root(&mut self) -> Lazy<CrateRoot<'tcx>> {
             source_map,
             impls,
             exported_symbols,
+            exported_monomorphizations,
+            used_upstream_items,
             interpret_alloc_index,
             tables,
             syntax_contexts,
@@ -1775,6 +1780,14 @@ fn encode_lang_items_missing(&mut self) -> Lazy<[lang_items::LangItem]> {
         self.lazy(&tcx.lang_items().missing)
     }
 
+    fn encode_used_upstream_items(&mut self) -> Option<Lazy<[DefId]>> {
+        if self.is_proc_macro {
+            return None;
+        }
+        let tcx = self.tcx;
+        tcx.used_upstream_items(LOCAL_CRATE).map(|items| self.lazy(items))
+    }
+
     /// Encodes an index, mapping each trait to its (local) implementations.
     fn encode_impls(&mut self) -> Lazy<[TraitImpls]> {
         empty_proc_macro!(self);
@@ -1832,6 +1845,12 @@ fn encode_exported_symbols(
         )
     }
 
+    fn encode_exported_monomorphizations(&mut self) -> Lazy<[ty::Instance<'tcx>]> {
+        empty_proc_macro!(self);
+        let exported_monomorphizations = self.tcx.exported_monomorphizations(LOCAL_CRATE);
+        self.lazy(exported_monomorphizations.iter())
+    }
+
     fn encode_dylib_dependency_formats(&mut self) -> Lazy<[Option<LinkagePreference>]> {
         empty_proc_macro!(self);
         let formats = self.tcx.dependency_formats(());
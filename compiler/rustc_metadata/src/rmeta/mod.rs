@@ -229,6 +229,10 @@ macro_rules! Lazy {
     tables: LazyTables<'tcx>,
 
     exported_symbols: Lazy!([(ExportedSymbol<'tcx>, SymbolExportLevel)]),
+    /// The monomorphizations downstream crates can link to with `-Z share-generics`.
+    exported_monomorphizations: Lazy!([ty::Instance<'tcx>]),
+    /// The items of other crates used by this crate, with `-Z unused-pub-items`.
+    used_upstream_items: Option<Lazy<[DefId]>>,
 
     syntax_contexts: SyntaxContextTable,
     expn_data: ExpnDataTable,
@@ -29,6 +29,10 @@ pub enum LintLevelSource {
     /// The provided `Level` is the level specified on the command line.
     /// (The actual level may be lower due to `--cap-lints`.)
     CommandLine(Symbol, Level),
+
+    /// Lint level was set by the lint configuration file given with `-Z lint-config`.
+    /// The `Span` points at the `lint = "level"` entry within that file.
+    ConfigFile(Symbol, Span),
 }
 
 impl LintLevelSource {
@@ -37,6 +41,7 @@ pub fn name(&self) -> Symbol {
             LintLevelSource::Default => symbol::kw::Default,
             LintLevelSource::Node(name, _, _) => name,
             LintLevelSource::CommandLine(name, _) => name,
+            LintLevelSource::ConfigFile(name, _) => name,
         }
     }
 
@@ -45,6 +50,7 @@ pub fn span(&self) -> Span {
             LintLevelSource::Default => DUMMY_SP,
             LintLevelSource::Node(_, span, _) => span,
             LintLevelSource::CommandLine(_, _) => DUMMY_SP,
+            LintLevelSource::ConfigFile(_, span) => span,
         }
     }
 }
@@ -335,6 +341,21 @@ fn struct_lint_level_impl(
                     );
                 }
             }
+            LintLevelSource::ConfigFile(lint_config_name, src) => {
+                sess.diag_span_note_once(
+                    &mut err,
+                    DiagnosticMessageId::from(lint),
+                    src,
+                    "the lint level is defined in the lint configuration file here",
+                );
+                if lint_config_name.as_str() != name {
+                    sess.diag_note_once(
+                        &mut err,
+                        DiagnosticMessageId::from(lint),
+                        &format!("`{}` is part of the `{}` lint group", name, lint_config_name),
+                    );
+                }
+            }
         }
 
         let is_force_warn = matches!(level, Level::ForceWarn);
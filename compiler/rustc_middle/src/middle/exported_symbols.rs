@@ -26,6 +26,9 @@ pub enum ExportedSymbol<'tcx> {
     NonGeneric(DefId),
     Generic(DefId, SubstsRef<'tcx>),
     DropGlue(Ty<'tcx>),
+    /// A compiler-generated shim with generic arguments, like the `Clone` impl of a tuple,
+    /// that is shared with downstream crates. See `InstanceDef::is_shareable_shim`.
+    Shim(ty::Instance<'tcx>),
     NoDefId(ty::SymbolName<'tcx>),
 }
 
@@ -41,6 +44,7 @@ pub fn symbol_name_for_local_instance(&self, tcx: TyCtxt<'tcx>) -> ty::SymbolNam
             ExportedSymbol::DropGlue(ty) => {
                 tcx.symbol_name(ty::Instance::resolve_drop_in_place(tcx, ty))
             }
+            ExportedSymbol::Shim(instance) => tcx.symbol_name(instance),
             ExportedSymbol::NoDefId(symbol_name) => symbol_name,
         }
     }
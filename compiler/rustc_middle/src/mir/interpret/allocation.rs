@@ -43,6 +43,9 @@ pub struct Allocation<Tag = AllocId, Extra = ()> {
     /// Also used by codegen to determine if a static should be put into mutable memory,
     /// which happens for `static mut` and `static` with interior mutability.
     pub mutability: Mutability,
+    /// `true` if the allocation was made with `const_allocate` during const evaluation and
+    /// became part of the value of a global. This memory must never be freed at runtime.
+    pub heap: bool,
     /// Extra state for the machine.
     pub extra: Extra,
 }
@@ -120,6 +123,7 @@ pub fn from_bytes<'a>(
             init_mask: InitMask::new(size, true),
             align,
             mutability,
+            heap: false,
             extra: (),
         }
     }
@@ -153,6 +157,7 @@ pub fn uninit(size: Size, align: Align, panic_on_fail: bool) -> InterpResult<'st
             init_mask: InitMask::new(size, false),
             align,
             mutability: Mutability::Mut,
+            heap: false,
             extra: (),
         })
     }
@@ -187,6 +192,7 @@ pub fn convert_tag_add_extra<Tag, Extra>(
             init_mask: self.init_mask,
             align: self.align,
             mutability: self.mutability,
+            heap: self.heap,
             extra,
         }
     }
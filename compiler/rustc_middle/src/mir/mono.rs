@@ -266,6 +266,16 @@ pub enum Visibility {
     Protected,
 }
 
+/// A call made by the code of a function, see the `instance_call_targets` query.
+#[derive(Copy, Clone, PartialEq, Debug, HashStable)]
+pub enum CallTarget<'tcx> {
+    /// A call of a known function, including drop glue and the panics of
+    /// failed assertions.
+    Instance(Instance<'tcx>),
+    /// A call through a function pointer or a trait object.
+    Indirect,
+}
+
 impl<'tcx> CodegenUnit<'tcx> {
     #[inline]
     pub fn new(name: Symbol) -> CodegenUnit<'tcx> {
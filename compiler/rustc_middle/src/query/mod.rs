@@ -1262,6 +1262,14 @@
         }
     }
 
+    /// The monomorphizations of generic functions, drop glue and shims that
+    /// the given crate instantiated and exports so that downstream crates can
+    /// link to them with `-Z share-generics`. For upstream crates, this is
+    /// the registry recorded in their metadata.
+    query exported_monomorphizations(_: CrateNum) -> &'tcx [ty::Instance<'tcx>] {
+        desc { "collecting the exported monomorphizations of a crate" }
+    }
+
     /// The entire set of monomorphizations the local crate can safely link
     /// to because they are exported from upstream crates. Do not depend on
     /// this directly, as its value changes anytime a monomorphization gets
@@ -1307,6 +1315,24 @@
         desc { "available upstream drop-glue for `{:?}`", substs }
     }
 
+    /// The compiler-generated shims, like the `Clone` impls of tuples, that the
+    /// local crate can link to because upstream crates export them. Like
+    /// `upstream_monomorphizations`, do not depend on this directly but use
+    /// `upstream_shim_for` or `Instance::upstream_monomorphization()`.
+    query upstream_shims(_: ()) -> FxHashMap<ty::Instance<'tcx>, CrateNum> {
+        storage(ArenaCacheSelector<'tcx>)
+        no_hash
+        desc { "collecting available upstream shims" }
+    }
+
+    /// Returns the upstream crate that exports the given shim, if any.
+    ///
+    /// You likely want to call `Instance::upstream_monomorphization()`
+    /// instead of invoking this query directly.
+    query upstream_shim_for(instance: ty::Instance<'tcx>) -> Option<CrateNum> {
+        desc { "available upstream shim for `{}`", instance }
+    }
+
     query foreign_modules(_: CrateNum) -> Lrc<FxHashMap<DefId, ForeignModule>> {
         desc { "looking up the foreign modules of a linked crate" }
     }
@@ -1469,6 +1495,12 @@
     query missing_lang_items(_: CrateNum) -> &'tcx [LangItem] {
         desc { "calculating the missing lang items in a crate" }
     }
+
+    /// Returns the items of other crates that a crate uses. This is only recorded
+    /// with `-Z unused-pub-items`, and is `None` for crates compiled without it.
+    query used_upstream_items(_: CrateNum) -> Option<&'tcx [DefId]> {
+        desc { "calculating the upstream items used by a crate" }
+    }
     query visible_parent_map(_: ()) -> DefIdMap<DefId> {
         storage(ArenaCacheSelector<'tcx>)
         desc { "calculating the visible parent map" }
@@ -1524,6 +1556,13 @@
         desc { "fetching all foreign CrateNum instances" }
     }
 
+    /// Like `crates`, but also includes the crates that were only loaded so that their items
+    /// can be suggested in diagnostics. Must only be used for error reporting.
+    query crates_for_diagnostics(_: ()) -> &'tcx [CrateNum] {
+        eval_always
+        desc { "fetching all foreign CrateNum instances, including those loaded for diagnostics" }
+    }
+
     /// A vector of every trait accessible in the whole crate
     /// (i.e., including those from subcrates). This is used only for
     /// error reporting.
@@ -1558,6 +1597,13 @@
     query codegen_unit(_: Symbol) -> &'tcx CodegenUnit<'tcx> {
         desc { "codegen_unit" }
     }
+
+    /// The calls made by the code of a function, used for the call graph of
+    /// `-Z emit-stack-sizes-report`. Calls that don't end up as calls in the
+    /// generated code, like those of intrinsics, are left out.
+    query instance_call_targets(key: ty::Instance<'tcx>) -> &'tcx [mir::mono::CallTarget<'tcx>] {
+        desc { "collecting the calls made by `{}`", key }
+    }
     query unused_generic_params(key: DefId) -> FiniteBitSet<u32> {
         cache_on_disk_if { key.is_local() }
         desc {
@@ -1565,6 +1611,14 @@
                 tcx.def_path_str(key)
         }
     }
+    /// The types whose identity is observed with `TypeId::of` or `type_name` by the code of an
+    /// instance and the code it calls, as resolved in the given environment. Used by
+    /// polymorphization to keep the generic parameters of the closures whose type is observed.
+    query types_observed_by(
+        key: ty::ParamEnvAnd<'tcx, ty::Instance<'tcx>>
+    ) -> &'tcx ty::List<Ty<'tcx>> {
+        desc { "computing the types observed by `{}`", key.value }
+    }
     query backend_optimization_level(_: ()) -> OptLevel {
         desc { "optimization level used by backend" }
     }
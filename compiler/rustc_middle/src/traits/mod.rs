@@ -863,8 +863,14 @@ trait objects",
                     Applicability::MachineApplicable,
                 );
             }
+            ObjectSafetyViolation::GAT(name, _) => {
+                err.help(&format!(
+                    "consider moving `{}` to another trait, or constraining it with \
+                     `where Self: Sized` so it does not apply to trait objects",
+                    name
+                ));
+            }
             ObjectSafetyViolation::AssocConst(name, _)
-            | ObjectSafetyViolation::GAT(name, _)
             | ObjectSafetyViolation::Method(name, ..) => {
                 err.help(&format!("consider moving `{}` to another trait", name));
             }
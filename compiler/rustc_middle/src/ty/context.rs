@@ -496,6 +496,12 @@ pub struct TypeckResults<'tcx> {
     /// that are live across the yield of this generator (if a generator).
     pub generator_interior_types: ty::Binder<'tcx, Vec<GeneratorInteriorTypeCause<'tcx>>>,
 
+    /// Like `generator_interior_types`, but with every value that is live across
+    /// a yield, including the values of a type that is listed already. This is
+    /// used to explain all the values that make a generator `!Send` or `!Sync`.
+    /// It shares the bound variables of `generator_interior_types`.
+    pub generator_interior_values: ty::Binder<'tcx, Vec<GeneratorInteriorTypeCause<'tcx>>>,
+
     /// We sometimes treat byte string literals (which are of type `&[u8; N]`)
     /// as `&[u8]`, depending on the pattern  in which they are used.
     /// This hashset records all instances where we behave
@@ -530,6 +536,7 @@ pub fn new(hir_owner: LocalDefId) -> TypeckResults<'tcx> {
             closure_min_captures: Default::default(),
             closure_fake_reads: Default::default(),
             generator_interior_types: ty::Binder::dummy(Default::default()),
+            generator_interior_values: ty::Binder::dummy(Default::default()),
             treat_byte_string_as_slice: Default::default(),
             closure_size_eval: Default::default(),
         }
@@ -775,6 +782,7 @@ fn hash_stable(&self, hcx: &mut StableHashingContext<'a>, hasher: &mut StableHas
             ref closure_min_captures,
             ref closure_fake_reads,
             ref generator_interior_types,
+            ref generator_interior_values,
             ref treat_byte_string_as_slice,
             ref closure_size_eval,
         } = *self;
@@ -802,6 +810,7 @@ fn hash_stable(&self, hcx: &mut StableHashingContext<'a>, hasher: &mut StableHas
             closure_min_captures.hash_stable(hcx, hasher);
             closure_fake_reads.hash_stable(hcx, hasher);
             generator_interior_types.hash_stable(hcx, hasher);
+            generator_interior_values.hash_stable(hcx, hasher);
             treat_byte_string_as_slice.hash_stable(hcx, hasher);
             closure_size_eval.hash_stable(hcx, hasher);
         })
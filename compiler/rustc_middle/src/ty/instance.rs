@@ -6,9 +6,11 @@
 use rustc_hir::def::Namespace;
 use rustc_hir::def_id::{CrateNum, DefId};
 use rustc_hir::lang_items::LangItem;
+use rustc_index::bit_set::FiniteBitSet;
 use rustc_macros::HashStable;
 
 use std::fmt;
+use std::iter;
 
 /// A monomorphized `InstanceDef`.
 ///
@@ -131,6 +133,7 @@ pub fn upstream_monomorphization(&self, tcx: TyCtxt<'tcx>) -> Option<CrateNum> {
                 .upstream_monomorphizations_for(def.did)
                 .and_then(|monos| monos.get(&self.substs).cloned()),
             InstanceDef::DropGlue(_, Some(_)) => tcx.upstream_drop_glue_for(self.substs),
+            def if def.is_shareable_shim() => tcx.upstream_shim_for(*self),
             _ => None,
         }
     }
@@ -172,6 +175,20 @@ pub fn attrs(&self, tcx: TyCtxt<'tcx>) -> ty::Attributes<'tcx> {
         tcx.get_attrs(self.def_id())
     }
 
+    /// Returns `true` if this is a compiler-generated shim that is the same in every crate
+    /// that instantiates it, so that with `-Z share-generics` downstream crates can link to
+    /// the instance of an upstream crate rather than generating their own.
+    pub fn is_shareable_shim(&self) -> bool {
+        matches!(
+            *self,
+            InstanceDef::VtableShim(..)
+                | InstanceDef::ReifyShim(..)
+                | InstanceDef::FnPtrShim(..)
+                | InstanceDef::ClosureOnceShim { .. }
+                | InstanceDef::CloneShim(..)
+        )
+    }
+
     /// Returns `true` if the LLVM version of this instance is unconditionally
     /// marked with `inline`. This implies that a copy of this instance is
     /// generated in every codegen unit.
@@ -563,7 +580,7 @@ pub fn subst_mir_and_normalize_erasing_regions<T>(
     /// identity parameters if they are determined to be unused in `instance.def`.
     pub fn polymorphize(self, tcx: TyCtxt<'tcx>) -> Self {
         debug!("polymorphize: running polymorphization analysis");
-        if !tcx.sess.opts.debugging_opts.polymorphize {
+        if !tcx.sess.polymorphize() {
             return self;
         }
 
@@ -602,6 +619,25 @@ fn polymorphize<'tcx>(
 
     struct PolymorphizationFolder<'tcx> {
         tcx: TyCtxt<'tcx>,
+        /// The parameters unused by the closure or generator whose upvars are folded.
+        unused: FiniteBitSet<u32>,
+    }
+
+    impl PolymorphizationFolder<'tcx> {
+        /// Polymorphizes the substs of a captured closure or generator. Only the parameters of
+        /// the parent that the capturing closure doesn't use either are replaced: the type of
+        /// the upvar is visible to the body of the capturing closure, which could observe it
+        /// with `type_name` or `TypeId::of`.
+        fn polymorphize_upvar(&self, def_id: DefId, substs: SubstsRef<'tcx>) -> SubstsRef<'tcx> {
+            let polymorphized_substs = polymorphize(self.tcx, def_id, substs);
+            let parent_count = self.tcx.generics_of(def_id).parent_count;
+            self.tcx.mk_substs(iter::zip(substs, polymorphized_substs).enumerate().map(
+                |(index, (arg, polymorphized_arg))| {
+                    let used = !self.unused.contains(index as u32).unwrap_or(false);
+                    if index < parent_count && used { arg } else { polymorphized_arg }
+                },
+            ))
+        }
     }
 
     impl ty::TypeFolder<'tcx> for PolymorphizationFolder<'tcx> {
@@ -613,7 +649,7 @@ fn fold_ty(&mut self, ty: Ty<'tcx>) -> Ty<'tcx> {
             debug!("fold_ty: ty={:?}", ty);
             match ty.kind {
                 ty::Closure(def_id, substs) => {
-                    let polymorphized_substs = polymorphize(self.tcx, def_id, substs);
+                    let polymorphized_substs = self.polymorphize_upvar(def_id, substs);
                     if substs == polymorphized_substs {
                         ty
                     } else {
@@ -621,7 +657,7 @@ fn fold_ty(&mut self, ty: Ty<'tcx>) -> Ty<'tcx> {
                     }
                 }
                 ty::Generator(def_id, substs, movability) => {
-                    let polymorphized_substs = polymorphize(self.tcx, def_id, substs);
+                    let polymorphized_substs = self.polymorphize_upvar(def_id, substs);
                     if substs == polymorphized_substs {
                         ty
                     } else {
@@ -648,7 +684,7 @@ fn fold_ty(&mut self, ty: Ty<'tcx>) -> Ty<'tcx> {
                     // ..and polymorphize any closures/generators captured as upvars.
                     let upvars_ty = upvars_ty.unwrap();
                     let polymorphized_upvars_ty = upvars_ty.fold_with(
-                        &mut PolymorphizationFolder { tcx });
+                        &mut PolymorphizationFolder { tcx, unused });
                     debug!("polymorphize: polymorphized_upvars_ty={:?}", polymorphized_upvars_ty);
                     ty::GenericArg::from(polymorphized_upvars_ty)
                 },
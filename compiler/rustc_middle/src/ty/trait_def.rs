@@ -213,7 +213,11 @@ pub(super) fn trait_impls_of_provider(tcx: TyCtxt<'_>, trait_id: DefId) -> Trait
     // Traits defined in the current crate can't have impls in upstream
     // crates, so we don't bother querying the cstore.
     if !trait_id.is_local() {
-        for &cnum in tcx.crates(()).iter() {
+        // The crate of the trait is not among `crates` if it was only loaded for diagnostics,
+        // but its impls are still needed to tell whether the trait is worth suggesting.
+        let crates = tcx.crates(());
+        let trait_crate = Some(trait_id.krate).filter(|krate| !crates.contains(krate));
+        for &cnum in crates.iter().chain(trait_crate.iter()) {
             for &(impl_def_id, simplified_self_ty) in
                 tcx.implementations_of_trait((cnum, trait_id)).iter()
             {
@@ -9,14 +9,17 @@
 use rustc_errors::{error_code, struct_span_err, Applicability, DiagnosticBuilder};
 use rustc_hir as hir;
 use rustc_hir::def::*;
-use rustc_hir::def_id::DefId;
+use rustc_hir::def_id::{DefId, CRATE_DEF_INDEX};
 use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
 use rustc_hir::{HirId, Pat};
-use rustc_middle::ty::{self, AdtDef, Ty, TyCtxt};
+use rustc_middle::thir;
+use rustc_middle::ty::print::{with_crate_prefix, with_no_trimmed_paths};
+use rustc_middle::ty::{self, AdtDef, DefIdTree, Ty, TyCtxt};
 use rustc_session::lint::builtin::{
     BINDINGS_WITH_VARIANT_NAME, IRREFUTABLE_LET_PATTERNS, UNREACHABLE_PATTERNS,
 };
 use rustc_session::Session;
+use rustc_span::symbol::sym;
 use rustc_span::{DesugaringKind, ExpnKind, Span};
 
 crate fn check_match(tcx: TyCtxt<'_>, def_id: DefId) {
@@ -63,7 +66,9 @@ fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
     fn visit_expr(&mut self, ex: &'tcx hir::Expr<'tcx>) {
         intravisit::walk_expr(self, ex);
         match &ex.kind {
-            hir::ExprKind::Match(scrut, arms, source) => self.check_match(scrut, arms, *source),
+            hir::ExprKind::Match(scrut, arms, source) => {
+                self.check_match(scrut, arms, *source, ex.span)
+            }
             hir::ExprKind::Let(pat, scrut, span) => self.check_let(pat, scrut, *span),
             _ => {}
         }
@@ -163,6 +168,7 @@ fn check_match(
         scrut: &hir::Expr<'_>,
         arms: &'tcx [hir::Arm<'tcx>],
         source: hir::MatchSource,
+        expr_span: Span,
     ) {
         let mut cx = self.new_cx(scrut.hir_id);
 
@@ -178,6 +184,7 @@ fn check_match(
 
         let mut have_errors = false;
 
+        let arms_span = MatchArmsSpan { scrut: scrut.span, expr_span, arms };
         let arms: Vec<_> = arms
             .iter()
             .map(|hir::Arm { pat, guard, .. }| MatchArm {
@@ -208,7 +215,11 @@ fn check_match(
         let is_empty_match = arms.is_empty();
         let witnesses = report.non_exhaustiveness_witnesses;
         if !witnesses.is_empty() {
-            non_exhaustive_match(&cx, scrut_ty, scrut.span, witnesses, is_empty_match);
+            let arms_span = match source {
+                hir::MatchSource::Normal => Some(arms_span),
+                _ => None,
+            };
+            non_exhaustive_match(&cx, scrut_ty, scrut.span, witnesses, is_empty_match, arms_span);
         }
     }
 
@@ -483,6 +494,13 @@ fn report_arm_reachability<'p, 'tcx>(
     }
 }
 
+/// The parts of a `match` expression that are needed to suggest new arms for it.
+struct MatchArmsSpan<'tcx> {
+    scrut: Span,
+    expr_span: Span,
+    arms: &'tcx [hir::Arm<'tcx>],
+}
+
 /// Report that a match is not exhaustive.
 fn non_exhaustive_match<'p, 'tcx>(
     cx: &MatchCheckCtxt<'p, 'tcx>,
@@ -490,6 +508,7 @@ fn non_exhaustive_match<'p, 'tcx>(
     sp: Span,
     witnesses: Vec<DeconstructedPat<'p, 'tcx>>,
     is_empty_match: bool,
+    arms_span: Option<MatchArmsSpan<'_>>,
 ) {
     let non_empty_enum = match scrut_ty.kind() {
         ty::Adt(def, _) => def.is_enum() && !def.variants.is_empty(),
@@ -552,9 +571,214 @@ fn non_exhaustive_match<'p, 'tcx>(
             err.note("references are always considered inhabited");
         }
     }
+    if let Some(arms_span) = arms_span {
+        suggest_missing_arms(cx, &mut err, &witnesses, arms_span);
+    }
     err.emit();
 }
 
+/// Suggests adding one arm per witness to the match, so that `cargo fix` can make it exhaustive.
+fn suggest_missing_arms<'p, 'tcx>(
+    cx: &MatchCheckCtxt<'p, 'tcx>,
+    err: &mut DiagnosticBuilder<'_>,
+    witnesses: &[DeconstructedPat<'p, 'tcx>],
+    MatchArmsSpan { scrut, expr_span, arms }: MatchArmsSpan<'_>,
+) {
+    if expr_span.from_expansion() || arms.iter().any(|arm| arm.span.from_expansion()) {
+        return;
+    }
+    let patterns = match witnesses
+        .iter()
+        .map(|witness| witness_to_source(cx, &witness.to_pat(cx)))
+        .collect::<Option<Vec<_>>>()
+    {
+        Some(patterns) => patterns,
+        None => return,
+    };
+
+    let sm = cx.tcx.sess.source_map();
+    let (span, suggestion) = match arms.last() {
+        Some(last_arm) => {
+            let indent = " ".repeat(sm.span_to_margin(last_arm.span).unwrap_or(0));
+            // The new arms go right after the body of the last arm, in front of its comma if it
+            // has one, so a comma is needed before each of them.
+            let new_arms: String = patterns
+                .iter()
+                .map(|pat| format!(",\n{}{} => todo!()", indent, pat))
+                .collect();
+            (last_arm.span.shrink_to_hi(), new_arms)
+        }
+        None => {
+            // Replace the empty pair of braces.
+            let indent = " ".repeat(sm.span_to_margin(expr_span).unwrap_or(0));
+            let new_arms: String = patterns
+                .iter()
+                .map(|pat| format!("{}    {} => todo!(),\n", indent, pat))
+                .collect();
+            (scrut.between(expr_span.shrink_to_hi()), format!(" {{\n{}{}}}", new_arms, indent))
+        }
+    };
+    err.span_suggestion_verbose(
+        span,
+        "add the missing match arms",
+        suggestion,
+        Applicability::MachineApplicable,
+    );
+}
+
+/// Renders a witness of a non-exhaustive match as source code that can be used as the pattern of
+/// a new match arm. Returns `None` if the pattern can't be written in stable Rust, e.g. because it
+/// contains a `box` pattern.
+fn witness_to_source<'p, 'tcx>(
+    cx: &MatchCheckCtxt<'p, 'tcx>,
+    pat: &thir::Pat<'tcx>,
+) -> Option<String> {
+    let all_to_source = |pats: &[thir::Pat<'tcx>]| {
+        pats.iter().map(|pat| witness_to_source(cx, pat)).collect::<Option<Vec<_>>>()
+    };
+    Some(match *pat.kind {
+        thir::PatKind::Wild => "_".to_string(),
+        thir::PatKind::Variant { adt_def, variant_index, ref subpatterns, .. } => {
+            let variant = &adt_def.variants[variant_index];
+            let path = if [sym::Option, sym::Result]
+                .iter()
+                .any(|&name| cx.tcx.is_diagnostic_item(name, adt_def.did))
+            {
+                // These variants are in the prelude.
+                variant.ident.to_string()
+            } else {
+                format!("{}::{}", adt_path_to_source(cx, adt_def), variant.ident)
+            };
+            fields_to_source(cx, path, variant, subpatterns)?
+        }
+        thir::PatKind::Leaf { ref subpatterns } => match pat.ty.kind() {
+            // Union patterns have to name exactly one field, which a witness doesn't provide.
+            ty::Adt(adt_def, _) if adt_def.is_union() => return None,
+            ty::Adt(adt_def, _) => {
+                let path = adt_path_to_source(cx, adt_def);
+                fields_to_source(cx, path, adt_def.non_enum_variant(), subpatterns)?
+            }
+            ty::Tuple(..) => {
+                let mut fields = subpatterns.iter().collect::<Vec<_>>();
+                fields.sort_by_key(|field| field.field);
+                let fields = fields
+                    .iter()
+                    .map(|field| witness_to_source(cx, &field.pattern))
+                    .collect::<Option<Vec<_>>>()?;
+                match &fields[..] {
+                    [field] => format!("({},)", field),
+                    _ => format!("({})", fields.join(", ")),
+                }
+            }
+            _ => return None,
+        },
+        thir::PatKind::Deref { ref subpattern } => match pat.ty.kind() {
+            ty::Ref(_, _, mutbl) => {
+                format!("&{}{}", mutbl.prefix_str(), witness_to_source(cx, subpattern)?)
+            }
+            _ => return None,
+        },
+        thir::PatKind::Constant { .. } | thir::PatKind::Range(..) => pat.to_string(),
+        thir::PatKind::Slice { ref prefix, ref slice, ref suffix }
+        | thir::PatKind::Array { ref prefix, ref slice, ref suffix } => {
+            let mut elems = all_to_source(prefix)?;
+            if let Some(slice) = slice {
+                if !matches!(*slice.kind, thir::PatKind::Wild) {
+                    return None;
+                }
+                elems.push("..".to_string());
+            }
+            elems.extend(all_to_source(suffix)?);
+            format!("[{}]", elems.join(", "))
+        }
+        thir::PatKind::AscribeUserType { .. }
+        | thir::PatKind::Binding { .. }
+        | thir::PatKind::Or { .. } => return None,
+    })
+}
+
+/// Renders the fields of a struct or variant pattern, using the syntax of its constructor.
+fn fields_to_source<'p, 'tcx>(
+    cx: &MatchCheckCtxt<'p, 'tcx>,
+    path: String,
+    variant: &ty::VariantDef,
+    subpatterns: &[thir::FieldPat<'tcx>],
+) -> Option<String> {
+    let field_to_source = |i: usize| match subpatterns.iter().find(|p| p.field.index() == i) {
+        Some(p) => witness_to_source(cx, &p.pattern),
+        None => Some("_".to_string()),
+    };
+    let is_accessible = |field: &ty::FieldDef| field.vis.is_accessible_from(cx.module, cx.tcx);
+    // The constructor of a variant or struct with a non-exhaustive field list is private to its
+    // crate, so outside of it even unit and tuple ones have to be matched with braces.
+    let ctor_kind = if variant.is_field_list_non_exhaustive() && !variant.def_id.is_local() {
+        CtorKind::Fictive
+    } else {
+        variant.ctor_kind
+    };
+    Some(match ctor_kind {
+        CtorKind::Const => path,
+        CtorKind::Fn => {
+            // The pattern has to list every field, so all of them have to be visible.
+            if !variant.fields.iter().all(is_accessible) {
+                return None;
+            }
+            let fields =
+                (0..variant.fields.len()).map(field_to_source).collect::<Option<Vec<_>>>()?;
+            format!("{}({})", path, fields.join(", "))
+        }
+        CtorKind::Fictive => {
+            let mut fields = Vec::new();
+            for (i, field) in variant.fields.iter().enumerate() {
+                let pat = field_to_source(i)?;
+                if pat != "_" {
+                    if !is_accessible(field) {
+                        return None;
+                    }
+                    fields.push(format!("{}: {}", field.ident, pat));
+                }
+            }
+            if fields.len() < variant.fields.len() || variant.is_field_list_non_exhaustive() {
+                fields.push("..".to_string());
+            }
+            format!("{} {{ {} }}", path, fields.join(", "))
+        }
+    })
+}
+
+/// Returns a path to `adt_def` that can be written in the module of the match, as short as the
+/// items in scope allow.
+fn adt_path_to_source<'p, 'tcx>(cx: &MatchCheckCtxt<'p, 'tcx>, adt_def: &AdtDef) -> String {
+    let tcx = cx.tcx;
+    let parent = tcx.parent(adt_def.did);
+    // Items defined in the module of the match, or within a function body, which is most likely
+    // the one containing the match, are in scope under their own name.
+    if parent == Some(cx.module) || parent.map_or(false, |p| tcx.def_kind(p) != DefKind::Mod) {
+        return tcx.item_name(adt_def.did).to_string();
+    }
+    // So are the items imported into the module of the match, under the name they are imported
+    // as.
+    if let Some(module) = cx.module.as_local() {
+        let (module, _, _) = tcx.hir().get_module(module);
+        for &item_id in module.item_ids {
+            let item = tcx.hir().item(item_id);
+            if let hir::ItemKind::Use(path, hir::UseKind::Single) = item.kind {
+                if path.res.opt_def_id() == Some(adt_def.did) {
+                    return item.ident.to_string();
+                }
+            }
+        }
+    }
+    let path = with_no_trimmed_paths(|| with_crate_prefix(|| tcx.def_path_str(adt_def.did)));
+    // Paths to other crates need a leading `::` in the 2015 edition, unless they are used from
+    // the crate root.
+    if adt_def.did.is_local() || tcx.sess.rust_2018() || cx.module.index == CRATE_DEF_INDEX {
+        path
+    } else {
+        format!("::{}", path)
+    }
+}
+
 crate fn joined_uncovered_patterns<'p, 'tcx>(
     cx: &MatchCheckCtxt<'p, 'tcx>,
     witnesses: &[DeconstructedPat<'p, 'tcx>],
@@ -188,11 +188,12 @@
 use rustc_index::bit_set::GrowableBitSet;
 use rustc_middle::mir::interpret::{AllocId, ConstValue};
 use rustc_middle::mir::interpret::{ErrorHandled, GlobalAlloc, Scalar};
-use rustc_middle::mir::mono::{InstantiationMode, MonoItem};
+use rustc_middle::mir::mono::{CallTarget, InstantiationMode, MonoItem};
 use rustc_middle::mir::visit::Visitor as MirVisitor;
 use rustc_middle::mir::{self, Local, Location};
 use rustc_middle::ty::adjustment::{CustomCoerceUnsized, PointerCast};
 use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::query::Providers;
 use rustc_middle::ty::subst::{GenericArgKind, InternalSubsts};
 use rustc_middle::ty::{self, GenericParamDefKind, Instance, Ty, TyCtxt, TypeFoldable, VtblEntry};
 use rustc_middle::{middle::codegen_fn_attrs::CodegenFnAttrFlags, mir::visit::TyContext};
@@ -934,6 +935,70 @@ fn visit_instance_use<'tcx>(
     }
 }
 
+pub fn provide(providers: &mut Providers) {
+    providers.instance_call_targets = instance_call_targets;
+}
+
+/// Collects the calls made by `instance`, resolving the callees like the
+/// `MirNeighborCollector` does. Unlike the collector, this includes upstream
+/// callees, and leaves out functions that are only referenced, not called.
+fn instance_call_targets<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+) -> &'tcx [CallTarget<'tcx>] {
+    let body = tcx.instance_mir(instance.def);
+    let monomorphize = |ty: Ty<'tcx>| {
+        instance.subst_mir_and_normalize_erasing_regions(tcx, ty::ParamEnv::reveal_all(), ty)
+    };
+    let call_target = |callee: Instance<'tcx>| match callee.def {
+        ty::InstanceDef::Virtual(..) => Some(CallTarget::Indirect),
+        // Trait objects are dropped through their vtable.
+        ty::InstanceDef::DropGlue(_, Some(ty)) if ty.is_trait() => Some(CallTarget::Indirect),
+        // Intrinsics are expanded in place and no-op drop glue isn't called.
+        ty::InstanceDef::Intrinsic(_) | ty::InstanceDef::DropGlue(_, None) => None,
+        // Codegen calls the polymorphized instance, so its frame is the one
+        // that ends up in the stack sizes.
+        _ => Some(CallTarget::Instance(callee.polymorphize(tcx))),
+    };
+
+    let mut targets = Vec::new();
+    for block in body.basic_blocks() {
+        let target = match block.terminator().kind {
+            mir::TerminatorKind::Call { ref func, .. } => {
+                match *monomorphize(func.ty(body, tcx)).kind() {
+                    ty::FnDef(def_id, substs) => call_target(
+                        ty::Instance::resolve(tcx, ty::ParamEnv::reveal_all(), def_id, substs)
+                            .unwrap()
+                            .unwrap(),
+                    ),
+                    _ => Some(CallTarget::Indirect),
+                }
+            }
+            mir::TerminatorKind::Drop { ref place, .. }
+            | mir::TerminatorKind::DropAndReplace { ref place, .. } => {
+                let ty = monomorphize(place.ty(body, tcx).ty);
+                call_target(Instance::resolve_drop_in_place(tcx, ty))
+            }
+            mir::TerminatorKind::Assert { ref msg, .. } => {
+                // Failed assertions panic like in `codegen_assert_terminator`.
+                let lang_item = match *msg {
+                    mir::AssertKind::BoundsCheck { .. } => LangItem::PanicBoundsCheck,
+                    _ => LangItem::Panic,
+                };
+                let def_id = tcx.require_lang_item(lang_item, None);
+                Some(CallTarget::Instance(Instance::mono(tcx, def_id)))
+            }
+            _ => None,
+        };
+        if let Some(target) = target {
+            if !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+    }
+    tcx.arena.alloc_from_iter(targets)
+}
+
 // Returns `true` if we should codegen an instance in the local crate.
 // Returns `false` if we can just link to the upstream crate and therefore don't
 // need a mono item.
@@ -941,14 +1006,14 @@ fn should_codegen_locally<'tcx>(tcx: TyCtxt<'tcx>, instance: &Instance<'tcx>) ->
     let def_id = match instance.def {
         ty::InstanceDef::Item(def) => def.did,
         ty::InstanceDef::DropGlue(def_id, Some(_)) => def_id,
-        ty::InstanceDef::VtableShim(..)
-        | ty::InstanceDef::ReifyShim(..)
-        | ty::InstanceDef::ClosureOnceShim { .. }
-        | ty::InstanceDef::Virtual(..)
-        | ty::InstanceDef::FnPtrShim(..)
+        // Shims with generic arguments may have been exported by an upstream crate that
+        // shares its generics, in which case we link against that copy.
+        def if def.is_shareable_shim() => {
+            return instance.polymorphize(tcx).upstream_monomorphization(tcx).is_none();
+        }
+        ty::InstanceDef::Virtual(..)
         | ty::InstanceDef::DropGlue(..)
-        | ty::InstanceDef::Intrinsic(_)
-        | ty::InstanceDef::CloneShim(..) => return true,
+        | ty::InstanceDef::Intrinsic(_) => return true,
     };
 
     if tcx.is_foreign_item(def_id) {
@@ -1086,7 +1151,15 @@ fn create_fn_mono_item<'tcx>(
         crate::util::dump_closure_profile(tcx, instance);
     }
 
-    respan(source, MonoItem::Fn(instance.polymorphize(tcx)))
+    let polymorphized = instance.polymorphize(tcx);
+    if tcx.sess.opts.debugging_opts.print_polymorphization_stats && polymorphized != instance {
+        with_no_trimmed_paths(|| {
+            let stats = &tcx.sess.code_stats;
+            stats.record_polymorphized_instance(polymorphized.to_string(), instance.to_string())
+        });
+    }
+
+    respan(source, MonoItem::Fn(polymorphized))
 }
 
 /// Creates a `MonoItem` for each method that is referenced by the vtable for
@@ -45,6 +45,7 @@ fn custom_coerce_unsize_info<'tcx>(
 }
 
 pub fn provide(providers: &mut Providers) {
+    collector::provide(providers);
     partitioning::provide(providers);
     polymorphize::provide(providers);
 }
@@ -419,6 +419,16 @@ fn mono_item_visibility(
         InstanceDef::Item(def) => def.did,
         InstanceDef::DropGlue(def_id, Some(_)) => def_id,
 
+        // Generic shims are shared with downstream crates in the same way as
+        // upstream monomorphizations, see `exported_symbols_provider_local`.
+        def if def.is_shareable_shim()
+            && export_generics
+            && instance.substs.non_erasable_generics().next().is_some() =>
+        {
+            *can_be_internalized = false;
+            return default_visibility(tcx, def.def_id(), true);
+        }
+
         // These are all compiler glue and such, never exported, always hidden.
         InstanceDef::VtableShim(..)
         | InstanceDef::ReifyShim(..)
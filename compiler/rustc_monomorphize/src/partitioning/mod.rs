@@ -94,6 +94,7 @@
 
 mod default;
 mod merging;
+mod profile_guided;
 
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::sync;
@@ -107,6 +108,7 @@
 
 use crate::collector::InliningMap;
 use crate::collector::{self, MonoItemCollectionMode};
+use crate::util;
 
 pub struct PartitioningCx<'a, 'tcx> {
     tcx: TyCtxt<'tcx>,
@@ -142,12 +144,16 @@ fn internalize_symbols(
 
 fn get_partitioner<'tcx>(tcx: TyCtxt<'tcx>) -> Box<dyn Partitioner<'tcx>> {
     let strategy = match &tcx.sess.opts.debugging_opts.cgu_partitioning_strategy {
+        None if tcx.sess.opts.debugging_opts.cgu_partitioning_profile.is_some() => {
+            "profile-guided"
+        }
         None => "default",
         Some(s) => &s[..],
     };
 
     match strategy {
         "default" => Box::new(default::DefaultPartitioning),
+        "profile-guided" => Box::new(profile_guided::ProfileGuidedPartitioning::new(tcx)),
         _ => tcx.sess.fatal("unknown partitioning strategy"),
     }
 }
@@ -421,6 +427,14 @@ fn collect_and_partition_mono_items<'tcx>(
         }
     }
 
+    if tcx.sess.opts.debugging_opts.print_mono_item_groups {
+        util::print_mono_item_groups(tcx, &items, &inlining_map);
+    }
+
+    if tcx.sess.opts.debugging_opts.print_polymorphization_stats {
+        tcx.sess.code_stats.print_polymorphization_stats();
+    }
+
     (tcx.arena.alloc(mono_items), codegen_units)
 }
 
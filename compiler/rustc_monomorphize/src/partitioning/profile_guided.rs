@@ -0,0 +1,178 @@
+//! Profile-Guided Partitioning
+//! ===========================
+//!
+//! This strategy is used when a profile is passed with
+//! `-Z cgu-partitioning-profile=<path>`. The profile lists the functions that
+//! were found to be hot when running the program, one per line. Each line is
+//! either a symbol name, as reported by `perf` or `llvm-profdata show
+//! --topn=N`, or a path like `my_crate::module::function`, which matches every
+//! instance of that function. Empty lines and lines starting with `#` are
+//! ignored.
+//!
+//! Root mono items are placed just like with the default strategy. Afterwards,
+//! the hot functions and the local functions they call directly are moved into
+//! a single codegen unit of their own. That way LLVM can inline across hot
+//! callers and callees, and the hot code ends up next to each other in the
+//! final binary. The hot codegen unit is exempt from merging, so cold code is
+//! kept apart from it.
+
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_middle::mir::mono::{CodegenUnit, CodegenUnitNameBuilder, MonoItem};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::symbol::Symbol;
+
+use super::PartitioningCx;
+use crate::partitioning::default::DefaultPartitioning;
+use crate::partitioning::merging;
+use crate::partitioning::{Partitioner, PostInliningPartitioning, PreInliningPartitioning};
+
+pub struct ProfileGuidedPartitioning {
+    hot_functions: FxHashSet<String>,
+    /// The name of the codegen unit holding the hot code, if there is any.
+    hot_cgu_name: Option<Symbol>,
+}
+
+impl ProfileGuidedPartitioning {
+    pub fn new(tcx: TyCtxt<'_>) -> Self {
+        let path = match tcx.sess.opts.debugging_opts.cgu_partitioning_profile {
+            Some(ref path) => path,
+            None => tcx.sess.fatal(
+                "the `profile-guided` partitioning strategy requires `-Z cgu-partitioning-profile`",
+            ),
+        };
+
+        let profile = match std::fs::read_to_string(path) {
+            Ok(profile) => profile,
+            Err(err) => tcx.sess.fatal(&format!(
+                "failed to read CGU partitioning profile `{}`: {}",
+                path.display(),
+                err
+            )),
+        };
+
+        let hot_functions = profile
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| line.to_string())
+            .collect();
+
+        ProfileGuidedPartitioning { hot_functions, hot_cgu_name: None }
+    }
+
+    fn is_hot<'tcx>(&self, tcx: TyCtxt<'tcx>, mono_item: MonoItem<'tcx>) -> bool {
+        let instance = match mono_item {
+            MonoItem::Fn(instance) => instance,
+            MonoItem::Static(..) | MonoItem::GlobalAsm(..) => return false,
+        };
+
+        if self.hot_functions.contains(tcx.symbol_name(instance).name) {
+            return true;
+        }
+
+        // Paths of local items are printed without the crate name, but the
+        // profile refers to them with it.
+        let def_id = instance.def_id();
+        let path = with_no_trimmed_paths(|| tcx.def_path_str(def_id));
+        if def_id.is_local() {
+            self.hot_functions.contains(&format!("{}::{}", tcx.crate_name(LOCAL_CRATE), path))
+        } else {
+            self.hot_functions.contains(&path)
+        }
+    }
+}
+
+impl<'tcx> Partitioner<'tcx> for ProfileGuidedPartitioning {
+    fn place_root_mono_items(
+        &mut self,
+        cx: &PartitioningCx<'_, 'tcx>,
+        mono_items: &mut dyn Iterator<Item = MonoItem<'tcx>>,
+    ) -> PreInliningPartitioning<'tcx> {
+        let mut partitioning = DefaultPartitioning.place_root_mono_items(cx, mono_items);
+
+        // With a single codegen unit, everything is kept together anyway.
+        if cx.target_cgu_count == 1 {
+            return partitioning;
+        }
+
+        let hot_roots: FxHashSet<_> =
+            partitioning.roots.iter().copied().filter(|&item| self.is_hot(cx.tcx, item)).collect();
+        if hot_roots.is_empty() {
+            return partitioning;
+        }
+
+        // Take the direct callees of the hot functions along, so that LLVM can
+        // inline them into their hot callers.
+        let mut hot_items = hot_roots.clone();
+        cx.inlining_map.iter_accesses(|accessor, accessees| {
+            if hot_roots.contains(&accessor) {
+                hot_items.extend(
+                    accessees.iter().copied().filter(|item| partitioning.roots.contains(item)),
+                );
+            }
+        });
+
+        let hot_cgu_name =
+            CodegenUnitNameBuilder::new(cx.tcx).build_cgu_name(LOCAL_CRATE, &["hot"], Some("cgu"));
+        let mut hot_cgu = CodegenUnit::new(hot_cgu_name);
+
+        for cgu in &mut partitioning.codegen_units {
+            cgu.items_mut().retain(|mono_item, linkage| {
+                if hot_items.contains(mono_item) {
+                    hot_cgu.items_mut().insert(*mono_item, *linkage);
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+
+        debug!("moved {} hot mono items into CodegenUnit {}", hot_cgu.items().len(), hot_cgu_name);
+
+        partitioning.codegen_units.retain(|cgu| !cgu.items().is_empty());
+        partitioning.codegen_units.push(hot_cgu);
+        self.hot_cgu_name = Some(hot_cgu_name);
+
+        partitioning
+    }
+
+    fn merge_codegen_units(
+        &mut self,
+        cx: &PartitioningCx<'_, 'tcx>,
+        initial_partitioning: &mut PreInliningPartitioning<'tcx>,
+    ) {
+        let hot_cgu_name = match self.hot_cgu_name {
+            Some(hot_cgu_name) => hot_cgu_name,
+            None => return merging::merge_codegen_units(cx, initial_partitioning),
+        };
+
+        // Only merge the cold codegen units, into one fewer than the target
+        // count to leave room for the hot one.
+        let codegen_units = &mut initial_partitioning.codegen_units;
+        let hot_index = codegen_units.iter().position(|cgu| cgu.name() == hot_cgu_name).unwrap();
+        let hot_cgu = codegen_units.swap_remove(hot_index);
+
+        let cold_cx = PartitioningCx { target_cgu_count: cx.target_cgu_count - 1, ..*cx };
+        merging::merge_codegen_units(&cold_cx, initial_partitioning);
+
+        initial_partitioning.codegen_units.push(hot_cgu);
+    }
+
+    fn place_inlined_mono_items(
+        &mut self,
+        cx: &PartitioningCx<'_, 'tcx>,
+        initial_partitioning: PreInliningPartitioning<'tcx>,
+    ) -> PostInliningPartitioning<'tcx> {
+        DefaultPartitioning.place_inlined_mono_items(cx, initial_partitioning)
+    }
+
+    fn internalize_symbols(
+        &mut self,
+        cx: &PartitioningCx<'_, 'tcx>,
+        partitioning: &mut PostInliningPartitioning<'tcx>,
+    ) {
+        DefaultPartitioning.internalize_symbols(cx, partitioning)
+    }
+}
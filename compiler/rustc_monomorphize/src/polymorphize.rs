@@ -5,26 +5,31 @@
 //! generic parameters are unused (and eventually, in what ways generic parameters are used - only
 //! for their size, offset of a field, etc.).
 
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_data_structures::stack::ensure_sufficient_stack;
 use rustc_hir::{def::DefKind, def_id::DefId, ConstContext};
 use rustc_index::bit_set::FiniteBitSet;
 use rustc_middle::mir::{
     visit::{TyContext, Visitor},
-    Local, LocalDecl, Location,
+    Body, CastKind, Local, LocalDecl, Location, Rvalue, StatementKind, TerminatorKind,
 };
 use rustc_middle::ty::{
     self,
+    adjustment::PointerCast,
     fold::{TypeFoldable, TypeVisitor},
     query::Providers,
-    subst::SubstsRef,
-    Const, Ty, TyCtxt,
+    subst::{GenericArg, GenericArgKind, InternalSubsts, SubstsRef},
+    Const, GenericParamDefKind, Instance, Ty, TyCtxt,
 };
 use rustc_span::symbol::sym;
 use std::convert::TryInto;
+use std::mem;
 use std::ops::ControlFlow;
 
 /// Provide implementations of queries relating to polymorphization analysis.
 pub fn provide(providers: &mut Providers) {
     providers.unused_generic_params = unused_generic_params;
+    providers.types_observed_by = types_observed_by;
 }
 
 /// Determine which generic parameters are used by the function/method/closure represented by
@@ -32,7 +37,7 @@ pub fn provide(providers: &mut Providers) {
 /// indicates all parameters are used).
 #[instrument(level = "debug", skip(tcx))]
 fn unused_generic_params(tcx: TyCtxt<'_>, def_id: DefId) -> FiniteBitSet<u32> {
-    if !tcx.sess.opts.debugging_opts.polymorphize {
+    if !tcx.sess.polymorphize() {
         // If polymorphization disabled, then all parameters are used.
         return FiniteBitSet::new_empty();
     }
@@ -82,7 +87,14 @@ fn unused_generic_params(tcx: TyCtxt<'_>, def_id: DefId) -> FiniteBitSet<u32> {
         Some(ConstContext::ConstFn) | None => tcx.optimized_mir(def_id),
         Some(_) => tcx.mir_for_ctfe(def_id),
     };
-    let mut vis = MarkUsedGenericParams { tcx, def_id, unused_parameters: &mut unused_parameters };
+    let mut vis = MarkUsedGenericParams {
+        tcx,
+        def_id,
+        body: Some(body),
+        escaping: false,
+        observed_types: &mut ObservedTypes::new(tcx, def_id),
+        unused_parameters: &mut unused_parameters,
+    };
     vis.visit_body(body);
     debug!(?unused_parameters, "(after visitor)");
 
@@ -167,6 +179,7 @@ fn mark_used_by_predicates<'tcx>(
     let def_id = tcx.closure_base_def_id(def_id);
     let predicates = tcx.explicit_predicates_of(def_id);
 
+    let mut observed_types = ObservedTypes::new(tcx, def_id);
     let mut current_unused_parameters = FiniteBitSet::new_empty();
     // Run to a fixed point to support `where T: Trait<U>, U: Trait<V>`, starting with an empty
     // bit set so that this is skipped if all parameters are already used.
@@ -183,7 +196,14 @@ fn mark_used_by_predicates<'tcx>(
             };
 
             if any_param_used {
-                let mut vis = MarkUsedGenericParams { tcx, def_id, unused_parameters };
+                let mut vis = MarkUsedGenericParams {
+                    tcx,
+                    def_id,
+                    body: None,
+                    escaping: false,
+                    observed_types: &mut observed_types,
+                    unused_parameters,
+                };
                 predicate.visit_with(&mut vis);
             }
         }
@@ -235,6 +255,15 @@ fn emit_unused_generic_params_error<'tcx>(
 struct MarkUsedGenericParams<'a, 'tcx> {
     tcx: TyCtxt<'tcx>,
     def_id: DefId,
+    /// The MIR being visited, if any, to find the types of operands.
+    body: Option<&'tcx Body<'tcx>>,
+    /// Whether the types being visited escape to code that observes their identity with
+    /// `TypeId::of` or `type_name`, as generic arguments of functions or sources of unsizing
+    /// casts to trait objects. Closures and generators in escaping types use all of their generic
+    /// parameters, as sharing their instances would make their distinct types indistinguishable.
+    escaping: bool,
+    /// The types observed by the functions and trait objects that the item uses.
+    observed_types: &'a mut ObservedTypes<'tcx>,
     unused_parameters: &'a mut FiniteBitSet<u32>,
 }
 
@@ -253,6 +282,27 @@ fn visit_child_body(&mut self, def_id: DefId, substs: SubstsRef<'tcx>) {
         }
         debug!(?self.unused_parameters);
     }
+
+    /// Visit `ty` as a type which escapes the current item, see `escaping`.
+    fn visit_escaping_ty(&mut self, ty: Ty<'tcx>) {
+        let escaping = mem::replace(&mut self.escaping, true);
+        ty.visit_with(self);
+        self.escaping = escaping;
+    }
+
+    /// Visit `ty`, as a type which escapes the current item if one of its closures or generators
+    /// is part of the `observed` types.
+    fn visit_maybe_escaping_ty(&mut self, ty: Ty<'tcx>, observed: &[Ty<'tcx>]) {
+        let is_observed = ty.walk(self.tcx).any(|arg| {
+            is_closure_or_generator(arg)
+                && observed.iter().any(|observed| observed.walk(self.tcx).any(|a| a == arg))
+        });
+        if is_observed {
+            self.visit_escaping_ty(ty);
+        } else {
+            ty.visit_with(self);
+        }
+    }
 }
 
 impl<'a, 'tcx> Visitor<'tcx> for MarkUsedGenericParams<'a, 'tcx> {
@@ -273,6 +323,22 @@ fn visit_local_decl(&mut self, local: Local, local_decl: &LocalDecl<'tcx>) {
         self.super_local_decl(local, local_decl);
     }
 
+    fn visit_rvalue(&mut self, rvalue: &Rvalue<'tcx>, location: Location) {
+        if let (Rvalue::Cast(CastKind::Pointer(PointerCast::Unsize), operand, target), Some(body)) =
+            (rvalue, self.body)
+        {
+            // The source of an unsizing cast can end up as a trait object, whose vtable can
+            // observe the identity of the type, e.g. `dyn Any`.
+            let source = operand.ty(body, self.tcx);
+            if source.walk(self.tcx).any(is_closure_or_generator) {
+                let observed = self.observed_types.of_unsize(source, *target);
+                self.visit_maybe_escaping_ty(source, &observed);
+            }
+        }
+
+        self.super_rvalue(rvalue, location);
+    }
+
     fn visit_const(&mut self, c: &&'tcx Const<'tcx>, _: Location) {
         c.visit_with(self);
     }
@@ -306,7 +372,9 @@ fn visit_const(&mut self, c: &'tcx Const<'tcx>) -> ControlFlow<Self::BreakTy> {
                 // If there is a promoted, don't look at the substs - since it will always contain
                 // the generic parameters, instead, traverse the promoted MIR.
                 let promoted = self.tcx.promoted_mir(def.did);
+                let body = self.body.replace(&promoted[p]);
                 self.visit_body(&promoted[p]);
+                self.body = body;
                 ControlFlow::CONTINUE
             }
             ty::ConstKind::Unevaluated(uv)
@@ -333,11 +401,34 @@ fn visit_ty(&mut self, ty: Ty<'tcx>) -> ControlFlow<Self::BreakTy> {
                     return ControlFlow::CONTINUE;
                 }
 
+                if self.escaping {
+                    // The identity of the closure/generator is observable, so its instances
+                    // can't be shared between any of its generic arguments.
+                    return ty.super_visit_with(self);
+                }
+
                 // Consider any generic parameters used by any closures/generators as used in the
                 // parent.
                 self.visit_child_body(def_id, substs);
                 ControlFlow::CONTINUE
             }
+            ty::FnDef(def_id, substs)
+                if substs.iter().any(|arg| arg.walk(self.tcx).any(is_closure_or_generator)) =>
+            {
+                // A closure passed to a function escapes if the code of the function can reach
+                // `TypeId::of::<F>()` or `type_name::<F>()`, which isn't the case when it is only
+                // called through the `Fn*` traits.
+                let observed = self.observed_types.of_call(def_id, substs);
+                for arg in substs {
+                    match arg.unpack() {
+                        GenericArgKind::Type(ty) => self.visit_maybe_escaping_ty(ty, &observed),
+                        _ => {
+                            arg.visit_with(self);
+                        }
+                    }
+                }
+                ControlFlow::CONTINUE
+            }
             ty::Param(param) => {
                 debug!(?param);
                 self.unused_parameters.clear(param.index);
@@ -348,6 +439,248 @@ fn visit_ty(&mut self, ty: Ty<'tcx>) -> ControlFlow<Self::BreakTy> {
     }
 }
 
+fn is_closure_or_generator(arg: GenericArg<'_>) -> bool {
+    match arg.unpack() {
+        GenericArgKind::Type(ty) => matches!(ty.kind(), ty::Closure(..) | ty::Generator(..)),
+        _ => false,
+    }
+}
+
+/// Computes the types observed by the code of an instance and the code it calls.
+fn types_observed_by<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    key: ty::ParamEnvAnd<'tcx, Instance<'tcx>>,
+) -> &'tcx ty::List<Ty<'tcx>> {
+    let ty::ParamEnvAnd { param_env, value: instance } = key;
+    let mut observed_types = ObservedTypes {
+        tcx,
+        param_env,
+        in_query: true,
+        cache: Default::default(),
+        stack: Vec::new(),
+        recursion_depth: usize::MAX,
+    };
+    tcx.mk_type_list(observed_types.of_instance(instance).into_iter())
+}
+
+/// Finds the types whose identity is observed with `TypeId::of` or `type_name` by the code that
+/// an item can run, following the instances of the functions it calls and of the vtables of the
+/// trait objects it makes, as resolved in the environment of the item.
+struct ObservedTypes<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    /// Whether this computes the `types_observed_by` query, which follows the code of the
+    /// instances it reaches on its own. Otherwise, what each instance observes is looked up with
+    /// the query, so that it is shared between all the items that reach the instance.
+    in_query: bool,
+    /// The types observed by the instances whose code has been followed.
+    cache: FxHashMap<Instance<'tcx>, Vec<Ty<'tcx>>>,
+    /// The instances whose code is being followed, to stop at recursive calls.
+    stack: Vec<Instance<'tcx>>,
+    /// The outermost position in `stack` whose instance was reached again by a recursive call:
+    /// the instances above it don't include what it observes, so they aren't cached.
+    recursion_depth: usize,
+}
+
+impl<'tcx> ObservedTypes<'tcx> {
+    fn new(tcx: TyCtxt<'tcx>, def_id: DefId) -> Self {
+        ObservedTypes {
+            tcx,
+            param_env: tcx.param_env_reveal_all_normalized(def_id),
+            in_query: false,
+            cache: Default::default(),
+            stack: Vec::new(),
+            recursion_depth: usize::MAX,
+        }
+    }
+
+    /// Returns the types observed by a call to `def_id` with `substs`.
+    fn of_call(&mut self, def_id: DefId, substs: SubstsRef<'tcx>) -> Vec<Ty<'tcx>> {
+        let substs = self.tcx.normalize_erasing_regions(self.param_env, substs);
+        let instance = match Instance::resolve(self.tcx, self.param_env, def_id, substs) {
+            Ok(Some(instance)) => instance,
+            // The callee depends on the generic arguments of the item, so it could observe
+            // any of the types it is called with.
+            _ => return substs.types().collect(),
+        };
+
+        match instance.def {
+            ty::InstanceDef::Intrinsic(def_id) => match self.tcx.item_name(def_id) {
+                sym::type_id | sym::type_name => instance.substs.types().collect(),
+                _ => Vec::new(),
+            },
+            // The types observed by virtual calls are found where the trait objects are made.
+            ty::InstanceDef::Virtual(..) => Vec::new(),
+            ty::InstanceDef::DropGlue(_, Some(ty)) => self.of_drop(ty),
+            ty::InstanceDef::DropGlue(_, None) => Vec::new(),
+            _ => self.of_instance(instance),
+        }
+    }
+
+    /// Returns the types observed by the code of `instance` and the code it calls.
+    fn of_instance(&mut self, instance: Instance<'tcx>) -> Vec<Ty<'tcx>> {
+        if !self.in_query {
+            return self.tcx.types_observed_by(self.param_env.and(instance)).to_vec();
+        }
+        if let Some(observed) = self.cache.get(&instance) {
+            return observed.clone();
+        }
+
+        if let Some(depth) = self.stack.iter().position(|&i| i == instance) {
+            // What the instance observes is found by the call in progress.
+            self.recursion_depth = self.recursion_depth.min(depth);
+            return Vec::new();
+        }
+
+        let has_mir = match instance.def {
+            ty::InstanceDef::Item(def) => self.tcx.is_mir_available(def.did),
+            _ => true,
+        };
+        if !has_mir || !self.tcx.recursion_limit().value_within_limit(self.stack.len()) {
+            return instance.substs.types().collect();
+        }
+
+        let (tcx, param_env) = (self.tcx, self.param_env);
+        let monomorphize =
+            |ty: Ty<'tcx>| instance.subst_mir_and_normalize_erasing_regions(tcx, param_env, ty);
+        let body = tcx.instance_mir(instance.def);
+        let mut observed = Vec::new();
+        self.stack.push(instance);
+        for block in body.basic_blocks() {
+            for statement in &block.statements {
+                let (kind, operand, target) = match statement.kind {
+                    StatementKind::Assign(ref assign) => match assign.1 {
+                        Rvalue::Cast(CastKind::Pointer(kind), ref operand, target) => {
+                            (kind, operand, target)
+                        }
+                        _ => continue,
+                    },
+                    _ => continue,
+                };
+                let source = monomorphize(operand.ty(body, tcx));
+                let target = monomorphize(target);
+                observed.extend(ensure_sufficient_stack(|| match (kind, *source.kind()) {
+                    (PointerCast::Unsize, _) => self.of_unsize(source, target),
+                    // The function or closure can then be called through the pointer.
+                    (PointerCast::ReifyFnPointer, ty::FnDef(def_id, substs)) => {
+                        self.of_call(def_id, substs)
+                    }
+                    (PointerCast::ClosureFnPointer(_), ty::Closure(def_id, substs)) => {
+                        self.of_instance(Instance::new(def_id, substs))
+                    }
+                    _ => Vec::new(),
+                }));
+            }
+
+            match block.terminator().kind {
+                TerminatorKind::Call { ref func, .. } => {
+                    if let ty::FnDef(def_id, substs) = *monomorphize(func.ty(body, tcx)).kind() {
+                        observed.extend(ensure_sufficient_stack(|| self.of_call(def_id, substs)));
+                    }
+                }
+                TerminatorKind::Drop { place, .. }
+                | TerminatorKind::DropAndReplace { place, .. } => {
+                    let ty = monomorphize(place.ty(body, tcx).ty);
+                    observed.extend(ensure_sufficient_stack(|| self.of_drop(ty)));
+                }
+                _ => {}
+            }
+        }
+        self.stack.pop();
+
+        let depth = self.stack.len();
+        if self.recursion_depth >= depth {
+            self.recursion_depth = usize::MAX;
+            self.cache.insert(instance, observed.clone());
+        }
+        observed
+    }
+
+    /// Returns the types observed by the `Drop` impls that run when a value of type `ty` is
+    /// dropped.
+    fn of_drop(&mut self, ty: Ty<'tcx>) -> Vec<Ty<'tcx>> {
+        let mut observed = Vec::new();
+        let mut visited = FxHashSet::default();
+        let mut types = vec![ty];
+        while let Some(ty) = types.pop() {
+            for arg in ty.walk(self.tcx) {
+                let (adt_def, substs) = match arg.unpack() {
+                    GenericArgKind::Type(ty) => match *ty.kind() {
+                        ty::Adt(adt_def, substs) if visited.insert(ty) => (adt_def, substs),
+                        _ => continue,
+                    },
+                    _ => continue,
+                };
+
+                if !self.tcx.recursion_limit().value_within_limit(visited.len()) {
+                    // The fields of the type keep growing, so any of it could be observed.
+                    observed.push(ty);
+                    return observed;
+                }
+
+                // `Drop` impls have the same generic parameters as the type they are for.
+                if let Some(destructor) = adt_def.destructor(self.tcx) {
+                    observed.extend(self.of_call(destructor.did, substs));
+                }
+                types.extend(adt_def.all_fields().map(|field| {
+                    self.tcx.normalize_erasing_regions(self.param_env, field.ty(self.tcx, substs))
+                }));
+            }
+        }
+        observed
+    }
+
+    /// Returns the types observed by the methods in the vtable of the trait object made by
+    /// unsizing `source` to `target`.
+    fn of_unsize(&mut self, source: Ty<'tcx>, target: Ty<'tcx>) -> Vec<Ty<'tcx>> {
+        let tcx = self.tcx;
+        let (source, target) = match (source.builtin_deref(true), target.builtin_deref(true)) {
+            (Some(source), Some(target)) => (source.ty, target.ty),
+            // Smart pointers unsize their contents with `CoerceUnsized`, assume that all of the
+            // source is observed.
+            _ => return vec![source],
+        };
+        let principal = match *target.kind() {
+            ty::Dynamic(predicates, _) => predicates.principal(),
+            // Unsizing to a slice doesn't make a vtable.
+            _ => None,
+        };
+        let trait_ref = match principal {
+            Some(principal) => tcx.erase_late_bound_regions(principal.with_self_ty(tcx, source)),
+            None => return Vec::new(),
+        };
+
+        let mut observed = Vec::new();
+        let mut visited = FxHashSet::default();
+        let mut trait_refs = vec![trait_ref];
+        while let Some(trait_ref) = trait_refs.pop() {
+            if !visited.insert(trait_ref) {
+                continue;
+            }
+
+            let existential_trait_ref =
+                ty::Binder::dummy(ty::ExistentialTraitRef::erase_self_ty(tcx, trait_ref));
+            for &def_id in tcx.own_existential_vtable_entries(existential_trait_ref) {
+                let substs = InternalSubsts::for_item(tcx, def_id, |param, _| match param.kind {
+                    GenericParamDefKind::Lifetime => tcx.lifetimes.re_erased.into(),
+                    GenericParamDefKind::Type { .. } | GenericParamDefKind::Const { .. } => {
+                        trait_ref.substs[param.index as usize]
+                    }
+                });
+                observed.extend(self.of_call(def_id, substs));
+            }
+
+            let super_predicates = tcx.super_predicates_of(trait_ref.def_id).predicates;
+            trait_refs.extend(super_predicates.iter().filter_map(|&(predicate, _)| {
+                let predicate = predicate.subst_supertrait(tcx, &ty::Binder::dummy(trait_ref));
+                let super_trait_ref = predicate.to_opt_poly_trait_ref()?.value;
+                Some(tcx.erase_late_bound_regions(super_trait_ref))
+            }));
+        }
+        observed
+    }
+}
+
 /// Visitor used to check if a generic parameter is used.
 struct HasUsedGenericParams<'a, 'tcx> {
     tcx: TyCtxt<'tcx>,
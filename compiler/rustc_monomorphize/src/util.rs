@@ -1,3 +1,9 @@
+use crate::collector::InliningMap;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir::def_id::{CrateNum, DefId};
+use rustc_middle::mir::mono::MonoItem;
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::subst::GenericArgKind;
 use rustc_middle::ty::{self, ClosureSizeProfileData, Instance, TyCtxt};
 use std::fs::OpenOptions;
 use std::io::prelude::*;
@@ -71,3 +77,79 @@
         }
     }
 }
+
+/// Prints the generic functions that were monomorphized, grouped by their definition, for
+/// `-Z print-mono-item-groups`. Every instantiation is listed with its generic arguments, the
+/// size estimate of its MIR, and the crates whose code uses it.
+///
+/// Groups are printed from the largest total size to the smallest, so that the definitions
+/// that cost the most to instantiate come first.
+crate fn print_mono_item_groups(
+    tcx: TyCtxt<'tcx>,
+    items: &FxHashSet<MonoItem<'tcx>>,
+    inlining_map: &InliningMap<'tcx>,
+) {
+    let mut users: FxHashMap<MonoItem<'tcx>, FxHashSet<CrateNum>> = Default::default();
+    inlining_map.iter_accesses(|accessor, accessed| {
+        for &item in accessed {
+            users.entry(item).or_default().insert(accessor.krate());
+        }
+    });
+
+    let mut groups: FxHashMap<DefId, Vec<(Instance<'tcx>, usize)>> = Default::default();
+    for &item in items {
+        if let MonoItem::Fn(instance) = item {
+            if instance.substs.non_erasable_generics().next().is_some() {
+                let instances = groups.entry(instance.def_id()).or_default();
+                instances.push((instance, item.size_estimate(tcx)));
+            }
+        }
+    }
+
+    with_no_trimmed_paths(|| {
+        let mut groups: Vec<_> = groups
+            .into_iter()
+            .map(|(def_id, instances)| {
+                let total_size: usize = instances.iter().map(|&(_, size)| size).sum();
+                (total_size, tcx.def_path_str(def_id), instances)
+            })
+            .collect();
+        groups.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+
+        for (total_size, path, instances) in groups {
+            println!(
+                "MONO_ITEM_GROUP {} @@ instances: {} size: {}",
+                path,
+                instances.len(),
+                total_size
+            );
+
+            let mut lines: Vec<_> = instances
+                .into_iter()
+                .map(|(instance, size)| {
+                    let args: Vec<_> = instance
+                        .substs
+                        .iter()
+                        .filter(|arg| !matches!(arg.unpack(), GenericArgKind::Lifetime(_)))
+                        .map(|arg| arg.to_string())
+                        .collect();
+                    let mut crates: Vec<_> = users
+                        .get(&MonoItem::Fn(instance))
+                        .into_iter()
+                        .flatten()
+                        .map(|&krate| tcx.crate_name(krate).to_string())
+                        .collect();
+                    crates.sort();
+                    // Items that nothing refers to are roots of the collection, e.g. `main`.
+                    let used_by =
+                        if crates.is_empty() { "<root>".to_string() } else { crates.join(", ") };
+                    (size, format!("    [{}] size: {} used by: {}", args.join(", "), size, used_by))
+                })
+                .collect();
+            lines.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+            for (_, line) in lines {
+                println!("{}", line);
+            }
+        }
+    });
+}
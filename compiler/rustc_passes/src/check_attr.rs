@@ -112,6 +112,8 @@ fn check_attributes(
                     self.check_default_method_body_is_const(attr, span, target)
                 }
                 sym::must_not_suspend => self.check_must_not_suspend(&attr, span, target),
+                sym::on_unimplemented => self.check_on_unimplemented(&attr, span, target),
+                sym::assert_layout => self.check_assert_layout(&attr, span, target),
                 sym::rustc_const_unstable
                 | sym::rustc_const_stable
                 | sym::unstable
@@ -1061,6 +1063,43 @@ fn check_must_not_suspend(&self, attr: &Attribute, span: &Span, target: Target)
         }
     }
 
+    /// Checks if `#[on_unimplemented]` is applied to a trait. Returns `true` if valid.
+    fn check_on_unimplemented(&self, attr: &Attribute, span: &Span, target: Target) -> bool {
+        match target {
+            Target::Trait => true,
+            _ => {
+                self.tcx
+                    .sess
+                    .struct_span_err(
+                        attr.span,
+                        "`on_unimplemented` attribute should be applied to a trait",
+                    )
+                    .span_label(*span, "is not a trait")
+                    .emit();
+                false
+            }
+        }
+    }
+
+    /// Checks if `#[assert_layout]` is applied to a type definition. Returns `true` if valid.
+    fn check_assert_layout(&self, attr: &Attribute, span: &Span, target: Target) -> bool {
+        match target {
+            Target::Struct | Target::Enum | Target::Union | Target::TyAlias => true,
+            _ => {
+                self.tcx
+                    .sess
+                    .struct_span_err(
+                        attr.span,
+                        "`assert_layout` attribute should be applied to a struct, enum, union or \
+                         type alias",
+                    )
+                    .span_label(*span, "is not a struct, enum, union or type alias")
+                    .emit();
+                false
+            }
+        }
+    }
+
     /// Checks if `#[cold]` is applied to a non-function. Returns `true` if valid.
     fn check_cold(&self, hir_id: HirId, attr: &Attribute, span: &Span, target: Target) {
         match target {
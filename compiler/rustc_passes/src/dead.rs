@@ -5,16 +5,20 @@
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_hir as hir;
 use rustc_hir::def::{CtorOf, DefKind, Res};
-use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::def_id::{CrateNum, DefId, LocalDefId, LOCAL_CRATE};
 use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
 use rustc_hir::itemlikevisit::ItemLikeVisitor;
-use rustc_hir::{Node, PatKind, TyKind};
+use rustc_hir::{Node, PatKind, TyKind, CRATE_HIR_ID};
 use rustc_middle::hir::map::Map;
 use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc_middle::middle::privacy;
-use rustc_middle::ty::{self, DefIdTree, TyCtxt};
+use rustc_middle::ty::query::Providers;
+use rustc_middle::ty::subst::GenericArgKind;
+use rustc_middle::ty::{self, DefIdTree, Ty, TyCtxt};
+use rustc_session::config::CrateType;
 use rustc_session::lint;
 use rustc_span::symbol::{sym, Symbol};
+use std::iter;
 use std::mem;
 
 // Any local node that may call something in its body block should be
@@ -800,3 +804,199 @@ pub fn check_crate(tcx: TyCtxt<'_>) {
     let mut visitor = DeadVisitor { tcx, live_symbols };
     tcx.hir().walk_toplevel_module(&mut visitor);
 }
+
+/// Collects the items of other crates that the local crate refers to, for
+/// `-Z unused-pub-items`.
+struct UpstreamUseCollector<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    used: FxHashSet<DefId>,
+}
+
+impl UpstreamUseCollector<'tcx> {
+    fn record(&mut self, def_id: DefId) {
+        if def_id.is_local() || !self.used.insert(def_id) {
+            return;
+        }
+        // Using a part of an item counts as using the item itself.
+        match self.tcx.def_kind(def_id) {
+            DefKind::Ctor(..) | DefKind::Variant | DefKind::Field => {
+                if let Some(parent) = self.tcx.parent(def_id) {
+                    self.record(parent);
+                }
+            }
+            DefKind::AssocFn | DefKind::AssocConst | DefKind::AssocTy => {
+                match self.tcx.associated_item(def_id).container {
+                    ty::TraitContainer(trait_id) => self.record(trait_id),
+                    ty::ImplContainer(impl_id) => {
+                        if let Some(trait_ref) = self.tcx.impl_trait_ref(impl_id) {
+                            self.record(trait_ref.def_id);
+                        }
+                        if let Some(adt) = self.tcx.type_of(impl_id).ty_adt_def() {
+                            self.record(adt.did);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn record_ty(&mut self, ty: Ty<'tcx>) {
+        for arg in ty.walk(self.tcx) {
+            if let GenericArgKind::Type(ty) = arg.unpack() {
+                match *ty.kind() {
+                    ty::Adt(adt, _) => self.record(adt.did),
+                    ty::Foreign(def_id)
+                    | ty::FnDef(def_id, _)
+                    | ty::Closure(def_id, _)
+                    | ty::Generator(def_id, ..)
+                    | ty::Opaque(def_id, _) => self.record(def_id),
+                    ty::Dynamic(preds, _) => {
+                        if let Some(def_id) = preds.principal_def_id() {
+                            self.record(def_id);
+                        }
+                        for def_id in preds.auto_traits() {
+                            self.record(def_id);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+impl Visitor<'tcx> for UpstreamUseCollector<'tcx> {
+    type Map = Map<'tcx>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::All(self.tcx.hir())
+    }
+
+    fn visit_nested_body(&mut self, body_id: hir::BodyId) {
+        // Method calls and inferred types only show up in the typeck results.
+        let typeck_results = self.tcx.typeck_body(body_id);
+        for (_, res) in typeck_results.type_dependent_defs().iter() {
+            if let Ok((_, def_id)) = *res {
+                self.record(def_id);
+            }
+        }
+        for (_, &ty) in typeck_results.node_types().iter() {
+            self.record_ty(ty);
+        }
+        intravisit::walk_body(self, self.tcx.hir().body(body_id));
+    }
+
+    fn visit_path(&mut self, path: &'tcx hir::Path<'tcx>, _: hir::HirId) {
+        if let Some(def_id) = path.res.opt_def_id() {
+            self.record(def_id);
+        }
+        intravisit::walk_path(self, path);
+    }
+}
+
+/// Returns the items of other crates that are used by the local crate, sorted by
+/// their `DefPathHash`. This is only computed with `-Z unused-pub-items`, to be
+/// stored in the crate metadata.
+fn used_upstream_items(tcx: TyCtxt<'_>, cnum: CrateNum) -> Option<&[DefId]> {
+    assert_eq!(cnum, LOCAL_CRATE);
+    if !tcx.sess.opts.debugging_opts.unused_pub_items {
+        return None;
+    }
+    let mut collector = UpstreamUseCollector { tcx, used: Default::default() };
+    tcx.hir().walk_toplevel_module(&mut collector);
+    let mut used: Vec<_> = collector.used.into_iter().collect();
+    used.sort_by_cached_key(|&def_id| tcx.def_path_hash(def_id));
+    Some(tcx.arena.alloc_from_iter(used))
+}
+
+pub fn provide(providers: &mut Providers) {
+    *providers = Providers { used_upstream_items, ..*providers };
+}
+
+/// Reports the public items of upstream crates that are never used by any crate in
+/// the crate graph, with `-Z unused-pub-items`.
+///
+/// Only crates that were themselves compiled with `-Z unused-pub-items` take part,
+/// both as users and as crates whose items are checked. As only the final artifact
+/// sees the whole crate graph, this only does anything for crate types that are
+/// linked, not for libraries that other crates may still use.
+pub fn check_unused_pub_items(tcx: TyCtxt<'_>) {
+    if !tcx.sess.opts.debugging_opts.unused_pub_items {
+        return;
+    }
+    if !tcx.sess.crate_types().iter().any(|crate_type| {
+        matches!(crate_type, CrateType::Executable | CrateType::Staticlib | CrateType::Cdylib)
+    }) {
+        return;
+    }
+
+    let mut used = FxHashSet::default();
+    let mut checked_crates = vec![];
+    for &cnum in iter::once(&LOCAL_CRATE).chain(tcx.crates(())) {
+        if let Some(items) = tcx.used_upstream_items(cnum) {
+            used.extend(items.iter().copied());
+            if cnum != LOCAL_CRATE {
+                checked_crates.push(cnum);
+            }
+        }
+    }
+
+    for cnum in checked_crates {
+        let mut unused = vec![];
+        let mut seen = FxHashSet::default();
+        let mut modules = vec![cnum.as_def_id()];
+        while let Some(module) = modules.pop() {
+            for child in tcx.item_children(module) {
+                let (kind, def_id) = match child.res {
+                    Res::Def(kind, def_id) => (kind, def_id),
+                    _ => continue,
+                };
+                // Re-exports of items from other crates are checked in their own crate.
+                if child.vis != ty::Visibility::Public
+                    || def_id.krate != cnum
+                    || !seen.insert(def_id)
+                {
+                    continue;
+                }
+                match kind {
+                    DefKind::Mod => modules.push(def_id),
+                    DefKind::Struct
+                    | DefKind::Union
+                    | DefKind::Enum
+                    | DefKind::Trait
+                    | DefKind::TraitAlias
+                    | DefKind::TyAlias
+                    | DefKind::ForeignTy
+                    | DefKind::Fn
+                    | DefKind::Const
+                    | DefKind::Static => {
+                        if !used.contains(&def_id) {
+                            unused.push((kind, def_id));
+                        }
+                    }
+                    // Macro uses are gone by the time the HIR is built, and constructors
+                    // are covered by their struct.
+                    _ => {}
+                }
+            }
+        }
+
+        unused.sort_by_cached_key(|&(_, def_id)| tcx.def_path_str(def_id));
+        // The items belong to other crates, so the lint level is the one of the crate that is
+        // being linked.
+        for (kind, def_id) in unused {
+            let span = tcx.def_span(def_id);
+            tcx.struct_span_lint_hir(lint::builtin::UNUSED_PUB_ITEMS, CRATE_HIR_ID, span, |lint| {
+                lint.build(&format!(
+                    "public {} `{}` is never used outside of `{}`",
+                    kind.descr(def_id),
+                    tcx.def_path_str(def_id),
+                    tcx.crate_name(cnum),
+                ))
+                .note("no crate in this crate graph built with `-Z unused-pub-items` uses it")
+                .emit();
+            });
+        }
+    }
+}
@@ -1,17 +1,17 @@
-use rustc_ast::Attribute;
+use rustc_ast::{Attribute, LitKind, NestedMetaItem};
 use rustc_hir as hir;
 use rustc_hir::def_id::LocalDefId;
 use rustc_hir::itemlikevisit::ItemLikeVisitor;
 use rustc_hir::ItemKind;
 use rustc_middle::ty::layout::{HasParamEnv, HasTyCtxt, LayoutError, LayoutOfHelpers, TyAndLayout};
-use rustc_middle::ty::{ParamEnv, Ty, TyCtxt};
-use rustc_span::symbol::sym;
+use rustc_middle::ty::{self, ParamEnv, Ty, TyCtxt};
+use rustc_span::symbol::{sym, Symbol};
 use rustc_span::Span;
 use rustc_target::abi::{HasDataLayout, TargetDataLayout};
 
 pub fn test_layout(tcx: TyCtxt<'_>) {
-    if tcx.features().rustc_attrs {
-        // if the `rustc_attrs` feature is not enabled, don't bother testing layout
+    if tcx.features().rustc_attrs || tcx.features().assert_layout {
+        // if neither `rustc_attrs` nor `assert_layout` is enabled, don't bother testing layout
         tcx.hir().visit_all_item_likes(&mut LayoutTest { tcx });
     }
 }
@@ -30,6 +30,8 @@ fn visit_item(&mut self, item: &'tcx hir::Item<'tcx>) {
                 for attr in self.tcx.get_attrs(item.def_id.to_def_id()).iter() {
                     if attr.has_name(sym::rustc_layout) {
                         self.dump_layout_of(item.def_id, item, attr);
+                    } else if attr.has_name(sym::assert_layout) {
+                        self.assert_layout_of(item.def_id, item, attr);
                     }
                 }
             }
@@ -109,6 +111,146 @@ fn dump_layout_of(&self, item_def_id: LocalDefId, item: &hir::Item<'tcx>, attr:
     }
 }
 
+impl LayoutTest<'tcx> {
+    /// Checks the layout of the type of `item` against its `#[assert_layout(..)]` attribute.
+    fn assert_layout_of(&self, item_def_id: LocalDefId, item: &hir::Item<'tcx>, attr: &Attribute) {
+        let tcx = self.tcx;
+        let param_env = tcx.param_env(item_def_id);
+        let ty = tcx.type_of(item_def_id);
+        let layout = match tcx.layout_of(param_env.and(ty)) {
+            Ok(layout) => layout,
+            Err(layout_error) => {
+                tcx.sess.span_err(
+                    item.span,
+                    &format!("cannot assert the layout of `{}`: {}", ty, layout_error),
+                );
+                return;
+            }
+        };
+
+        // The names of the fields of structs, unions and tuples, in source order.
+        let field_names: Vec<Symbol> = match *ty.kind() {
+            ty::Adt(adt, _) if !adt.is_enum() => {
+                adt.non_enum_variant().fields.iter().map(|field| field.ident.name).collect()
+            }
+            ty::Tuple(fields) => (0..fields.len()).map(Symbol::integer).collect(),
+            _ => vec![],
+        };
+        let niche = layout.largest_niche.filter(|niche| niche.available(&tcx) > 0);
+
+        // The assertions that don't hold, with what the layout has instead.
+        let mut mismatches = vec![];
+        for meta_item in attr.meta_item_list().unwrap_or_default() {
+            match meta_item.name_or_empty() {
+                sym::size => {
+                    let size = layout.size.bytes();
+                    if self.int_value(&meta_item).map_or(false, |expected| expected != size) {
+                        mismatches.push((meta_item.span(), format!("the size is {} bytes", size)));
+                    }
+                }
+                sym::align => {
+                    let align = layout.align.abi.bytes();
+                    if self.int_value(&meta_item).map_or(false, |expected| expected != align) {
+                        let label = format!("the alignment is {} bytes", align);
+                        mismatches.push((meta_item.span(), label));
+                    }
+                }
+                sym::offset => {
+                    let fields = meta_item.meta_item_list().unwrap_or_default();
+                    if fields.is_empty() {
+                        tcx.sess.span_err(meta_item.span(), "expected a list of field offsets");
+                    }
+                    for field in fields {
+                        let name = field.name_or_empty();
+                        let index = match field_names.iter().position(|&other| other == name) {
+                            Some(index) => index,
+                            None => {
+                                tcx.sess.span_err(
+                                    field.span(),
+                                    &format!("`{}` has no field named `{}`", ty, name),
+                                );
+                                continue;
+                            }
+                        };
+                        let offset = layout.fields.offset(index).bytes();
+                        if self.int_value(field).map_or(false, |expected| expected != offset) {
+                            let label = format!("`{}` is at offset {}", name, offset);
+                            mismatches.push((field.span(), label));
+                        }
+                    }
+                }
+                sym::niche => match assertion_value(&meta_item) {
+                    Some(&LitKind::Bool(expected)) if expected != niche.is_some() => {
+                        let label = if expected { "there is no niche" } else { "there is a niche" };
+                        mismatches.push((meta_item.span(), label.to_string()));
+                    }
+                    Some(LitKind::Bool(_)) => {}
+                    _ => {
+                        tcx.sess.span_err(meta_item.span(), "expected `niche = true|false`");
+                    }
+                },
+                name => {
+                    tcx.sess.span_err(
+                        meta_item.span(),
+                        &format!("unrecognized layout assertion `{}`", name),
+                    );
+                }
+            }
+        }
+        if mismatches.is_empty() {
+            return;
+        }
+
+        let mut err = tcx.sess.struct_span_err(
+            item.span,
+            &format!("the layout of `{}` doesn't match its assertions", ty),
+        );
+        for (span, label) in mismatches {
+            err.span_label(span, label);
+        }
+        err.note(&format!(
+            "`{}` has a size of {} bytes and an alignment of {} bytes",
+            ty,
+            layout.size.bytes(),
+            layout.align.abi.bytes()
+        ));
+        if !field_names.is_empty() {
+            let offsets: Vec<_> = field_names
+                .iter()
+                .enumerate()
+                .map(|(i, name)| format!("`{}` at {}", name, layout.fields.offset(i).bytes()))
+                .collect();
+            err.note(&format!("its fields are at offsets: {}", offsets.join(", ")));
+        }
+        match niche {
+            Some(niche) => err.note(&format!(
+                "its largest niche has {} invalid values at offset {}",
+                niche.available(&tcx),
+                niche.offset.bytes()
+            )),
+            None => err.note("it has no niche"),
+        };
+        err.emit();
+    }
+
+    /// Returns the integer of a `name = N` assertion, or reports an error.
+    fn int_value(&self, meta_item: &NestedMetaItem) -> Option<u64> {
+        match assertion_value(meta_item) {
+            Some(&LitKind::Int(value, _)) => Some(value as u64),
+            _ => {
+                let msg = format!("expected `{} = N`", meta_item.name_or_empty());
+                self.tcx.sess.span_err(meta_item.span(), &msg);
+                None
+            }
+        }
+    }
+}
+
+/// Returns the value of a `name = value` assertion.
+fn assertion_value(meta_item: &NestedMetaItem) -> Option<&LitKind> {
+    meta_item.meta_item()?.name_value_literal().map(|lit| &lit.kind)
+}
+
 struct UnwrapLayoutCx<'tcx> {
     tcx: TyCtxt<'tcx>,
     param_env: ParamEnv<'tcx>,
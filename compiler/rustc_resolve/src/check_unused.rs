@@ -16,9 +16,10 @@
 //
 //  - `calc_unused_spans` then walks over all the `use` items marked in the
 //    previous step to collect the spans associated with the `NodeId`s and to
-//    calculate the spans that can be removed by rustfix; This is done in a
+//    calculate the edits that rustfix should apply; This is done in a
 //    separate step to be able to collapse the adjacent spans that rustfix
-//    will remove
+//    will remove, and to rewrite nested groups that end up with a single
+//    import (`a::{b, c}` becomes `a::c` if `b` is unused)
 //
 //  - `check_crate` finally emits the diagnostics based on the data generated
 //    in the last step
@@ -35,6 +36,8 @@
 use rustc_middle::ty;
 use rustc_session::lint::builtin::{MACRO_USE_EXTERN_CRATE, UNUSED_IMPORTS};
 use rustc_session::lint::BuiltinLintDiagnostics;
+use rustc_span::source_map::SourceMap;
+use rustc_span::symbol::kw;
 use rustc_span::{MultiSpan, Span, DUMMY_SP};
 
 struct UnusedImport<'a> {
@@ -136,10 +139,74 @@ enum UnusedSpanResult {
     Used,
     FlatUnused(Span, Span),
     NestedFullUnused(Vec<Span>, Span),
-    NestedPartialUnused(Vec<Span>, Vec<Span>),
+    NestedPartialUnused(Vec<Span>, Vec<(Span, String)>),
+}
+
+/// Returns whether nothing imported by `use_tree` is used.
+fn is_fully_unused(
+    unused_import: &UnusedImport<'_>,
+    use_tree: &ast::UseTree,
+    use_tree_id: ast::NodeId,
+) -> bool {
+    match use_tree.kind {
+        ast::UseTreeKind::Simple(..) | ast::UseTreeKind::Glob => {
+            unused_import.unused.contains(&use_tree_id)
+        }
+        ast::UseTreeKind::Nested(ref nested) => nested
+            .iter()
+            .all(|(use_tree, use_tree_id)| is_fully_unused(unused_import, use_tree, *use_tree_id)),
+    }
+}
+
+/// Renders `use_tree` with its unused imports left out, collapsing the braces of
+/// nested groups that are left with a single import. Returns `None` if the source
+/// of some part of the tree isn't available.
+fn render_used_tree(
+    source_map: &SourceMap,
+    unused_import: &UnusedImport<'_>,
+    use_tree: &ast::UseTree,
+) -> Option<String> {
+    let nested = match use_tree.kind {
+        ast::UseTreeKind::Simple(..) | ast::UseTreeKind::Glob => {
+            return source_map.span_to_snippet(use_tree.span).ok();
+        }
+        ast::UseTreeKind::Nested(ref nested) => nested,
+    };
+    let prefix = if use_tree.prefix.segments.is_empty() {
+        String::new()
+    } else {
+        source_map.span_to_snippet(use_tree.prefix.span).ok()?
+    };
+    let used: Vec<_> = nested
+        .iter()
+        .filter(|(use_tree, use_tree_id)| !is_fully_unused(unused_import, use_tree, *use_tree_id))
+        .collect();
+    let rendered = used
+        .iter()
+        .map(|(use_tree, _)| render_used_tree(source_map, unused_import, use_tree))
+        .collect::<Option<Vec<_>>>()?;
+
+    // `a::{self}` only imports the module, while `a` would import everything named `a`,
+    // so the braces have to stay in that case.
+    let is_self_import = |use_tree: &ast::UseTree| match use_tree.kind {
+        ast::UseTreeKind::Simple(..) => matches!(
+            &use_tree.prefix.segments[..],
+            [segment] if segment.ident.name == kw::SelfLower
+        ),
+        _ => false,
+    };
+    Some(match (&used[..], &rendered[..]) {
+        ([_], [single]) if prefix.is_empty() => single.clone(),
+        ([(single_tree, _)], [single]) if !is_self_import(single_tree) => {
+            format!("{}::{}", prefix, single)
+        }
+        _ if prefix.is_empty() => format!("{{{}}}", rendered.join(", ")),
+        _ => format!("{}::{{{}}}", prefix, rendered.join(", ")),
+    })
 }
 
 fn calc_unused_spans(
+    source_map: &SourceMap,
     unused_import: &UnusedImport<'_>,
     use_tree: &ast::UseTree,
     use_tree_id: ast::NodeId,
@@ -166,12 +233,20 @@ fn calc_unused_spans(
 
             let mut unused_spans = Vec::new();
             let mut to_remove = Vec::new();
+            let mut nested_fixes = Vec::new();
+            let mut used_count = 0;
             let mut all_nested_unused = true;
             let mut previous_unused = false;
             for (pos, (use_tree, use_tree_id)) in nested.iter().enumerate() {
-                let remove = match calc_unused_spans(unused_import, use_tree, *use_tree_id) {
+                let remove = match calc_unused_spans(
+                    source_map,
+                    unused_import,
+                    use_tree,
+                    *use_tree_id,
+                ) {
                     UnusedSpanResult::Used => {
                         all_nested_unused = false;
+                        used_count += 1;
                         None
                     }
                     UnusedSpanResult::FlatUnused(span, remove) => {
@@ -182,10 +257,11 @@ fn calc_unused_spans(
                         unused_spans.append(&mut spans);
                         Some(remove)
                     }
-                    UnusedSpanResult::NestedPartialUnused(mut spans, mut to_remove_extra) => {
+                    UnusedSpanResult::NestedPartialUnused(mut spans, mut fixes) => {
                         all_nested_unused = false;
+                        used_count += 1;
                         unused_spans.append(&mut spans);
-                        to_remove.append(&mut to_remove_extra);
+                        nested_fixes.append(&mut fixes);
                         None
                     }
                 };
@@ -217,7 +293,23 @@ fn calc_unused_spans(
             } else if all_nested_unused {
                 UnusedSpanResult::NestedFullUnused(unused_spans, full_span)
             } else {
-                UnusedSpanResult::NestedPartialUnused(unused_spans, to_remove)
+                // If a single import is left, removing the others would leave it in a
+                // pair of braces of its own, so rewrite this whole group instead. This is
+                // also done if a nested group is rewritten, so that the rewrite doesn't
+                // touch the spans that are removed around it.
+                let rewrites_nested = nested_fixes.iter().any(|(_, s)| !s.is_empty());
+                if used_count == 1 || rewrites_nested {
+                    if let Some(rendered) = render_used_tree(source_map, unused_import, use_tree) {
+                        return UnusedSpanResult::NestedPartialUnused(
+                            unused_spans,
+                            vec![(use_tree.span, rendered)],
+                        );
+                    }
+                }
+                let mut fixes: Vec<_> =
+                    to_remove.into_iter().map(|span| (span, String::new())).collect();
+                fixes.append(&mut nested_fixes);
+                UnusedSpanResult::NestedPartialUnused(unused_spans, fixes)
             }
         }
     }
@@ -266,9 +358,16 @@ impl Resolver<'_> {
         };
         visit::walk_crate(&mut visitor, krate);
 
+        let session = visitor.r.session;
+        let source_map = session.source_map();
         for unused in visitor.unused_imports.values() {
             let mut fixes = Vec::new();
-            let mut spans = match calc_unused_spans(unused, unused.use_tree, unused.use_tree_id) {
+            let mut spans = match calc_unused_spans(
+                source_map,
+                unused,
+                unused.use_tree,
+                unused.use_tree_id,
+            ) {
                 UnusedSpanResult::Used => continue,
                 UnusedSpanResult::FlatUnused(span, remove) => {
                     fixes.push((remove, String::new()));
@@ -278,10 +377,8 @@ impl Resolver<'_> {
                     fixes.push((remove, String::new()));
                     spans
                 }
-                UnusedSpanResult::NestedPartialUnused(spans, remove) => {
-                    for fix in &remove {
-                        fixes.push((*fix, String::new()));
-                    }
+                UnusedSpanResult::NestedPartialUnused(spans, mut partial_fixes) => {
+                    fixes.append(&mut partial_fixes);
                     spans
                 }
             };
@@ -291,7 +388,7 @@ impl Resolver<'_> {
             let ms = MultiSpan::from_spans(spans.clone());
             let mut span_snippets = spans
                 .iter()
-                .filter_map(|s| match visitor.r.session.source_map().span_to_snippet(*s) {
+                .filter_map(|s| match source_map.span_to_snippet(*s) {
                     Ok(s) => Some(format!("`{}`", s)),
                     _ => None,
                 })
@@ -1,4 +1,5 @@
 use std::cmp::Reverse;
+use std::collections::VecDeque;
 use std::ptr;
 
 use rustc_ast::{self as ast, Path};
@@ -66,6 +67,8 @@ impl TypoSuggestion {
     pub descr: &'static str,
     pub path: Path,
     pub accessible: bool,
+    /// Whether the item is marked as unstable, only known for items of other crates.
+    pub unstable: bool,
 }
 
 /// Adjust the impl span so that just the `impl` keyword is taken by removing
@@ -794,11 +797,18 @@ fn lookup_import_candidates_from_module<FilterFn>(
     {
         let mut candidates = Vec::new();
         let mut seen_modules = FxHashSet::default();
-        let mut worklist = vec![(start_module, Vec::<ast::PathSegment>::new(), true)];
-        let mut worklist_via_import = vec![];
+        let mut worklist =
+            VecDeque::from(vec![(start_module, Vec::<ast::PathSegment>::new(), true)]);
+        let mut worklist_via_import = VecDeque::new();
+        // When ranking suggestions by path length, walk the modules breadth-first so that each
+        // module, and so every item in it, is first reached through its shortest path.
+        let shortest_paths = self.session.opts.debugging_opts.suggest_imports_from_all_crates;
+        let pop = |worklist: &mut VecDeque<_>| {
+            if shortest_paths { worklist.pop_front() } else { worklist.pop_back() }
+        };
 
-        while let Some((in_module, path_segments, accessible)) = match worklist.pop() {
-            None => worklist_via_import.pop(),
+        while let Some((in_module, path_segments, accessible)) = match pop(&mut worklist) {
+            None => pop(&mut worklist_via_import),
             Some(x) => Some(x),
         } {
             let in_module_is_extern = !in_module.def_id().is_local();
@@ -862,12 +872,30 @@ fn lookup_import_candidates_from_module<FilterFn>(
                             }
                         }
 
-                        if candidates.iter().all(|v: &ImportSuggestion| v.did != did) {
+                        if let Some(candidate) =
+                            candidates.iter_mut().find(|v: &&mut ImportSuggestion| v.did == did)
+                        {
+                            // Re-exports can make the same item reachable through a shorter path.
+                            if shortest_paths
+                                && candidate.accessible == child_accessible
+                                && path.segments.len() < candidate.path.segments.len()
+                            {
+                                candidate.path = path;
+                            }
+                        } else {
+                            let unstable = match did {
+                                Some(did) if !did.is_local() => this
+                                    .cstore()
+                                    .stability_untracked(did)
+                                    .map_or(false, |stability| stability.level.is_unstable()),
+                                _ => false,
+                            };
                             candidates.push(ImportSuggestion {
                                 did,
                                 descr: res.descr(),
                                 path,
                                 accessible: child_accessible,
+                                unstable,
                             });
                         }
                     }
@@ -886,7 +914,7 @@ fn lookup_import_candidates_from_module<FilterFn>(
                         // add the module to the lookup
                         if seen_modules.insert(module.def_id()) {
                             if via_import { &mut worklist_via_import } else { &mut worklist }
-                                .push((module, path_segments, child_accessible));
+                                .push_back((module, path_segments, child_accessible));
                         }
                     }
                 }
@@ -908,6 +936,9 @@ fn lookup_import_candidates_from_module<FilterFn>(
     ///
     /// N.B., the method does not look into imports, but this is not a problem,
     /// since we report the definitions (thus, the de-aliased imports).
+    ///
+    /// With `-Z suggest-imports-from-all-crates`, every item is reported with the shortest
+    /// path it can be found at, which may go through re-exports.
     crate fn lookup_import_candidates<FilterFn>(
         &mut self,
         lookup_ident: Ident,
@@ -1734,6 +1765,15 @@ fn find_span_immediately_after_crate_name(
         return;
     }
 
+    let rank_candidates = session.opts.debugging_opts.suggest_imports_from_all_crates;
+    let mut candidates: Vec<_> = candidates.iter().collect();
+    if rank_candidates {
+        // Suggest stable items before unstable ones, and shorter paths before longer ones.
+        candidates.sort_by_cached_key(|c| {
+            (c.unstable, c.path.segments.len(), path_names_to_string(&c.path))
+        });
+    }
+
     let mut accessible_path_strings: Vec<(String, &str, Option<DefId>)> = Vec::new();
     let mut inaccessible_path_strings: Vec<(String, &str, Option<DefId>)> = Vec::new();
 
@@ -1745,7 +1785,9 @@ fn find_span_immediately_after_crate_name(
     // we want consistent results across executions, but candidates are produced
     // by iterating through a hash map, so make sure they are ordered:
     for path_strings in [&mut accessible_path_strings, &mut inaccessible_path_strings] {
-        path_strings.sort_by(|a, b| a.0.cmp(&b.0));
+        if !rank_candidates {
+            path_strings.sort_by(|a, b| a.0.cmp(&b.0));
+        }
         let core_path_strings =
             path_strings.drain_filter(|p| p.0.starts_with("core::")).collect::<Vec<_>>();
         path_strings.extend(core_path_strings);
@@ -1502,6 +1502,7 @@ fn find_module(&mut self, def_id: DefId) -> Option<(Module<'a>, ImportSuggestion
                                 descr: "module",
                                 path,
                                 accessible: true,
+                                unstable: false,
                             },
                         ));
                     } else {
@@ -1578,9 +1578,29 @@ pub fn resolve_crate(&mut self, krate: &Crate) {
             self.session.time("resolve_check_unused", || self.check_unused(krate));
             self.session.time("resolve_report_errors", || self.report_errors(krate));
             self.session.time("resolve_postprocess", || self.crate_loader.postprocess(krate));
+            if self.session.opts.debugging_opts.suggest_imports_from_all_crates {
+                self.session.time("resolve_load_extern_crates", || self.load_extern_crates());
+            }
         });
     }
 
+    /// Loads every crate passed with `--extern` that is not used, so that the items they define,
+    /// traits in particular, can be suggested for import later on. The crates are only loaded
+    /// for diagnostics, so they do not become dependencies of the current crate.
+    fn load_extern_crates(&mut self) {
+        let names: Vec<_> = self
+            .session
+            .opts
+            .externs
+            .iter()
+            .filter(|(_, entry)| entry.add_prelude)
+            .map(|(name, _)| Symbol::intern(name))
+            .collect();
+        for name in names {
+            self.crate_loader.load_crate_for_diagnostics(name);
+        }
+    }
+
     pub fn traits_in_scope(
         &mut self,
         current_trait: Option<Module<'a>>,
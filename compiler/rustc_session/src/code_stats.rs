@@ -1,7 +1,8 @@
-use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::sync::Lock;
 use rustc_target::abi::{Align, Size};
 use std::cmp::{self, Ordering};
+use std::time::{Duration, Instant};
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct VariantInfo {
@@ -45,9 +46,27 @@ pub struct TypeSizeInfo {
     pub variants: Vec<VariantInfo>,
 }
 
+#[derive(Debug)]
+pub struct ConstEvalCost {
+    pub item: String,
+    /// The number of statements and terminators that were evaluated.
+    pub steps: u64,
+    /// The total size of the allocations that were made.
+    pub allocated_bytes: u64,
+    /// The time spent evaluating the item, without the time spent evaluating the other
+    /// items it uses.
+    pub time: Duration,
+}
+
 #[derive(Default)]
 pub struct CodeStats {
     type_sizes: Lock<FxHashSet<TypeSizeInfo>>,
+    const_eval_costs: Lock<Vec<ConstEvalCost>>,
+    /// The start of the evaluations in progress, and the time spent in the evaluations
+    /// nested in them.
+    const_eval_stack: Lock<Vec<(Instant, Duration)>>,
+    /// The instances that were replaced by each polymorphized instance.
+    polymorphized_instances: Lock<FxHashMap<String, FxHashSet<String>>>,
 }
 
 impl CodeStats {
@@ -190,4 +209,102 @@ pub fn print_type_sizes(&self) {
             }
         }
     }
+
+    /// Starts timing the evaluation of a constant or static. The evaluations that are
+    /// started before the matching `record_const_eval_cost` are nested in this one.
+    pub fn start_const_eval(&self) {
+        self.const_eval_stack.borrow_mut().push((Instant::now(), Duration::ZERO));
+    }
+
+    pub fn record_const_eval_cost(&self, item: String, steps: u64, allocated_bytes: u64) {
+        let mut stack = self.const_eval_stack.borrow_mut();
+        let (start, nested) = stack.pop().expect("no const evaluation was started");
+        let elapsed = start.elapsed();
+        if let Some((_, parent_nested)) = stack.last_mut() {
+            *parent_nested += elapsed;
+        }
+        let time = elapsed.saturating_sub(nested);
+        self.const_eval_costs.borrow_mut().push(ConstEvalCost {
+            item,
+            steps,
+            allocated_bytes,
+            time,
+        });
+    }
+
+    pub fn print_const_eval_profile(&self) {
+        let mut costs = self.const_eval_costs.borrow_mut();
+
+        // Primary sort: slow-to-fast.
+        // Secondary sort: steps (large-to-small), then item (dictionary order)
+        costs.sort_by(|cost1, cost2| {
+            cost2
+                .time
+                .cmp(&cost1.time)
+                .then_with(|| cost2.steps.cmp(&cost1.steps))
+                .then_with(|| cost1.item.cmp(&cost2.item))
+        });
+
+        let (mut time, mut steps, mut allocated_bytes) = (Duration::ZERO, 0, 0);
+        for cost in costs.iter() {
+            println!(
+                "const-eval-profile item `{}`: {:.3}ms, {} steps, {} bytes allocated",
+                cost.item,
+                cost.time.as_secs_f64() * 1000.0,
+                cost.steps,
+                cost.allocated_bytes
+            );
+            time += cost.time;
+            steps += cost.steps;
+            allocated_bytes += cost.allocated_bytes;
+        }
+        println!(
+            "const-eval-profile total: {} items, {:.3}ms, {} steps, {} bytes allocated",
+            costs.len(),
+            time.as_secs_f64() * 1000.0,
+            steps,
+            allocated_bytes
+        );
+    }
+
+    /// Records that the mono item collector replaced `instance` by the polymorphized instance
+    /// `polymorphized`, which is shared with the other instances it replaced.
+    pub fn record_polymorphized_instance(&self, polymorphized: String, instance: String) {
+        self.polymorphized_instances
+            .borrow_mut()
+            .entry(polymorphized)
+            .or_default()
+            .insert(instance);
+    }
+
+    pub fn print_polymorphization_stats(&self) {
+        let polymorphized_instances = self.polymorphized_instances.borrow();
+        let mut sorted: Vec<_> = polymorphized_instances
+            .iter()
+            .map(|(polymorphized, instances)| (polymorphized, instances.len()))
+            .collect();
+
+        // Primary sort: most-to-least replaced instances.
+        // Secondary sort: polymorphized instance (dictionary order)
+        sorted.sort_by(|(polymorphized1, count1), (polymorphized2, count2)| {
+            count2.cmp(count1).then_with(|| polymorphized1.cmp(polymorphized2))
+        });
+
+        let mut instances = 0;
+        for &(polymorphized, count) in &sorted {
+            // Only the polymorphized instance is codegened, instead of all the ones it replaced.
+            println!(
+                "polymorphization-stats instance `{}`: shared by {} instances, {} saved",
+                polymorphized,
+                count,
+                count - 1
+            );
+            instances += count;
+        }
+        println!(
+            "polymorphization-stats total: {} polymorphized instances, {} instances saved",
+            sorted.len(),
+            instances - sorted.len()
+        );
+    }
 }
@@ -216,6 +216,7 @@ pub enum OutputType {
     Object,
     Exe,
     DepInfo,
+    LtoBitcodeBundle,
 }
 
 impl_stable_hash_via_hash!(OutputType);
@@ -223,7 +224,10 @@ pub enum OutputType {
 impl OutputType {
     fn is_compatible_with_codegen_units_and_single_output_file(&self) -> bool {
         match *self {
-            OutputType::Exe | OutputType::DepInfo | OutputType::Metadata => true,
+            OutputType::Exe
+            | OutputType::DepInfo
+            | OutputType::Metadata
+            | OutputType::LtoBitcodeBundle => true,
             OutputType::Bitcode
             | OutputType::Assembly
             | OutputType::LlvmAssembly
@@ -242,6 +246,7 @@ fn shorthand(&self) -> &'static str {
             OutputType::Metadata => "metadata",
             OutputType::Exe => "link",
             OutputType::DepInfo => "dep-info",
+            OutputType::LtoBitcodeBundle => "lto-bitcode-bundle",
         }
     }
 
@@ -255,13 +260,14 @@ fn from_shorthand(shorthand: &str) -> Option<Self> {
             "metadata" => OutputType::Metadata,
             "link" => OutputType::Exe,
             "dep-info" => OutputType::DepInfo,
+            "lto-bitcode-bundle" => OutputType::LtoBitcodeBundle,
             _ => return None,
         })
     }
 
     fn shorthands_display() -> String {
         format!(
-            "`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`",
+            "`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`",
             OutputType::Bitcode.shorthand(),
             OutputType::Assembly.shorthand(),
             OutputType::LlvmAssembly.shorthand(),
@@ -270,6 +276,7 @@ fn shorthands_display() -> String {
             OutputType::Metadata.shorthand(),
             OutputType::Exe.shorthand(),
             OutputType::DepInfo.shorthand(),
+            OutputType::LtoBitcodeBundle.shorthand(),
         )
     }
 
@@ -283,6 +290,7 @@ pub fn extension(&self) -> &'static str {
             OutputType::Metadata => "rmeta",
             OutputType::DepInfo => "d",
             OutputType::Exe => "",
+            OutputType::LtoBitcodeBundle => "lto.a",
         }
     }
 }
@@ -365,7 +373,8 @@ pub fn should_codegen(&self) -> bool {
             | OutputType::LlvmAssembly
             | OutputType::Mir
             | OutputType::Object
-            | OutputType::Exe => true,
+            | OutputType::Exe
+            | OutputType::LtoBitcodeBundle => true,
             OutputType::Metadata | OutputType::DepInfo => false,
         })
     }
@@ -379,7 +388,8 @@ pub fn should_link(&self) -> bool {
             | OutputType::Mir
             | OutputType::Metadata
             | OutputType::Object
-            | OutputType::DepInfo => false,
+            | OutputType::DepInfo
+            | OutputType::LtoBitcodeBundle => false,
             OutputType::Exe => true,
         })
     }
@@ -1386,10 +1396,28 @@ fn parse_output_types(
                         ),
                     )
                 });
+                if output_type == OutputType::LtoBitcodeBundle && !debugging_opts.unstable_options {
+                    early_error(
+                        error_format,
+                        "`--emit=lto-bitcode-bundle` is unstable and requires \
+                         `-Z unstable-options`",
+                    );
+                }
                 output_types.insert(output_type, path);
             }
         }
     };
+    if output_types.contains_key(&OutputType::LtoBitcodeBundle)
+        && (output_types.contains_key(&OutputType::Exe)
+            || output_types.contains_key(&OutputType::Object))
+    {
+        // The bundle needs bitcode that is only prepared for LTO, which would
+        // leave the linked artifacts and object files without ThinLTO.
+        early_error(
+            error_format,
+            "`--emit=lto-bitcode-bundle` can't be combined with `--emit=link` or `--emit=obj`",
+        );
+    }
     if output_types.is_empty() {
         output_types.insert(OutputType::Exe, None);
     }
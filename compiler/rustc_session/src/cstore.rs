@@ -38,6 +38,10 @@ pub fn paths(&self) -> impl Iterator<Item = &PathBuf> {
 #[derive(Encodable, Decodable, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Debug)]
 #[derive(HashStable_Generic)]
 pub enum CrateDepKind {
+    /// Not a dependency at all: a crate that is only loaded so that its items can be suggested
+    /// in diagnostics (see `-Z suggest-imports-from-all-crates`). It is neither linked nor
+    /// recorded in the metadata, and is left out of `tcx.crates(())`.
+    DiagnosticsOnly,
     /// A dependency that is only used for its macros.
     MacrosOnly,
     /// A dependency that is always injected into the dependency list and so
@@ -53,7 +57,9 @@ impl CrateDepKind {
     pub fn macros_only(self) -> bool {
         match self {
             CrateDepKind::MacrosOnly => true,
-            CrateDepKind::Implicit | CrateDepKind::Explicit => false,
+            CrateDepKind::DiagnosticsOnly | CrateDepKind::Implicit | CrateDepKind::Explicit => {
+                false
+            }
         }
     }
 }
@@ -1030,6 +1030,9 @@ mod parse {
         (default: no)"),
     borrowck: String = ("migrate".to_string(), parse_string, [UNTRACKED],
         "select which borrowck is used (`mir` or `migrate`) (default: `migrate`)"),
+    cgu_partitioning_profile: Option<PathBuf> = (None, parse_opt_pathbuf, [TRACKED],
+        "group the hot functions listed in the given file into their own codegen unit \
+        (implies `-Z cgu-partitioning-strategy=profile-guided`)"),
     cgu_partitioning_strategy: Option<String> = (None, parse_opt_string, [TRACKED],
         "the codegen unit partitioning strategy to use"),
     chalk: bool = (false, parse_bool, [TRACKED],
@@ -1038,6 +1041,9 @@ mod parse {
         "the backend to use"),
     combine_cgu: bool = (false, parse_bool, [TRACKED],
         "combine CGUs into a single one"),
+    const_eval_profile: bool = (false, parse_bool, [UNTRACKED],
+        "print the steps, allocations and time of the evaluation of each constant and static \
+        (default: no)"),
     crate_attr: Vec<String> = (Vec::new(), parse_string_push, [TRACKED],
         "inject the given attribute in the crate"),
     debug_info_for_profiling: bool = (false, parse_bool, [TRACKED],
@@ -1084,10 +1090,19 @@ mod parse {
         computed `block` spans (one span encompassing a block's terminator and \
         all statements). If `-Z instrument-coverage` is also enabled, create \
         an additional `.html` file showing the computed coverage spans."),
+    dump_trait_selection: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "print the candidates considered for the trait obligations of the item with the given \
+        path, why they were rejected, and the nested obligations, as an indented tree"),
     emit_future_incompat_report: bool = (false, parse_bool, [UNTRACKED],
         "emits a future-incompatibility report for lints (RFC 2834)"),
     emit_stack_sizes: bool = (false, parse_bool, [UNTRACKED],
         "emit a section containing stack size metadata (default: no)"),
+    emit_stack_sizes_report: bool = (false, parse_bool, [TRACKED],
+        "write the frame size of every function and the worst-case stack usage of every \
+        entry point to `<crate>.stack-sizes` (implies `-Z emit-stack-sizes`) (default: no)"),
+    export_map: Option<PathBuf> = (None, parse_opt_pathbuf, [TRACKED],
+        "export exactly the symbols listed in this file from a `cdylib`, optionally with \
+        symbol versions (`symbol@@VERSION`)"),
     fewer_names: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "reduce memory use by retaining fewer names within compilation artifacts (LLVM-IR) \
         (default: no)"),
@@ -1148,12 +1163,18 @@ mod parse {
         "link native libraries in the linker invocation (default: yes)"),
     link_only: bool = (false, parse_bool, [TRACKED],
         "link the `.rlink` file generated by `-Z no-link` (default: no)"),
+    lint_config: Option<PathBuf> = (None, parse_opt_pathbuf, [TRACKED_NO_CRATE_HASH],
+        "read lint levels from the given TOML file, in addition to the `-A`/`-W`/`-D`/`-F` \
+        flags given on the command line (default: none)"),
     llvm_plugins: Vec<String> = (Vec::new(), parse_list, [TRACKED],
         "a list LLVM plugins to enable (space separated)"),
     llvm_time_trace: bool = (false, parse_bool, [UNTRACKED],
         "generate JSON tracing data file from LLVM data (default: no)"),
     ls: bool = (false, parse_bool, [UNTRACKED],
         "list the symbols defined by a library crate (default: no)"),
+    lto_bitcode_bundle_clang: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "check that the LLVM version of this `clang` matches the one of rustc when \
+        emitting an LTO bitcode bundle"),
     macro_backtrace: bool = (false, parse_bool, [UNTRACKED],
         "show macro backtraces (default: no)"),
     merge_functions: Option<MergeFunctions> = (None, parse_merge_functions, [TRACKED],
@@ -1213,8 +1234,9 @@ mod parse {
         (default: PLT is disabled if full relro is enabled)"),
     polonius: bool = (false, parse_bool, [TRACKED],
         "enable polonius-based borrow-checker (default: no)"),
-    polymorphize: bool = (false, parse_bool, [TRACKED],
-          "perform polymorphization analysis"),
+    polymorphize: Option<bool> = (None, parse_opt_bool, [TRACKED],
+        "share the instances of closures and functions between the generic arguments that \
+        don't affect their code (default: yes if optimizing)"),
     pre_link_arg: (/* redirected to pre_link_args */) = ((), parse_string_push, [UNTRACKED],
         "a single extra argument to prepend the linker invocation (can be used several times)"),
     pre_link_args: Vec<String> = (Vec::new(), parse_list, [UNTRACKED],
@@ -1229,8 +1251,14 @@ mod parse {
         "print the arguments passed to the linker (default: no)"),
     print_llvm_passes: bool = (false, parse_bool, [UNTRACKED],
         "print the LLVM optimization passes being run (default: no)"),
+    print_mono_item_groups: bool = (false, parse_bool, [UNTRACKED],
+        "print the monomorphized instances of each generic function, with their generic \
+        arguments, size estimate and the crates that use them (default: no)"),
     print_mono_items: Option<String> = (None, parse_opt_string, [UNTRACKED],
         "print the result of the monomorphization collection pass"),
+    print_polymorphization_stats: bool = (false, parse_bool, [UNTRACKED],
+        "print how many instances polymorphization saved for each closure and function \
+        (default: no)"),
     print_type_sizes: bool = (false, parse_bool, [UNTRACKED],
         "print layout information for each type encountered (default: no)"),
     proc_macro_backtrace: bool = (false, parse_bool, [UNTRACKED],
@@ -1300,6 +1328,10 @@ mod parse {
     split_dwarf_inlining: bool = (true, parse_bool, [UNTRACKED],
         "provide minimal debug info in the object/executable to facilitate online \
          symbolication/stack traces in the absence of .dwo/.dwp files when using Split DWARF"),
+    suggest_imports_from_all_crates: bool = (false, parse_bool, [UNTRACKED],
+        "load every `--extern` crate so that import and trait suggestions can come from crates \
+        that are not used yet, and rank import suggestions by path length and stability \
+        (default: no)"),
     symbol_mangling_version: Option<SymbolManglingVersion> = (None,
         parse_symbol_mangling_version, [TRACKED],
         "which mangling version to use for symbol names ('legacy' (default) or 'v0')"),
@@ -1355,6 +1387,9 @@ mod parse {
         "enable unsound and buggy MIR optimizations (default: no)"),
     unstable_options: bool = (false, parse_bool, [UNTRACKED],
         "adds unstable command line options to rustc interface (default: no)"),
+    unused_pub_items: bool = (false, parse_bool, [TRACKED],
+        "record the items of other crates used by this crate in its metadata, and when \
+        linking, warn about public items of such crates that no crate uses (default: no)"),
     use_ctors_section: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "use legacy .ctors section for initializers rather than .init_array"),
     validate_mir: bool = (false, parse_bool, [UNTRACKED],
@@ -1363,6 +1398,9 @@ mod parse {
         "in general, enable more debug printouts (default: no)"),
     verify_llvm_ir: bool = (false, parse_bool, [TRACKED],
         "verify LLVM IR (default: no)"),
+    verify_reproducible: bool = (false, parse_bool, [UNTRACKED],
+        "compile the crate a second time under different conditions and check that all \
+        output artifacts are identical (default: no)"),
     wasi_exec_model: Option<WasiExecModel> = (None, parse_wasi_exec_model, [TRACKED],
         "whether to build a wasi command or reactor"),
 
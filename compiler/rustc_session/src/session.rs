@@ -566,6 +566,15 @@ pub fn mir_opt_level(&self) -> usize {
             .unwrap_or_else(|| if self.opts.optimize != config::OptLevel::No { 2 } else { 1 })
     }
 
+    /// Whether the instances of closures and functions are shared between the generic arguments
+    /// they don't use. Enabled by default when optimizing.
+    pub fn polymorphize(&self) -> bool {
+        self.opts
+            .debugging_opts
+            .polymorphize
+            .unwrap_or_else(|| self.opts.optimize != config::OptLevel::No)
+    }
+
     /// Gets the features enabled for the current compilation session.
     /// DO NOT USE THIS METHOD if there is a TyCtxt available, as it circumvents
     /// dependency tracking. Use tcx.features() instead.
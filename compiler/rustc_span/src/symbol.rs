@@ -329,6 +329,7 @@
         asm,
         assert,
         assert_inhabited,
+        assert_layout,
         assert_macro,
         assert_receiver_is_total_eq,
         assert_uninit_valid,
@@ -873,6 +874,7 @@
         new,
         new_unchecked,
         next,
+        niche,
         nll,
         no,
         no_builtins,
@@ -298,26 +298,24 @@ fn report_selection_error(
                             })
                             .unwrap_or_default();
 
-                        let OnUnimplementedNote { message, label, note, enclosing_scope } =
+                        let OnUnimplementedNote { message, label, notes, enclosing_scope } =
                             self.on_unimplemented_note(trait_ref, &obligation);
                         let have_alt_message = message.is_some() || label.is_some();
                         let is_try_conversion = self.is_try_conversion(span, trait_ref.def_id());
                         let is_unsize =
                             { Some(trait_ref.def_id()) == self.tcx.lang_items().unsize_trait() };
-                        let (message, note) = if is_try_conversion {
+                        let (message, notes) = if is_try_conversion {
                             (
                                 Some(format!(
                                     "`?` couldn't convert the error to `{}`",
                                     trait_ref.skip_binder().self_ty(),
                                 )),
-                                Some(
-                                    "the question mark operation (`?`) implicitly performs a \
+                                vec!["the question mark operation (`?`) implicitly performs a \
                                         conversion on the error value using the `From` trait"
-                                        .to_owned(),
-                                ),
+                                    .to_owned()],
                             )
                         } else {
-                            (message, note)
+                            (message, notes)
                         };
 
                         let mut err = struct_span_err!(
@@ -407,8 +405,8 @@ fn report_selection_error(
                         if let Some((msg, span)) = type_def {
                             err.span_label(span, &msg);
                         }
-                        if let Some(ref s) = note {
-                            // If it has a custom `#[rustc_on_unimplemented]` note, let's display it
+                        for s in &notes {
+                            // If it has custom `#[rustc_on_unimplemented]` notes, let's display them
                             err.note(s.as_str());
                         }
                         if let Some(ref s) = enclosing_scope {
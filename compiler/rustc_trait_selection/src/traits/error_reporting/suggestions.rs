@@ -1639,9 +1639,11 @@ fn note_obligation_cause_for_async_await(
         // Special case the primary error message when send or sync is the trait that was
         // not implemented.
         let hir = self.tcx.hir();
-        let trait_explanation = if let Some(name @ (sym::Send | sym::Sync)) =
-            self.tcx.get_diagnostic_name(trait_ref.def_id)
-        {
+        let send_or_sync = match self.tcx.get_diagnostic_name(trait_ref.def_id) {
+            name @ Some(sym::Send | sym::Sync) => name,
+            _ => None,
+        };
+        let trait_explanation = if let Some(name) = send_or_sync {
             let (trait_name, trait_verb) =
                 if name == sym::Send { ("`Send`", "sent") } else { ("`Sync`", "shared") };
 
@@ -1685,107 +1687,83 @@ fn note_obligation_cause_for_async_await(
             format!("does not implement `{}`", trait_ref.print_only_trait_path())
         };
 
-        let mut explain_yield =
-            |interior_span: Span, yield_span: Span, scope_span: Option<Span>| {
-                let mut span = MultiSpan::from_span(yield_span);
-                if let Ok(snippet) = source_map.span_to_snippet(interior_span) {
-                    // #70935: If snippet contains newlines, display "the value" instead
-                    // so that we do not emit complex diagnostics.
-                    let snippet = &format!("`{}`", snippet);
-                    let snippet = if snippet.contains('\n') { "the value" } else { snippet };
-                    // The multispan can be complex here, like:
-                    // note: future is not `Send` as this value is used across an await
-                    //   --> $DIR/issue-70935-complex-spans.rs:13:9
-                    //    |
-                    // LL |            baz(|| async{
-                    //    |  __________^___-
-                    //    | | _________|
-                    //    | ||
-                    // LL | ||             foo(tx.clone());
-                    // LL | ||         }).await;
-                    //    | ||         -      ^- value is later dropped here
-                    //    | ||_________|______|
-                    //    | |__________|      await occurs here, with value maybe used later
-                    //    |            has type `closure` which is not `Send`
-                    //
-                    // So, detect it and separate into some notes, like:
-                    //
-                    // note: future is not `Send` as this value is used across an await
-                    //   --> $DIR/issue-70935-complex-spans.rs:13:9
-                    //    |
-                    // LL | /         baz(|| async{
-                    // LL | |             foo(tx.clone());
-                    // LL | |         }).await;
-                    //    | |________________^ first, await occurs here, with the value maybe used later...
-                    // note: the value is later dropped here
-                    //   --> $DIR/issue-70935-complex-spans.rs:15:17
-                    //    |
-                    // LL |         }).await;
-                    //    |                 ^
-                    //
-                    // If available, use the scope span to annotate the drop location.
-                    if let Some(scope_span) = scope_span {
-                        let scope_span = source_map.end_point(scope_span);
-                        let is_overlapped =
-                            yield_span.overlaps(scope_span) || yield_span.overlaps(interior_span);
-                        if is_overlapped {
-                            span.push_span_label(
-                                yield_span,
-                                format!(
-                                    "first, {} occurs here, with {} maybe used later...",
-                                    await_or_yield, snippet
-                                ),
+        let explain_yield = |err: &mut DiagnosticBuilder<'_>,
+                             interior_span: Span,
+                             yield_span: Span,
+                             scope_span: Option<Span>,
+                             ty: Ty<'tcx>| {
+            let mut span = MultiSpan::from_span(yield_span);
+            if let Ok(snippet) = source_map.span_to_snippet(interior_span) {
+                // #70935: If snippet contains newlines, display "the value" instead
+                // so that we do not emit complex diagnostics.
+                let snippet = &format!("`{}`", snippet);
+                let snippet = if snippet.contains('\n') { "the value" } else { snippet };
+                // The multispan can be complex here, like:
+                // note: future is not `Send` as this value is used across an await
+                //   --> $DIR/issue-70935-complex-spans.rs:13:9
+                //    |
+                // LL |            baz(|| async{
+                //    |  __________^___-
+                //    | | _________|
+                //    | ||
+                // LL | ||             foo(tx.clone());
+                // LL | ||         }).await;
+                //    | ||         -      ^- value is later dropped here
+                //    | ||_________|______|
+                //    | |__________|      await occurs here, with value maybe used later
+                //    |            has type `closure` which is not `Send`
+                //
+                // So, detect it and separate into some notes, like:
+                //
+                // note: future is not `Send` as this value is used across an await
+                //   --> $DIR/issue-70935-complex-spans.rs:13:9
+                //    |
+                // LL | /         baz(|| async{
+                // LL | |             foo(tx.clone());
+                // LL | |         }).await;
+                //    | |________________^ first, await occurs here, with the value maybe used later...
+                // note: the value is later dropped here
+                //   --> $DIR/issue-70935-complex-spans.rs:15:17
+                //    |
+                // LL |         }).await;
+                //    |                 ^
+                //
+                // If available, use the scope span to annotate the drop location.
+                if let Some(scope_span) = scope_span {
+                    let scope_span = source_map.end_point(scope_span);
+                    let is_overlapped =
+                        yield_span.overlaps(scope_span) || yield_span.overlaps(interior_span);
+                    if is_overlapped {
+                        span.push_span_label(
+                            yield_span,
+                            format!(
+                                "first, {} occurs here, with {} maybe used later...",
+                                await_or_yield, snippet
+                            ),
+                        );
+                        err.span_note(
+                            span,
+                            &format!(
+                                "{} {} as this value is used across {}",
+                                future_or_generator, trait_explanation, an_await_or_yield
+                            ),
+                        );
+                        if source_map.is_multiline(interior_span) {
+                            err.span_note(
+                                scope_span,
+                                &format!("{} is later dropped here", snippet),
                             );
                             err.span_note(
-                                span,
-                                &format!(
-                                    "{} {} as this value is used across {}",
-                                    future_or_generator, trait_explanation, an_await_or_yield
-                                ),
+                                interior_span,
+                                &format!("this has type `{}` which {}", ty, trait_explanation),
                             );
-                            if source_map.is_multiline(interior_span) {
-                                err.span_note(
-                                    scope_span,
-                                    &format!("{} is later dropped here", snippet),
-                                );
-                                err.span_note(
-                                    interior_span,
-                                    &format!(
-                                        "this has type `{}` which {}",
-                                        target_ty, trait_explanation
-                                    ),
-                                );
-                            } else {
-                                let mut span = MultiSpan::from_span(scope_span);
-                                span.push_span_label(
-                                    interior_span,
-                                    format!("has type `{}` which {}", target_ty, trait_explanation),
-                                );
-                                err.span_note(span, &format!("{} is later dropped here", snippet));
-                            }
                         } else {
-                            span.push_span_label(
-                                yield_span,
-                                format!(
-                                    "{} occurs here, with {} maybe used later",
-                                    await_or_yield, snippet
-                                ),
-                            );
-                            span.push_span_label(
-                                scope_span,
-                                format!("{} is later dropped here", snippet),
-                            );
+                            let mut span = MultiSpan::from_span(scope_span);
                             span.push_span_label(
                                 interior_span,
-                                format!("has type `{}` which {}", target_ty, trait_explanation),
-                            );
-                            err.span_note(
-                                span,
-                                &format!(
-                                    "{} {} as this value is used across {}",
-                                    future_or_generator, trait_explanation, an_await_or_yield
-                                ),
+                                format!("has type `{}` which {}", ty, trait_explanation),
                             );
+                            err.span_note(span, &format!("{} is later dropped here", snippet));
                         }
                     } else {
                         span.push_span_label(
@@ -1795,9 +1773,13 @@ fn note_obligation_cause_for_async_await(
                                 await_or_yield, snippet
                             ),
                         );
+                        span.push_span_label(
+                            scope_span,
+                            format!("{} is later dropped here", snippet),
+                        );
                         span.push_span_label(
                             interior_span,
-                            format!("has type `{}` which {}", target_ty, trait_explanation),
+                            format!("has type `{}` which {}", ty, trait_explanation),
                         );
                         err.span_note(
                             span,
@@ -1807,8 +1789,37 @@ fn note_obligation_cause_for_async_await(
                             ),
                         );
                     }
+                } else {
+                    span.push_span_label(
+                        yield_span,
+                        format!(
+                            "{} occurs here, with {} maybe used later",
+                            await_or_yield, snippet
+                        ),
+                    );
+                    span.push_span_label(
+                        interior_span,
+                        format!("has type `{}` which {}", ty, trait_explanation),
+                    );
+                    err.span_note(
+                        span,
+                        &format!(
+                            "{} {} as this value is used across {}",
+                            future_or_generator, trait_explanation, an_await_or_yield
+                        ),
+                    );
                 }
-            };
+            }
+            if let Some(body) = inner_generator_body {
+                suggest_block_for_held_value(
+                    self.tcx,
+                    err,
+                    body.id(),
+                    interior_span,
+                    await_or_yield,
+                );
+            }
+        };
         match interior_or_upvar_span {
             GeneratorInteriorOrUpvar::Interior(interior_span) => {
                 if let Some((scope_span, yield_span, expr, from_awaited_ty)) = interior_extra_info {
@@ -1835,7 +1846,7 @@ fn note_obligation_cause_for_async_await(
                             "note_obligation_cause_for_async_await generator_interior_types: {:#?}",
                             typeck_results.generator_interior_types
                         );
-                        explain_yield(interior_span, yield_span, scope_span);
+                        explain_yield(err, interior_span, yield_span, scope_span, target_ty);
                     }
 
                     if let Some(expr_id) = expr {
@@ -1882,6 +1893,40 @@ fn note_obligation_cause_for_async_await(
                             }
                         }
                     }
+
+                    // Every other value of the same type held across a yield makes the
+                    // generator `!Send` or `!Sync` as well, so explain them too. The values of
+                    // other types are explained under the errors for their own types.
+                    if send_or_sync.is_some() {
+                        let interior_types = &typeck_results.generator_interior_values;
+                        let target_ty_erased = self.tcx.erase_regions(target_ty);
+                        let mut explained = vec![interior_span];
+                        for cause in interior_types.as_ref().skip_binder() {
+                            let in_generator = inner_generator_body
+                                .map_or(false, |body| body.value.span.contains(cause.yield_span));
+                            // Skip the values introduced by `.await`, the moves of bindings, and
+                            // the values that contain or are part of a value explained already.
+                            if !in_generator
+                                || cause.span.is_desugaring(DesugaringKind::Await)
+                                || cause.expr.map_or(false, |id| is_local_path(hir.expect_expr(id)))
+                                || explained.iter().any(|span| span.overlaps(cause.span))
+                            {
+                                continue;
+                            }
+                            let ty = interior_types.rebind(cause.ty);
+                            let ty = self.tcx.erase_regions(self.tcx.erase_late_bound_regions(ty));
+                            if ty::TyS::same_type(ty, target_ty_erased) {
+                                explain_yield(
+                                    err,
+                                    cause.span,
+                                    cause.yield_span,
+                                    cause.scope_span,
+                                    ty,
+                                );
+                                explained.push(cause.span);
+                            }
+                        }
+                    }
                 }
             }
             GeneratorInteriorOrUpvar::Upvar(upvar_span) => {
@@ -2628,6 +2673,169 @@ fn visit_expr(&mut self, ex: &'v hir::Expr<'v>) {
     }
 }
 
+/// Whether `expr` is a path to a local binding, i.e. a use of a binding rather than a new value.
+fn is_local_path(expr: &hir::Expr<'_>) -> bool {
+    matches!(
+        expr.kind,
+        hir::ExprKind::Path(hir::QPath::Resolved(
+            None,
+            hir::Path { res: hir::def::Res::Local(_), .. }
+        ))
+    )
+}
+
+/// Suggests to move the statements from the `let` statement that binds the value at `value_span`
+/// to the next yield into a block, to drop the value before the yield, if none of the bindings of
+/// these statements are used after them.
+fn suggest_block_for_held_value<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    err: &mut DiagnosticBuilder<'_>,
+    body_id: hir::BodyId,
+    value_span: Span,
+    await_or_yield: &str,
+) {
+    let mut visitor = HeldValueScopeVisitor { tcx, value_span, scope: None };
+    visitor.visit_body(tcx.hir().body(body_id));
+    if let Some((name, stmts)) = visitor.scope {
+        let span = stmts[0].span.to(stmts[stmts.len() - 1].span);
+        err.span_help(
+            span,
+            &format!(
+                "`{}` isn't used after the {}: consider moving {} into a block, so that it's \
+                 dropped before the {}",
+                name,
+                await_or_yield,
+                if stmts.len() == 1 { "this statement" } else { "these statements" },
+                await_or_yield
+            ),
+        );
+    }
+}
+
+/// Finds the statements that bind a value held across a yield and that can be moved into a block
+/// to drop the value before the yield, see `suggest_block_for_held_value`.
+struct HeldValueScopeVisitor<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    value_span: Span,
+    /// The name of the value and the statements to move into a block.
+    scope: Option<(Ident, &'tcx [hir::Stmt<'tcx>])>,
+}
+
+impl<'tcx> HeldValueScopeVisitor<'tcx> {
+    fn scope_in_block(
+        &self,
+        block: &'tcx hir::Block<'tcx>,
+    ) -> Option<(Ident, &'tcx [hir::Stmt<'tcx>])> {
+        let start = block.stmts.iter().position(|stmt| match stmt.kind {
+            hir::StmtKind::Local(local) => local.pat.span.contains(self.value_span),
+            _ => false,
+        })?;
+        let contains_yield = |stmt: &'tcx hir::Stmt<'tcx>| {
+            let mut visitor = YieldsVisitor::default();
+            visitor.visit_stmt(stmt);
+            visitor.found
+        };
+        let end = match block.stmts[start + 1..].iter().position(contains_yield) {
+            Some(end) => start + 1 + end,
+            None => {
+                let mut visitor = YieldsVisitor::default();
+                visitor.visit_expr(block.expr?);
+                if !visitor.found {
+                    return None;
+                }
+                block.stmts.len()
+            }
+        };
+
+        let stmts = &block.stmts[start..end];
+        let mut name = None;
+        let mut uses = LocalUsesVisitor { tcx: self.tcx, locals: vec![], used: false };
+        for stmt in stmts {
+            match stmt.kind {
+                hir::StmtKind::Local(local) => local.pat.each_binding(|_, hir_id, span, ident| {
+                    if span == self.value_span {
+                        name = Some(ident);
+                    }
+                    uses.locals.push(hir_id);
+                }),
+                // Items can't be moved into a block without hiding them from the rest of it.
+                hir::StmtKind::Item(_) => return None,
+                hir::StmtKind::Expr(_) | hir::StmtKind::Semi(_) => {}
+            }
+        }
+        for stmt in &block.stmts[end..] {
+            uses.visit_stmt(stmt);
+        }
+        if let Some(expr) = block.expr {
+            uses.visit_expr(expr);
+        }
+        if uses.used || stmts.iter().any(|stmt| stmt.span.from_expansion()) {
+            return None;
+        }
+        Some((name?, stmts))
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for HeldValueScopeVisitor<'tcx> {
+    type Map = hir::intravisit::ErasedMap<'tcx>;
+
+    fn nested_visit_map(&mut self) -> hir::intravisit::NestedVisitorMap<Self::Map> {
+        hir::intravisit::NestedVisitorMap::None
+    }
+
+    fn visit_block(&mut self, block: &'tcx hir::Block<'tcx>) {
+        if self.scope.is_none() {
+            self.scope = self.scope_in_block(block);
+        }
+        hir::intravisit::walk_block(self, block)
+    }
+}
+
+/// Whether there's a yield or an await in the visited nodes.
+#[derive(Default)]
+struct YieldsVisitor {
+    found: bool,
+}
+
+impl<'v> Visitor<'v> for YieldsVisitor {
+    type Map = hir::intravisit::ErasedMap<'v>;
+
+    fn nested_visit_map(&mut self) -> hir::intravisit::NestedVisitorMap<Self::Map> {
+        hir::intravisit::NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, ex: &'v hir::Expr<'v>) {
+        if let hir::ExprKind::Yield(..) = ex.kind {
+            self.found = true;
+        }
+        hir::intravisit::walk_expr(self, ex)
+    }
+}
+
+/// Whether any of `locals` is used in the visited nodes, including the closures that capture them.
+struct LocalUsesVisitor<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    locals: Vec<hir::HirId>,
+    used: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for LocalUsesVisitor<'tcx> {
+    type Map = rustc_middle::hir::map::Map<'tcx>;
+
+    fn nested_visit_map(&mut self) -> hir::intravisit::NestedVisitorMap<Self::Map> {
+        hir::intravisit::NestedVisitorMap::OnlyBodies(self.tcx.hir())
+    }
+
+    fn visit_expr(&mut self, ex: &'tcx hir::Expr<'tcx>) {
+        if let hir::ExprKind::Path(hir::QPath::Resolved(None, path)) = ex.kind {
+            if let hir::def::Res::Local(hir_id) = path.res {
+                self.used |= self.locals.contains(&hir_id);
+            }
+        }
+        hir::intravisit::walk_expr(self, ex)
+    }
+}
+
 pub trait NextTypeParamName {
     fn next_type_param_name(&self, name: Option<&str>) -> String;
 }
@@ -52,6 +52,7 @@
 pub use self::engine::TraitEngineExt;
 pub use self::fulfill::{FulfillmentContext, PendingPredicateObligation};
 pub use self::object_safety::astconv_object_safety_violations;
+pub use self::object_safety::gat_requires_sized_self;
 pub use self::object_safety::is_vtable_safe_method;
 pub use self::object_safety::MethodViolationCode;
 pub use self::object_safety::ObjectSafetyViolation;
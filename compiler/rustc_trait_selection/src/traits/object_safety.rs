@@ -137,6 +137,7 @@ fn object_safety_violations_for_trait(
             .in_definition_order()
             .filter(|item| item.kind == ty::AssocKind::Type)
             .filter(|item| !tcx.generics_of(item.def_id).params.is_empty())
+            .filter(|item| !generics_require_sized_self(tcx, item.def_id))
             .map(|item| ObjectSafetyViolation::GAT(item.ident.name, item.ident.span)),
     );
 
@@ -319,6 +320,13 @@ fn trait_has_sized_self(tcx: TyCtxt<'_>, trait_def_id: DefId) -> bool {
     generics_require_sized_self(tcx, trait_def_id)
 }
 
+/// Whether the generic associated type `def_id` requires `Self: Sized`, which leaves it out of
+/// the trait objects of its trait: they don't have to specify it, and only the methods that
+/// require `Self: Sized` themselves can use it.
+pub fn gat_requires_sized_self(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    !tcx.generics_of(def_id).params.is_empty() && generics_require_sized_self(tcx, def_id)
+}
+
 fn generics_require_sized_self(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
     let sized_def_id = match tcx.lang_items().sized_trait() {
         Some(def_id) => def_id,
@@ -17,7 +17,7 @@ pub struct OnUnimplementedDirective {
     pub subcommands: Vec<OnUnimplementedDirective>,
     pub message: Option<OnUnimplementedFormatString>,
     pub label: Option<OnUnimplementedFormatString>,
-    pub note: Option<OnUnimplementedFormatString>,
+    pub notes: Vec<OnUnimplementedFormatString>,
     pub enclosing_scope: Option<OnUnimplementedFormatString>,
 }
 
@@ -25,7 +25,7 @@ pub struct OnUnimplementedDirective {
 pub struct OnUnimplementedNote {
     pub message: Option<String>,
     pub label: Option<String>,
-    pub note: Option<String>,
+    pub notes: Vec<String>,
     pub enclosing_scope: Option<String>,
 }
 
@@ -49,6 +49,7 @@ impl<'tcx> OnUnimplementedDirective {
     fn parse(
         tcx: TyCtxt<'tcx>,
         trait_def_id: DefId,
+        attr_name: Symbol,
         items: &[NestedMetaItem],
         span: Span,
         is_root: bool,
@@ -65,7 +66,7 @@ fn parse(
                     parse_error(
                         tcx,
                         span,
-                        "empty `on`-clause in `#[rustc_on_unimplemented]`",
+                        &format!("empty `on`-clause in `#[{}]`", attr_name),
                         "empty on-clause here",
                         None,
                     )
@@ -75,7 +76,7 @@ fn parse(
                     parse_error(
                         tcx,
                         span,
-                        "invalid `on`-clause in `#[rustc_on_unimplemented]`",
+                        &format!("invalid `on`-clause in `#[{}]`", attr_name),
                         "invalid on-clause here",
                         None,
                     )
@@ -86,12 +87,13 @@ fn parse(
 
         let mut message = None;
         let mut label = None;
-        let mut note = None;
+        let mut notes = vec![];
         let mut enclosing_scope = None;
         let mut subcommands = vec![];
 
         let parse_value = |value_str| {
-            OnUnimplementedFormatString::try_parse(tcx, trait_def_id, value_str, span).map(Some)
+            OnUnimplementedFormatString::try_parse(tcx, trait_def_id, attr_name, value_str, span)
+                .map(Some)
         };
 
         for item in item_iter {
@@ -105,25 +107,29 @@ fn parse(
                     label = parse_value(label_)?;
                     continue;
                 }
-            } else if item.has_name(sym::note) && note.is_none() {
-                if let Some(note_) = item.value_str() {
-                    note = parse_value(note_)?;
+            } else if item.has_name(sym::note) {
+                if let Some(note) = item.value_str() {
+                    notes.extend(parse_value(note)?);
                     continue;
                 }
-            } else if item.has_name(sym::enclosing_scope) && enclosing_scope.is_none() {
+            } else if item.has_name(sym::enclosing_scope)
+                && enclosing_scope.is_none()
+                && attr_name == sym::rustc_on_unimplemented
+            {
                 if let Some(enclosing_scope_) = item.value_str() {
                     enclosing_scope = parse_value(enclosing_scope_)?;
                     continue;
                 }
             } else if item.has_name(sym::on)
                 && is_root
+                && attr_name == sym::rustc_on_unimplemented
                 && message.is_none()
                 && label.is_none()
-                && note.is_none()
+                && notes.is_empty()
             {
                 if let Some(items) = item.meta_item_list() {
                     if let Ok(subcommand) =
-                        Self::parse(tcx, trait_def_id, &items, item.span(), false)
+                        Self::parse(tcx, trait_def_id, attr_name, &items, item.span(), false)
                     {
                         subcommands.push(subcommand);
                     } else {
@@ -139,7 +145,7 @@ fn parse(
                 item.span(),
                 "this attribute must have a valid value",
                 "expected value here",
-                Some(r#"eg `#[rustc_on_unimplemented(message="foo")]`"#),
+                Some(&format!(r#"eg `#[{}(message="foo")]`"#, attr_name)),
             );
         }
 
@@ -151,7 +157,7 @@ fn parse(
                 subcommands,
                 message,
                 label,
-                note,
+                notes,
                 enclosing_scope,
             })
         }
@@ -164,14 +170,18 @@ pub fn of_item(
     ) -> Result<Option<Self>, ErrorReported> {
         let attrs = tcx.get_attrs(impl_def_id);
 
+        // The internal attribute takes precedence over the one for user traits.
         let attr = if let Some(item) = tcx.sess.find_by_name(&attrs, sym::rustc_on_unimplemented) {
             item
+        } else if let Some(item) = tcx.sess.find_by_name(&attrs, sym::on_unimplemented) {
+            item
         } else {
             return Ok(None);
         };
+        let attr_name = attr.name_or_empty();
 
         let result = if let Some(items) = attr.meta_item_list() {
-            Self::parse(tcx, trait_def_id, &items, attr.span, true).map(Some)
+            Self::parse(tcx, trait_def_id, attr_name, &items, attr.span, true).map(Some)
         } else if let Some(value) = attr.value_str() {
             Ok(Some(OnUnimplementedDirective {
                 condition: None,
@@ -180,10 +190,11 @@ pub fn of_item(
                 label: Some(OnUnimplementedFormatString::try_parse(
                     tcx,
                     trait_def_id,
+                    attr_name,
                     value,
                     attr.span,
                 )?),
-                note: None,
+                notes: vec![],
                 enclosing_scope: None,
             }))
         } else {
@@ -201,7 +212,7 @@ pub fn evaluate(
     ) -> OnUnimplementedNote {
         let mut message = None;
         let mut label = None;
-        let mut note = None;
+        let mut notes = &[][..];
         let mut enclosing_scope = None;
         info!("evaluate({:?}, trait_ref={:?}, options={:?})", self, trait_ref, options);
 
@@ -230,8 +241,8 @@ pub fn evaluate(
                 label = Some(label_.clone());
             }
 
-            if let Some(ref note_) = command.note {
-                note = Some(note_.clone());
+            if !command.notes.is_empty() {
+                notes = &command.notes[..];
             }
 
             if let Some(ref enclosing_scope_) = command.enclosing_scope {
@@ -244,7 +255,7 @@ pub fn evaluate(
         OnUnimplementedNote {
             label: label.map(|l| l.format(tcx, trait_ref, &options)),
             message: message.map(|m| m.format(tcx, trait_ref, &options)),
-            note: note.map(|n| n.format(tcx, trait_ref, &options)),
+            notes: notes.iter().map(|n| n.format(tcx, trait_ref, &options)).collect(),
             enclosing_scope: enclosing_scope.map(|e_s| e_s.format(tcx, trait_ref, &options)),
         }
     }
@@ -254,11 +265,12 @@ impl<'tcx> OnUnimplementedFormatString {
     fn try_parse(
         tcx: TyCtxt<'tcx>,
         trait_def_id: DefId,
+        attr_name: Symbol,
         from: Symbol,
         err_sp: Span,
     ) -> Result<Self, ErrorReported> {
         let result = OnUnimplementedFormatString(from);
-        result.verify(tcx, trait_def_id, err_sp)?;
+        result.verify(tcx, trait_def_id, attr_name, err_sp)?;
         Ok(result)
     }
 
@@ -266,9 +278,12 @@ fn verify(
         &self,
         tcx: TyCtxt<'tcx>,
         trait_def_id: DefId,
+        attr_name: Symbol,
         span: Span,
     ) -> Result<(), ErrorReported> {
         let name = tcx.item_name(trait_def_id);
+        // The parameters describing the obligation are only known to the internal attribute.
+        let internal = attr_name == sym::rustc_on_unimplemented;
         let generics = tcx.generics_of(trait_def_id);
         let s = self.0.as_str();
         let parser = Parser::new(&s, None, None, false, ParseMode::Format);
@@ -282,11 +297,11 @@ fn verify(
                     // `{ThisTraitsName}` is allowed
                     Position::ArgumentNamed(s) if s == name => (),
                     // `{from_method}` is allowed
-                    Position::ArgumentNamed(s) if s == sym::from_method => (),
+                    Position::ArgumentNamed(s) if internal && s == sym::from_method => (),
                     // `{from_desugaring}` is allowed
-                    Position::ArgumentNamed(s) if s == sym::from_desugaring => (),
+                    Position::ArgumentNamed(s) if internal && s == sym::from_desugaring => (),
                     // `{ItemContext}` is allowed
-                    Position::ArgumentNamed(s) if s == sym::ItemContext => (),
+                    Position::ArgumentNamed(s) if internal && s == sym::ItemContext => (),
                     // So is `{A}` if A is a type parameter
                     Position::ArgumentNamed(s) => {
                         match generics.params.iter().find(|param| param.name == s) {
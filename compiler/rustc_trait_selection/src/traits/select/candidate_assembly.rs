@@ -32,6 +32,12 @@ pub(super) fn candidate_from_obligation<'o>(
         &mut self,
         stack: &TraitObligationStack<'o, 'tcx>,
     ) -> SelectionResult<'tcx, SelectionCandidate<'tcx>> {
+        let dumped = self.is_dumped(stack.obligation);
+        if dumped {
+            let predicate = stack.obligation.predicate;
+            self.dump(stack.obligation, false, format_args!("select `{}`", predicate));
+        }
+
         // Watch out for overflow. This intentionally bypasses (and does
         // not update) the cache.
         self.check_recursion_limit(&stack.obligation, &stack.obligation)?;
@@ -48,6 +54,9 @@ pub(super) fn candidate_from_obligation<'o>(
             self.check_candidate_cache(stack.obligation.param_env, cache_fresh_trait_pred)
         {
             debug!(candidate = ?c, "CACHE HIT");
+            if dumped {
+                self.dump_selection_result(stack.obligation, &c, "cached: ");
+            }
             return c;
         }
 
@@ -61,6 +70,9 @@ pub(super) fn candidate_from_obligation<'o>(
             self.in_task(|this| this.candidate_from_obligation_no_cache(stack));
 
         debug!(?candidate, "CACHE MISS");
+        if dumped {
+            self.dump_selection_result(stack.obligation, &candidate, "");
+        }
         self.insert_candidate_cache(
             stack.obligation.param_env,
             cache_fresh_trait_pred,
@@ -118,12 +130,21 @@ fn candidate_from_obligation_no_cache<'o>(
 
         if candidate_set.ambiguous {
             debug!("candidate set contains ambig");
+            if self.is_dumped(stack.obligation) {
+                self.dump(stack.obligation, true, format_args!("ambiguous candidate set"));
+            }
             return Ok(None);
         }
 
         let mut candidates = candidate_set.vec;
 
         debug!(?stack, ?candidates, "assembled {} candidates", candidates.len());
+        if self.is_dumped(stack.obligation) {
+            for candidate in &candidates {
+                let candidate = self.describe_candidate(candidate);
+                self.dump(stack.obligation, true, format_args!("candidate {}", candidate));
+            }
+        }
 
         // At this point, we know that each of the entries in the
         // candidate set is *individually* applicable. Now we have to
@@ -177,15 +198,24 @@ fn candidate_from_obligation_no_cache<'o>(
         if candidates.len() > 1 {
             let mut i = 0;
             while i < candidates.len() {
-                let is_dup = (0..candidates.len()).filter(|&j| i != j).any(|j| {
+                let favored = (0..candidates.len()).filter(|&j| i != j).find(|&j| {
                     self.candidate_should_be_dropped_in_favor_of(
                         &candidates[i],
                         &candidates[j],
                         needs_infer,
                     )
                 });
-                if is_dup {
+                if let Some(j) = favored {
                     debug!(candidate = ?candidates[i], "Dropping candidate #{}/{}", i, candidates.len());
+                    if self.is_dumped(stack.obligation) {
+                        let victim = self.describe_candidate(&candidates[i].candidate);
+                        let other = self.describe_candidate(&candidates[j].candidate);
+                        self.dump(
+                            stack.obligation,
+                            true,
+                            format_args!("{} dropped in favor of {}", victim, other),
+                        );
+                    }
                     candidates.swap_remove(i);
                 } else {
                     debug!(candidate = ?candidates[i], "Retaining candidate #{}/{}", i, candidates.len());
@@ -0,0 +1,72 @@
+//! The trace of `-Z dump-trait-selection`.
+//!
+//! The selection of the trait obligations of the item named by the option is
+//! printed as a tree, indented by the recursion depth of the obligations: every
+//! obligation is followed by the candidates that were assembled for it, why the
+//! impls and candidates that don't apply were rejected, the result, and the
+//! nested obligations of the selected candidate.
+
+use rustc_infer::traits::{Obligation, SelectionError};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+
+use std::fmt;
+
+use crate::traits::SelectionResult;
+
+use super::SelectionCandidate::{self, *};
+use super::SelectionContext;
+
+impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
+    /// Whether the selection of `obligation` is traced, i.e. whether it belongs
+    /// to the item named by `-Z dump-trait-selection`.
+    pub(super) fn is_dumped<T>(&self, obligation: &Obligation<'tcx, T>) -> bool {
+        let path = match &self.tcx().sess.opts.debugging_opts.dump_trait_selection {
+            Some(path) => path,
+            None => return false,
+        };
+        let owner = obligation.cause.body_id.owner.to_def_id();
+        with_no_trimmed_paths(|| self.tcx().def_path_str(owner)) == *path
+    }
+
+    /// Prints an event of the selection of `obligation`, below the obligation
+    /// itself. Events at the depth of the obligation, like the obligation
+    /// itself, are printed with `nested: false`.
+    pub(super) fn dump<T>(
+        &self,
+        obligation: &Obligation<'tcx, T>,
+        nested: bool,
+        event: fmt::Arguments<'_>,
+    ) {
+        let indent = 2 * obligation.recursion_depth + if nested { 2 } else { 0 };
+        with_no_trimmed_paths(|| println!("{:indent$}{}", "", event, indent = indent));
+    }
+
+    /// Prints the result of the selection of `obligation`.
+    pub(super) fn dump_selection_result<T>(
+        &self,
+        obligation: &Obligation<'tcx, T>,
+        result: &SelectionResult<'tcx, SelectionCandidate<'tcx>>,
+        prefix: &str,
+    ) {
+        let result = match result {
+            Ok(Some(candidate)) => format!("selected {}", self.describe_candidate(candidate)),
+            Ok(None) => "ambiguous".to_string(),
+            Err(SelectionError::Unimplemented) => "unimplemented: no candidate applies".to_string(),
+            Err(err) => format!("error: {:?}", err),
+        };
+        self.dump(obligation, true, format_args!("{}{}", prefix, result));
+    }
+
+    pub(super) fn describe_candidate(&self, candidate: &SelectionCandidate<'tcx>) -> String {
+        with_no_trimmed_paths(|| match *candidate {
+            ImplCandidate(impl_def_id) => {
+                let trait_ref = self.tcx().impl_trait_ref(impl_def_id).unwrap();
+                format!("impl `{} for {}`", trait_ref.print_only_trait_path(), trait_ref.self_ty())
+            }
+            ParamCandidate(ref trait_ref) => {
+                format!("where clause `{}`", trait_ref.value.to_poly_trait_predicate())
+            }
+            ref candidate => format!("{:?}", candidate),
+        })
+    }
+}
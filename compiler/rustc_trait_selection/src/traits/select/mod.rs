@@ -53,6 +53,7 @@
 
 mod candidate_assembly;
 mod confirmation;
+mod dump;
 
 #[derive(Clone, Debug)]
 pub enum IntercrateAmbiguityCause {
@@ -378,7 +379,19 @@ pub fn select(
             Ok(Some(candidate)) => candidate,
         };
 
-        match self.confirm_candidate(obligation, candidate) {
+        let result = self.confirm_candidate(obligation, candidate);
+        if self.is_dumped(obligation) {
+            match &result {
+                Ok(candidate) => {
+                    for nested in candidate.borrow_nested_obligations() {
+                        self.dump(obligation, true, format_args!("nested `{}`", nested.predicate));
+                    }
+                }
+                Err(e) => self.dump(obligation, true, format_args!("confirmation failed: {:?}", e)),
+            }
+        }
+
+        match result {
             Err(SelectionError::Overflow) => {
                 assert!(self.query_mode == TraitQueryMode::Canonical);
                 Err(SelectionError::Overflow)
@@ -970,6 +983,10 @@ fn evaluate_candidate<'o>(
         }
 
         debug!(?result);
+        if self.is_dumped(stack.obligation) {
+            let candidate = self.describe_candidate(candidate);
+            self.dump(stack.obligation, true, format_args!("{}: {:?}", candidate, result));
+        }
         Ok(result)
     }
 
@@ -1056,6 +1073,9 @@ fn check_recursion_depth<T: Display + TypeFoldable<'tcx>>(
         error_obligation: &Obligation<'tcx, T>,
     ) -> Result<(), OverflowError> {
         if !self.infcx.tcx.recursion_limit().value_within_limit(depth) {
+            if self.is_dumped(error_obligation) {
+                self.dump(error_obligation, true, format_args!("overflow at depth {}", depth));
+            }
             match self.query_mode {
                 TraitQueryMode::Standard => {
                     if self.infcx.is_tainted_by_errors() {
@@ -2029,8 +2049,9 @@ fn match_impl(
 
         // Before we create the substitutions and everything, first
         // consider a "quick reject". This avoids creating more types
-        // and so forth that we need to.
-        if self.fast_reject_trait_refs(obligation, &impl_trait_ref) {
+        // and so forth that we need to. It's skipped when the selection
+        // is traced, to show the type error of the impls that don't apply.
+        if !self.is_dumped(obligation) && self.fast_reject_trait_refs(obligation, &impl_trait_ref) {
             return Err(());
         }
 
@@ -2067,7 +2088,17 @@ fn match_impl(
             .infcx
             .at(&cause, obligation.param_env)
             .eq(placeholder_obligation_trait_ref, impl_trait_ref)
-            .map_err(|e| debug!("match_impl: failed eq_trait_refs due to `{}`", e))?;
+            .map_err(|e| {
+                debug!("match_impl: failed eq_trait_refs due to `{}`", e);
+                if self.is_dumped(obligation) {
+                    let impl_candidate = self.describe_candidate(&ImplCandidate(impl_def_id));
+                    self.dump(
+                        obligation,
+                        true,
+                        format_args!("{} doesn't apply: {}", impl_candidate, e),
+                    );
+                }
+            })?;
         nested_obligations.extend(obligations);
 
         if !self.intercrate
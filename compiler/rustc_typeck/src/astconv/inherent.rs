@@ -0,0 +1,242 @@
+use crate::astconv::AstConv;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_errors::{struct_span_err, ErrorReported};
+use rustc_hir as hir;
+use rustc_hir::def_id::DefId;
+use rustc_middle::ty::error::TypeError;
+use rustc_middle::ty::fold::TypeFoldable;
+use rustc_middle::ty::relate::{self, Relate, RelateResult, TypeRelation};
+use rustc_middle::ty::subst::{GenericArg, GenericArgKind, InternalSubsts, Subst, SubstsRef};
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_span::Span;
+
+use std::collections::hash_map::Entry;
+
+impl<'o, 'tcx> dyn AstConv<'tcx> + 'o {
+    /// Lowers the path `qself_ty::Name` to the associated type `Name` of an inherent impl of the
+    /// ADT `adt_did`, i.e. to the type it is defined as, with the generic parameters of the impl
+    /// replaced by the generic arguments of `qself_ty`. Returns `None` if no inherent impl of the
+    /// ADT has an associated type with that name.
+    pub(crate) fn lookup_inherent_assoc_ty(
+        &self,
+        hir_ref_id: hir::HirId,
+        span: Span,
+        qself_ty: Ty<'tcx>,
+        adt_did: DefId,
+        assoc_segment: &hir::PathSegment<'_>,
+    ) -> Result<Option<(Ty<'tcx>, DefId)>, ErrorReported> {
+        let tcx = self.tcx();
+        let assoc_ident = assoc_segment.ident;
+
+        let candidates: Vec<_> = tcx
+            .inherent_impls(adt_did)
+            .iter()
+            .filter_map(|&impl_def_id| {
+                let (ident, def_scope) =
+                    tcx.adjust_ident_and_get_scope(assoc_ident, impl_def_id, hir_ref_id);
+                let item = tcx.associated_items(impl_def_id).in_definition_order().find(|i| {
+                    i.kind == ty::AssocKind::Type && i.ident.normalize_to_macros_2_0() == ident
+                })?;
+                Some((impl_def_id, item, def_scope))
+            })
+            .collect();
+        if candidates.is_empty() {
+            return Ok(None);
+        }
+
+        // Only the impls whose self type can be `qself_ty` and whose where clauses may hold for
+        // it apply, e.g. `impl Foo<u8>` applies to `Foo<u8>::Name` but `impl Foo<u16>` doesn't,
+        // and `impl<T: Copy> Foo<T>` doesn't apply to `Foo<String>::Name`.
+        let mut applicable: Vec<_> = candidates
+            .iter()
+            .filter_map(|&(impl_def_id, item, def_scope)| {
+                let impl_substs = match_impl_self_ty(tcx, impl_def_id, qself_ty)?;
+                let predicates = tcx.predicates_of(impl_def_id).instantiate(tcx, impl_substs);
+                // Higher-ranked types can only be checked once their bound regions are replaced.
+                let predicates = if predicates.has_escaping_bound_vars() {
+                    None
+                } else if self.where_clauses_may_hold(span, predicates.clone()) {
+                    Some(predicates)
+                } else {
+                    return None;
+                };
+                Some((impl_substs, predicates, item, def_scope))
+            })
+            .collect();
+
+        if applicable.len() != 1 {
+            let mut err = if applicable.is_empty() {
+                struct_span_err!(
+                    tcx.sess,
+                    span,
+                    E0220,
+                    "associated type `{}` not found for `{}`",
+                    assoc_ident,
+                    qself_ty
+                )
+            } else {
+                struct_span_err!(tcx.sess, span, E0223, "ambiguous associated type")
+            };
+            for &(impl_def_id, item, _) in &candidates {
+                let impl_self_ty = tcx.type_of(impl_def_id);
+                err.span_note(
+                    tcx.def_span(item.def_id),
+                    &format!(
+                        "associated type `{}` of `{}` defined here",
+                        assoc_ident, impl_self_ty
+                    ),
+                );
+            }
+            err.emit();
+            return Err(ErrorReported);
+        }
+
+        let (impl_substs, predicates, item, def_scope) = applicable.pop().unwrap();
+        if !item.vis.is_accessible_from(def_scope, tcx) {
+            let msg = format!("associated type `{}` is private", assoc_ident);
+            tcx.sess.struct_span_err(span, &msg).span_label(span, "private associated type").emit();
+        }
+        tcx.check_stability(item.def_id, Some(hir_ref_id), span, None);
+        if let Some(predicates) = predicates {
+            self.register_where_clauses(span, predicates);
+        }
+
+        let item_substs = self.create_substs_for_associated_item(
+            tcx,
+            span,
+            item.def_id,
+            assoc_segment,
+            impl_substs,
+        );
+        let ty = tcx.type_of(item.def_id).subst(tcx, item_substs);
+        Ok(Some((self.normalize_ty(span, ty), item.def_id)))
+    }
+}
+
+/// Matches the self type of the inherent impl `impl_def_id` with `self_ty`, and returns the
+/// generic arguments of the impl that make them equal, or `None` if the impl can't apply to
+/// `self_ty`. Regions are not compared, and the parts of `self_ty` that aren't known yet, like
+/// inference variables and projections, match any type.
+fn match_impl_self_ty<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    impl_def_id: DefId,
+    self_ty: Ty<'tcx>,
+) -> Option<SubstsRef<'tcx>> {
+    let mut matcher = ImplSelfTyMatcher { tcx, args: Default::default() };
+    matcher.tys(tcx.type_of(impl_def_id), self_ty).ok()?;
+    Some(InternalSubsts::for_item(tcx, impl_def_id, |param, _| {
+        match matcher.args.get(&param.index) {
+            Some(&arg) => arg,
+            // Only lifetimes can be missing: the other parameters of an impl have to be
+            // constrained by its self type.
+            None => match param.kind {
+                ty::GenericParamDefKind::Lifetime => tcx.lifetimes.re_static.into(),
+                ty::GenericParamDefKind::Type { .. } => tcx.ty_error().into(),
+                ty::GenericParamDefKind::Const { .. } => {
+                    tcx.const_error(tcx.type_of(param.def_id)).into()
+                }
+            },
+        }
+    }))
+}
+
+/// Finds the arguments of the generic parameters of an impl that make its self type equal to
+/// another type, see `match_impl_self_ty`.
+struct ImplSelfTyMatcher<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    /// The arguments of the parameters of the impl, by index.
+    args: FxHashMap<u32, GenericArg<'tcx>>,
+}
+
+impl ImplSelfTyMatcher<'tcx> {
+    fn bind(&mut self, index: u32, arg: GenericArg<'tcx>) -> RelateResult<'tcx, ()> {
+        match self.args.entry(index) {
+            Entry::Vacant(entry) => {
+                entry.insert(arg);
+                Ok(())
+            }
+            Entry::Occupied(entry) => match arg.unpack() {
+                GenericArgKind::Lifetime(_) => Ok(()),
+                _ if self.tcx.erase_regions(*entry.get()) == self.tcx.erase_regions(arg) => Ok(()),
+                _ => Err(TypeError::Mismatch),
+            },
+        }
+    }
+}
+
+impl TypeRelation<'tcx> for ImplSelfTyMatcher<'tcx> {
+    fn tag(&self) -> &'static str {
+        "ImplSelfTyMatcher"
+    }
+
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn param_env(&self) -> ty::ParamEnv<'tcx> {
+        ty::ParamEnv::empty()
+    }
+
+    fn a_is_expected(&self) -> bool {
+        true
+    }
+
+    fn relate_with_variance<T: Relate<'tcx>>(
+        &mut self,
+        _: ty::Variance,
+        _: ty::VarianceDiagInfo<'tcx>,
+        a: T,
+        b: T,
+    ) -> RelateResult<'tcx, T> {
+        self.relate(a, b)
+    }
+
+    fn regions(
+        &mut self,
+        a: ty::Region<'tcx>,
+        b: ty::Region<'tcx>,
+    ) -> RelateResult<'tcx, ty::Region<'tcx>> {
+        if let ty::ReEarlyBound(param) = *a {
+            self.bind(param.index, b.into())?;
+        }
+        Ok(a)
+    }
+
+    fn tys(&mut self, a: Ty<'tcx>, b: Ty<'tcx>) -> RelateResult<'tcx, Ty<'tcx>> {
+        match (a.kind(), b.kind()) {
+            (&ty::Param(param), _) => {
+                self.bind(param.index, b.into())?;
+                Ok(a)
+            }
+            (_, ty::Infer(_) | ty::Projection(_) | ty::Opaque(..) | ty::Error(_)) => Ok(a),
+            _ => relate::super_relate_tys(self, a, b),
+        }
+    }
+
+    fn consts(
+        &mut self,
+        a: &'tcx ty::Const<'tcx>,
+        b: &'tcx ty::Const<'tcx>,
+    ) -> RelateResult<'tcx, &'tcx ty::Const<'tcx>> {
+        match (a.val, b.val) {
+            (ty::ConstKind::Param(param), _) => {
+                self.bind(param.index, b.into())?;
+                Ok(a)
+            }
+            (
+                _,
+                ty::ConstKind::Infer(_) | ty::ConstKind::Unevaluated(_) | ty::ConstKind::Error(_),
+            ) => Ok(a),
+            _ => relate::super_relate_consts(self, a, b),
+        }
+    }
+
+    fn binders<T: Relate<'tcx>>(
+        &mut self,
+        a: ty::Binder<'tcx, T>,
+        b: ty::Binder<'tcx, T>,
+    ) -> RelateResult<'tcx, ty::Binder<'tcx, T>> {
+        self.relate(a.skip_binder(), b.skip_binder())?;
+        Ok(a)
+    }
+}
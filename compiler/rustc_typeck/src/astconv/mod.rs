@@ -4,6 +4,7 @@
 
 mod errors;
 mod generics;
+mod inherent;
 
 use crate::bounds::Bounds;
 use crate::collect::PlaceholderHirTyCollector;
@@ -102,6 +103,18 @@ fn projected_ty_from_poly_trait_ref(
     /// Normalize an associated type coming from the user.
     fn normalize_ty(&self, span: Span, ty: Ty<'tcx>) -> Ty<'tcx>;
 
+    /// Returns `true` if the `predicates` may hold where the type is written, e.g. the where
+    /// clauses of an inherent impl whose associated type is named by a path.
+    fn where_clauses_may_hold(
+        &self,
+        span: Span,
+        predicates: ty::InstantiatedPredicates<'tcx>,
+    ) -> bool;
+
+    /// Requires the `predicates` to hold for the type to be well-formed, e.g. the where clauses
+    /// of the inherent impl that an associated type is found in.
+    fn register_where_clauses(&self, span: Span, predicates: ty::InstantiatedPredicates<'tcx>);
+
     /// Invoked when we encounter an error from some prior pass
     /// (e.g., resolve) that is translated into a ty-error. This is
     /// used to help suppress derived errors typeck might otherwise
@@ -1383,6 +1396,7 @@ trait here instead: `trait NewTrait: {} {{}}`",
                             tcx.associated_items(pred.def_id())
                                 .in_definition_order()
                                 .filter(|item| item.kind == ty::AssocKind::Type)
+                                .filter(|item| !traits::gat_requires_sized_self(tcx, item.def_id))
                                 .map(|item| item.def_id),
                         );
                     }
@@ -1788,6 +1802,20 @@ pub fn associated_path_to_ty(
                 Res::SelfTy(Some(param_did), None) | Res::Def(DefKind::TyParam, param_did),
             ) => self.find_bound_for_assoc_item(param_did.expect_local(), assoc_ident, span)?,
             _ => {
+                if let ty::Adt(adt_def, _) = qself_ty.kind() {
+                    if tcx.features().inherent_associated_types {
+                        if let Some((ty, def_id)) = self.lookup_inherent_assoc_ty(
+                            hir_ref_id,
+                            span,
+                            qself_ty,
+                            adt_def.did,
+                            assoc_segment,
+                        )? {
+                            return Ok((ty, DefKind::AssocTy, def_id));
+                        }
+                    }
+                }
+
                 if variant_resolution.is_some() {
                     // Variant in type position
                     let msg = format!("expected type, found variant `{}`", assoc_ident);
@@ -21,7 +21,8 @@
 use rustc_session::Session;
 use rustc_span::symbol::Ident;
 use rustc_span::{self, Span};
-use rustc_trait_selection::traits::{ObligationCause, ObligationCauseCode};
+use rustc_trait_selection::traits::query::evaluate_obligation::InferCtxtExt;
+use rustc_trait_selection::traits::{self, ObligationCause, ObligationCauseCode};
 
 use std::cell::{Cell, RefCell};
 use std::ops::Deref;
@@ -287,6 +288,21 @@ fn normalize_ty(&self, span: Span, ty: Ty<'tcx>) -> Ty<'tcx> {
         }
     }
 
+    fn where_clauses_may_hold(
+        &self,
+        span: Span,
+        predicates: ty::InstantiatedPredicates<'tcx>,
+    ) -> bool {
+        let cause = ObligationCause::misc(span, self.body_id);
+        traits::predicates_for_generics(cause, self.param_env, predicates)
+            .all(|obligation| self.predicate_may_hold(&obligation))
+    }
+
+    fn register_where_clauses(&self, span: Span, predicates: ty::InstantiatedPredicates<'tcx>) {
+        let cause = ObligationCause::misc(span, self.body_id);
+        self.add_obligations_for_parameters(cause, predicates);
+    }
+
     fn set_tainted_by_errors(&self) {
         self.infcx.set_tainted_by_errors()
     }
@@ -4,7 +4,7 @@
 //! types computed here.
 
 use super::FnCtxt;
-use rustc_data_structures::fx::{FxHashSet, FxIndexSet};
+use rustc_data_structures::fx::{FxHashMap, FxIndexSet};
 use rustc_errors::pluralize;
 use rustc_hir as hir;
 use rustc_hir::def::{CtorKind, DefKind, Res};
@@ -208,14 +208,17 @@ pub fn resolve_interior<'a, 'tcx>(
     debug!("types in generator {:?}, span = {:?}", types, body.value.span);
 
     let mut counter = 0;
-    let mut captured_tys = FxHashSet::default();
-    let type_causes: Vec<_> = types
+    let mut folded_tys = FxHashMap::default();
+    let mut type_causes = Vec::new();
+    let value_causes: Vec<_> = types
         .into_iter()
-        .filter_map(|mut cause| {
+        .map(|mut cause| {
             // Erase regions and canonicalize late-bound regions to deduplicate as many types as we
-            // can.
+            // can. Values of the same type share one type of the witness and one type cause, but
+            // all of them are kept in the value causes, to explain every value that makes the
+            // generator `!Send` or `!Sync`.
             let erased = fcx.tcx.erase_regions(cause.ty);
-            if captured_tys.insert(erased) {
+            cause.ty = *folded_tys.entry(erased).or_insert_with(|| {
                 // Replace all regions inside the generator interior with late bound regions.
                 // Note that each region slot in the types gets a new fresh late bound region,
                 // which means that none of the regions inside relate to any other, even if
@@ -229,12 +232,10 @@ pub fn resolve_interior<'a, 'tcx>(
                     counter += 1;
                     r
                 });
-
-                cause.ty = folded;
-                Some(cause)
-            } else {
-                None
-            }
+                type_causes.push(ty::GeneratorInteriorTypeCause { ty: folded, ..cause.clone() });
+                folded
+            });
+            cause
         })
         .collect();
 
@@ -248,7 +249,9 @@ pub fn resolve_interior<'a, 'tcx>(
 
     // Store the generator types and spans into the typeck results for this generator.
     visitor.fcx.inh.typeck_results.borrow_mut().generator_interior_types =
-        ty::Binder::bind_with_vars(type_causes, bound_vars);
+        ty::Binder::bind_with_vars(type_causes, bound_vars.clone());
+    visitor.fcx.inh.typeck_results.borrow_mut().generator_interior_values =
+        ty::Binder::bind_with_vars(value_causes, bound_vars);
 
     debug!(
         "types in generator after region replacement {:?}, span = {:?}",
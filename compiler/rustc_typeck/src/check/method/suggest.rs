@@ -1736,7 +1736,7 @@ fn handle_external_res(
             _ => {}
         }
     }
-    for &cnum in tcx.crates(()).iter() {
+    for &cnum in tcx.crates_for_diagnostics(()).iter() {
         let def_id = DefId { krate: cnum, index: CRATE_DEF_INDEX };
         handle_external_res(tcx, &mut traits, &mut external_mods, Res::Def(DefKind::Mod, def_id));
     }
@@ -3,7 +3,7 @@
 
 use rustc_ast as ast;
 use rustc_data_structures::fx::FxHashSet;
-use rustc_errors::{struct_span_err, Applicability, DiagnosticBuilder};
+use rustc_errors::{pluralize, struct_span_err, Applicability, DiagnosticBuilder};
 use rustc_hir as hir;
 use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_hir::intravisit as hir_visit;
@@ -11,8 +11,10 @@
 use rustc_hir::itemlikevisit::ParItemLikeVisitor;
 use rustc_hir::lang_items::LangItem;
 use rustc_hir::ItemKind;
+use rustc_infer::infer::TyCtxtInferExt;
 use rustc_middle::hir::map as hir_map;
-use rustc_middle::ty::subst::{InternalSubsts, Subst};
+use rustc_middle::traits::query::OutlivesBound;
+use rustc_middle::ty::subst::{GenericArgKind, InternalSubsts, Subst};
 use rustc_middle::ty::trait_def::TraitSpecializationKind;
 use rustc_middle::ty::{
     self, AdtKind, GenericParamDefKind, ToPredicate, Ty, TyCtxt, TypeFoldable, WithConstness,
@@ -21,6 +23,7 @@
 use rustc_span::symbol::{sym, Ident, Symbol};
 use rustc_span::Span;
 use rustc_trait_selection::traits::query::evaluate_obligation::InferCtxtExt;
+use rustc_trait_selection::traits::query::outlives_bounds::InferCtxtExt as _;
 use rustc_trait_selection::traits::{self, ObligationCause, ObligationCauseCode, WellFormedLoc};
 
 use std::convert::TryInto;
@@ -204,6 +207,8 @@ pub fn check_trait_item(tcx: TyCtxt<'_>, def_id: LocalDefId) {
     let encl_trait_hir_id = tcx.hir().get_parent_item(hir_id);
     let encl_trait = tcx.hir().expect_item(encl_trait_hir_id);
     let encl_trait_def_id = encl_trait.def_id.to_def_id();
+    check_gat_where_clauses(tcx, trait_item, encl_trait_def_id);
+
     let fn_lang_item_name = if Some(encl_trait_def_id) == tcx.lang_items().fn_trait() {
         Some("fn")
     } else if Some(encl_trait_def_id) == tcx.lang_items().fn_mut_trait() {
@@ -255,6 +260,159 @@ pub fn check_trait_item(tcx: TyCtxt<'_>, def_id: LocalDefId) {
     }
 }
 
+/// Requires the generic associated types of a trait to declare the outlives bounds that the
+/// methods of the trait imply for the arguments they pass to them. Here, `next` implies that
+/// `Self: 'a` through its `&'a mut self` argument, so `Item` has to declare it:
+///
+/// ```rust
+/// trait LendingIterator {
+///     type Item<'a> where Self: 'a;
+///
+///     fn next<'a>(&'a mut self) -> Option<Self::Item<'a>>;
+/// }
+/// ```
+///
+/// Without the bound, impls couldn't define `Item<'a>` as a type that borrows from `Self`, like
+/// `&'a mut [T]`. A bound is only required if every method that returns the associated type
+/// implies it.
+fn check_gat_where_clauses<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    trait_item: &hir::TraitItem<'_>,
+    encl_trait_def_id: DefId,
+) {
+    let item = tcx.associated_item(trait_item.def_id.to_def_id());
+    if item.kind != ty::AssocKind::Type || tcx.generics_of(item.def_id).params.is_empty() {
+        return;
+    }
+    let gat_substs = InternalSubsts::identity_for_item(tcx, item.def_id);
+
+    let mut required: Option<FxHashSet<ty::Predicate<'tcx>>> = None;
+    for method in tcx
+        .associated_items(encl_trait_def_id)
+        .in_definition_order()
+        .filter(|item| item.kind == ty::AssocKind::Fn)
+    {
+        let sig = tcx.liberate_late_bound_regions(method.def_id, tcx.fn_sig(method.def_id));
+
+        // The arguments the method passes to the associated type in its return type, with the
+        // index of the parameter they are passed to.
+        let mut regions = FxHashSet::default();
+        let mut types = FxHashSet::default();
+        for arg in sig.output().walk(tcx) {
+            let projection = match arg.unpack() {
+                GenericArgKind::Type(ty) => match *ty.kind() {
+                    ty::Projection(projection) if projection.item_def_id == item.def_id => {
+                        projection
+                    }
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            for (index, arg) in projection.substs.iter().enumerate() {
+                match arg.unpack() {
+                    GenericArgKind::Lifetime(region) => {
+                        regions.insert((region, index));
+                    }
+                    GenericArgKind::Type(ty) => {
+                        types.insert((ty, index));
+                    }
+                    GenericArgKind::Const(_) => {}
+                }
+            }
+        }
+        // Methods that don't return the associated type don't require anything of it.
+        if regions.is_empty() && types.is_empty() {
+            continue;
+        }
+
+        let param_env = tcx.param_env(method.def_id);
+        let hir_id = tcx.hir().local_def_id_to_hir_id(method.def_id.expect_local());
+        let span = tcx.def_span(method.def_id);
+        let implied_bounds: Vec<_> = tcx.infer_ctxt().enter(|infcx| {
+            sig.inputs()
+                .iter()
+                .flat_map(|&ty| infcx.implied_outlives_bounds(param_env, hir_id, ty, span))
+                .collect()
+        });
+        let is_declared = |predicate: ty::PredicateKind<'tcx>| {
+            param_env.caller_bounds().iter().any(|p| p.kind().skip_binder() == predicate)
+        };
+        let ty_outlives = |ty: Ty<'tcx>, region: ty::Region<'tcx>| {
+            let param = match *ty.kind() {
+                ty::Param(param) => param,
+                _ => return false,
+            };
+            implied_bounds.iter().any(|bound| {
+                matches!(*bound, OutlivesBound::RegionSubParam(r, p) if r == region && p == param)
+            }) || is_declared(ty::PredicateKind::TypeOutlives(ty::OutlivesPredicate(ty, region)))
+        };
+        let region_outlives = |a: ty::Region<'tcx>, b: ty::Region<'tcx>| {
+            implied_bounds.iter().any(|bound| {
+                matches!(*bound, OutlivesBound::RegionSubRegion(sub, sup) if sub == b && sup == a)
+            }) || is_declared(ty::PredicateKind::RegionOutlives(ty::OutlivesPredicate(a, b)))
+        };
+
+        let mut method_required = FxHashSet::default();
+        for &(region, region_index) in &regions {
+            let gat_region = gat_substs.region_at(region_index);
+            for &(ty, ty_index) in &types {
+                if ty_outlives(ty, region) {
+                    let gat_ty = gat_substs.type_at(ty_index);
+                    let outlives = ty::OutlivesPredicate(gat_ty, gat_region);
+                    let predicate = ty::PredicateKind::TypeOutlives(outlives);
+                    method_required.insert(ty::Binder::dummy(predicate).to_predicate(tcx));
+                }
+            }
+            for &(other, other_index) in &regions {
+                if other != region && region_outlives(other, region) {
+                    let gat_other = gat_substs.region_at(other_index);
+                    let outlives = ty::OutlivesPredicate(gat_other, gat_region);
+                    let predicate = ty::PredicateKind::RegionOutlives(outlives);
+                    method_required.insert(ty::Binder::dummy(predicate).to_predicate(tcx));
+                }
+            }
+        }
+        // A method returning `Self::Item<'static>` doesn't imply `Self: 'a` for all `'a`, so
+        // only the bounds that all methods imply are required.
+        required = Some(match required {
+            Some(required) => required.intersection(&method_required).copied().collect(),
+            None => method_required,
+        });
+    }
+
+    let declared = tcx.explicit_predicates_of(item.def_id).predicates;
+    let mut missing: Vec<_> = required
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|&predicate| !declared.iter().any(|&(p, _)| p == predicate))
+        .map(|predicate| predicate.to_string())
+        .collect();
+    if missing.is_empty() {
+        return;
+    }
+    missing.sort();
+
+    let mut err = tcx.sess.struct_span_err(
+        trait_item.span,
+        &format!("missing required bound{} on `{}`", pluralize!(missing.len()), trait_item.ident),
+    );
+    let separator =
+        if trait_item.generics.where_clause.predicates.is_empty() { " where" } else { "," };
+    err.span_suggestion(
+        trait_item.generics.where_clause.tail_span_for_suggestion(),
+        &format!("add the required where clause{}", pluralize!(missing.len())),
+        format!("{} {}", separator, missing.join(", ")),
+        Applicability::MachineApplicable,
+    );
+    err.note(&format!(
+        "{} implied by the signatures of the methods that return `{}`, so impls can rely on {}",
+        if missing.len() == 1 { "this bound is" } else { "these bounds are" },
+        trait_item.ident,
+        if missing.len() == 1 { "it" } else { "them" },
+    ));
+    err.emit();
+}
+
 fn could_be_self(trait_def_id: LocalDefId, ty: &hir::Ty<'_>) -> bool {
     match ty.kind {
         hir::TyKind::TraitObject([trait_ref], ..) => match trait_ref.trait_ref.path.segments {
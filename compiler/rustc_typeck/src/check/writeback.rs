@@ -494,6 +494,8 @@ fn visit_generator_interior_types(&mut self) {
         assert_eq!(fcx_typeck_results.hir_owner, self.typeck_results.hir_owner);
         self.typeck_results.generator_interior_types =
             fcx_typeck_results.generator_interior_types.clone();
+        self.typeck_results.generator_interior_values =
+            fcx_typeck_results.generator_interior_values.clone();
     }
 
     #[instrument(skip(self, span), level = "debug")]
@@ -33,6 +33,7 @@
 use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
 use rustc_hir::weak_lang_items;
 use rustc_hir::{GenericParamKind, HirId, Node};
+use rustc_infer::infer::TyCtxtInferExt;
 use rustc_middle::hir::map::Map;
 use rustc_middle::middle::codegen_fn_attrs::{CodegenFnAttrFlags, CodegenFnAttrs};
 use rustc_middle::mir::mono::Linkage;
@@ -40,14 +41,16 @@
 use rustc_middle::ty::subst::InternalSubsts;
 use rustc_middle::ty::util::Discr;
 use rustc_middle::ty::util::IntTypeExt;
-use rustc_middle::ty::{self, AdtKind, Const, DefIdTree, Ty, TyCtxt};
+use rustc_middle::ty::{self, AdtKind, Const, DefIdTree, Ty, TyCtxt, TypeFoldable};
 use rustc_middle::ty::{ReprOptions, ToPredicate, WithConstness};
 use rustc_session::lint;
 use rustc_session::parse::feature_err;
 use rustc_span::symbol::{kw, sym, Ident, Symbol};
 use rustc_span::{Span, DUMMY_SP};
 use rustc_target::spec::{abi, PanicStrategy, SanitizerSet};
+use rustc_trait_selection::traits;
 use rustc_trait_selection::traits::error_reporting::suggestions::NextTypeParamName;
+use rustc_trait_selection::traits::query::evaluate_obligation::InferCtxtExt;
 use std::iter;
 
 mod item_bounds;
@@ -505,6 +508,27 @@ fn normalize_ty(&self, _span: Span, ty: Ty<'tcx>) -> Ty<'tcx> {
         ty
     }
 
+    fn where_clauses_may_hold(
+        &self,
+        span: Span,
+        predicates: ty::InstantiatedPredicates<'tcx>,
+    ) -> bool {
+        // The param env of the item can't be used, as its predicates may be what is being
+        // lowered. So the where clauses that mention generic parameters are assumed to hold, and
+        // only the others are checked.
+        let cause = traits::ObligationCause::dummy_with_span(span);
+        self.tcx.infer_ctxt().enter(|infcx| {
+            traits::predicates_for_generics(cause, ty::ParamEnv::empty(), predicates)
+                .filter(|obligation| !obligation.predicate.needs_subst())
+                .all(|obligation| infcx.predicate_may_hold(&obligation))
+        })
+    }
+
+    fn register_where_clauses(&self, _span: Span, _predicates: ty::InstantiatedPredicates<'tcx>) {
+        // Signatures have no inference variables, so `where_clauses_may_hold` already checked
+        // all the where clauses that can be checked here.
+    }
+
     fn set_tainted_by_errors(&self) {
         // There's no obvious place to track this, so just let it go.
     }
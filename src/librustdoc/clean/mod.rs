@@ -1131,7 +1131,7 @@ fn clean(&self, cx: &mut DocContext<'_>) -> Item {
 
                     AssocTypeItem(bounds, ty.clean(cx))
                 } else {
-                    // FIXME: when could this happen? Associated items in inherent impls?
+                    // Associated types of inherent impls.
                     let type_ = tcx.type_of(self.def_id).clean(cx);
                     TypedefItem(
                         Typedef {
@@ -1287,7 +1287,8 @@ fn clean_qpath(hir_ty: &hir::Ty<'_>, cx: &mut DocContext<'_>) -> Type {
                 ty::Projection(proj) => Res::Def(DefKind::Trait, proj.trait_ref(cx.tcx).def_id),
                 // Rustdoc handles `ty::Error`s by turning them into `Type::Infer`s.
                 ty::Error(_) => return Type::Infer,
-                _ => bug!("clean: expected associated type, found `{:?}`", ty),
+                // Inherent associated types are resolved to the type they are defined as.
+                _ => return ty.clean(cx),
             };
             let trait_ = hir::Path { span, res, segments: &[] }.clean(cx);
             register_res(cx, trait_.res);
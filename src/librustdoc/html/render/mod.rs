@@ -1811,6 +1811,21 @@ fn get_next_url(used_links: &mut FxHashSet<String>, url: String) -> String {
     format!("{}-{}", url, add)
 }
 
+/// Returns the sidebar links to the associated types of an inherent impl.
+fn get_associated_types(i: &clean::Impl, used_links: &mut FxHashSet<String>) -> Vec<String> {
+    i.items
+        .iter()
+        .filter_map(|item| match item.name {
+            Some(ref name) if !name.is_empty() && item.is_typedef() => Some(format!(
+                "<a href=\"#{}\">{}</a>",
+                get_next_url(used_links, format!("{}.{}", ItemType::AssocType, name)),
+                name
+            )),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+}
+
 fn get_methods(
     i: &clean::Impl,
     for_deref: bool,
@@ -1879,6 +1894,29 @@ fn sidebar_assoc_items(cx: &Context<'_>, out: &mut Buffer, it: &clean::Item) {
     if let Some(v) = cache.impls.get(&did) {
         let mut used_links = FxHashSet::default();
 
+        {
+            let used_links_bor = &mut used_links;
+            let mut ret = v
+                .iter()
+                .filter(|i| i.inner_impl().trait_.is_none())
+                .flat_map(move |i| get_associated_types(i.inner_impl(), used_links_bor))
+                .collect::<Vec<_>>();
+            if !ret.is_empty() {
+                // We want links' order to be reproducible so we don't use unstable sort.
+                ret.sort();
+
+                out.push_str(
+                    "<h3 class=\"sidebar-title\">\
+                     <a href=\"#implementations\">Associated Types</a></h3>\
+                     <div class=\"sidebar-links\">",
+                );
+                for line in ret {
+                    out.push_str(&line);
+                }
+                out.push_str("</div>");
+            }
+        }
+
         {
             let used_links_bor = &mut used_links;
             let mut ret = v
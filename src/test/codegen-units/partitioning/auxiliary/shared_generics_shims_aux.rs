@@ -0,0 +1,14 @@
+// NOTE: We always compile this test with -Copt-level=0 because higher opt-levels
+//       make shims CGU-internal, which prevents them from participating in
+//       share-generics.
+// compile-flags:-Zshare-generics=yes -Copt-level=0
+// no-prefer-dynamic
+
+#![crate_type="rlib"]
+
+pub fn clone_pair(pair: &(u32, u32)) -> (u32, u32) {
+    // This instantiates the compiler-generated `Clone` shim for `(u32, u32)`.
+    // We want to make sure that this crate exports it to be re-used by
+    // share-generics.
+    pair.clone()
+}
@@ -0,0 +1,25 @@
+//
+// no-prefer-dynamic
+// NOTE: We always compile this test with -Copt-level=0 because higher opt-levels
+//       make shims CGU-internal, which prevents them from participating in
+//       share-generics.
+// incremental
+// compile-flags:-Zprint-mono-items=eager -Zshare-generics=yes -Copt-level=0
+
+#![crate_type="rlib"]
+
+// aux-build:shared_generics_shims_aux.rs
+extern crate shared_generics_shims_aux;
+
+//~ MONO_ITEM fn foo
+pub fn foo() {
+
+    //~ MONO_ITEM fn <(u16, u16) as std::clone::Clone>::clone - shim((u16, u16)) @@ shared_generics_shims-fallback.cgu[External]
+    let _ = (0u16, 1u16).clone();
+
+    // This should not generate a monomorphization of the `Clone` shim because
+    // it's already available in `shared_generics_shims_aux`.
+    let _ = (0u32, 1u32).clone();
+
+    let _ = shared_generics_shims_aux::clone_pair(&(2, 3));
+}
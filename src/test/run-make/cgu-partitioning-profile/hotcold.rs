@@ -0,0 +1,19 @@
+pub mod hot {
+    pub fn hot_loop(values: &[u32]) -> u32 {
+        let mut sum = 0;
+        for &value in values {
+            sum = step(sum, value);
+        }
+        sum
+    }
+
+    pub fn step(sum: u32, value: u32) -> u32 {
+        sum.wrapping_mul(31).wrapping_add(value)
+    }
+}
+
+pub mod cold {
+    pub fn report(len: usize) {
+        println!("no data in {} values", len);
+    }
+}
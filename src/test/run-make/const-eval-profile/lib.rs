@@ -0,0 +1,25 @@
+pub const fn squares() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < table.len() {
+        table[i] = (i * i) as u32;
+        i += 1;
+    }
+    table
+}
+
+pub static TABLE: [u32; 256] = squares();
+
+pub const SUM: u32 = squares()[2] + squares()[3];
+
+pub trait Size {
+    const SIZE: usize;
+}
+
+impl<T> Size for T {
+    const SIZE: usize = std::mem::size_of::<T>();
+}
+
+pub fn sizes() -> [usize; 2] {
+    [<u16 as Size>::SIZE, <u64 as Size>::SIZE]
+}
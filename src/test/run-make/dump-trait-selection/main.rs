@@ -0,0 +1,39 @@
+pub struct Set;
+pub struct Unset;
+
+pub struct Builder<Name, Port>(Name, Port);
+
+pub trait Build {
+    fn build(self) -> u32;
+}
+
+impl Build for Builder<Set, Set> {
+    fn build(self) -> u32 {
+        1
+    }
+}
+
+impl Build for Builder<Unset, Unset> {
+    fn build(self) -> u32 {
+        0
+    }
+}
+
+impl<T: Build> Build for Vec<T> {
+    fn build(self) -> u32 {
+        self.into_iter().map(Build::build).sum()
+    }
+}
+
+fn run<B: Build>(builder: B) -> u32 {
+    builder.build()
+}
+
+fn unrelated() -> u32 {
+    run(Builder(Unset, Unset))
+}
+
+fn main() {
+    run(vec![Builder(Set, Set)]);
+    unrelated();
+}
@@ -0,0 +1,12 @@
+#![crate_type = "cdylib"]
+
+#[no_mangle]
+pub extern "C" fn foo_init() {}
+
+#[no_mangle]
+pub extern "C" fn foo_process(x: u32) -> u32 {
+    x + 1
+}
+
+#[no_mangle]
+pub extern "C" fn foo_internal() {}
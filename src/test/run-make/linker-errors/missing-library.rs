@@ -0,0 +1,10 @@
+#[link(name = "does_not_exist")]
+extern "C" {
+    fn foo_init();
+}
+
+fn main() {
+    unsafe {
+        foo_init();
+    }
+}
@@ -0,0 +1,9 @@
+extern "C" {
+    fn foo_init();
+}
+
+fn main() {
+    unsafe {
+        foo_init();
+    }
+}
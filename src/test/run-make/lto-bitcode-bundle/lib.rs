@@ -0,0 +1,11 @@
+#![crate_type = "rlib"]
+
+#[no_mangle]
+pub extern "C" fn exported_to_c(x: u32) -> u32 {
+    not_exported(x) + 1
+}
+
+#[inline(never)]
+fn not_exported(x: u32) -> u32 {
+    x * 2
+}
@@ -0,0 +1,5 @@
+pub struct Wrapper<T>(pub T);
+
+pub fn wrap<T>(x: T) -> Wrapper<T> {
+    Wrapper(x)
+}
@@ -0,0 +1,7 @@
+extern crate helper;
+
+pub fn user() -> u32 {
+    let a = helper::wrap(1u8);
+    let b = helper::wrap('a');
+    a.0 as u32 + b.0 as u32
+}
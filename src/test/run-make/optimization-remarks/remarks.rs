@@ -0,0 +1,10 @@
+#![crate_type = "lib"]
+
+#[inline]
+fn double(x: u32) -> u32 {
+    x * 2
+}
+
+pub fn sum_doubled(values: &[u32]) -> u32 {
+    values.iter().map(|&x| double(x)).sum()
+}
@@ -0,0 +1,9 @@
+pub fn count_even<T, F: Fn(&T) -> u32>(items: &[T], key: F) -> usize {
+    items.iter().map(key).filter(|k| k % 2 == 0).count()
+}
+
+pub fn counts() -> usize {
+    count_even(&[1u8, 2], |&x| x.into())
+        + count_even(&[1u16, 2], |&x| x.into())
+        + count_even(&['a', 'b'], |&x| x.into())
+}
@@ -0,0 +1,27 @@
+#![crate_type = "cdylib"]
+
+#[inline(never)]
+fn leaf(x: u32) -> u32 {
+    let buffer = [x; 64];
+    buffer.iter().sum()
+}
+
+#[no_mangle]
+pub extern "C" fn bounded(x: u32) -> u32 {
+    leaf(x) + 1
+}
+
+#[no_mangle]
+pub extern "C" fn recursive(n: u32) -> u32 {
+    if n == 0 { 0 } else { recursive(n - 1) + 1 }
+}
+
+#[no_mangle]
+pub extern "C" fn indirect(f: extern "C" fn(u32) -> u32) -> u32 {
+    f(1)
+}
+
+#[no_mangle]
+pub extern "C" fn drops_trait_object(x: u32) {
+    let _boxed: Box<dyn std::fmt::Debug> = Box::new(x);
+}
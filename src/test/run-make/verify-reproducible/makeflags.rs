@@ -0,0 +1,3 @@
+pub fn makeflags() -> Option<&'static str> {
+    option_env!("MAKEFLAGS")
+}
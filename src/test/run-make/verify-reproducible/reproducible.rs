@@ -0,0 +1,3 @@
+pub fn sum(values: &[u32]) -> u32 {
+    values.iter().sum()
+}
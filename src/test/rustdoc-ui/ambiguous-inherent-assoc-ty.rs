@@ -1,4 +1,5 @@
-// This test ensures that rustdoc does not panic on inherented associated types
+// check-pass
+// This test ensures that rustdoc does not panic on inherent associated types
 // that are referred to without fully-qualified syntax.
 
 #![feature(inherent_associated_types)]
@@ -9,9 +10,4 @@
 impl Struct {
     pub type AssocTy = usize;
     pub const AssocConst: Self::AssocTy = 42;
-    //~^ ERROR ambiguous associated type
-    //~| HELP use fully-qualified syntax
-    // FIXME: for some reason, the error is shown twice with rustdoc but only once with rustc
-    //~| ERROR ambiguous associated type
-    //~| HELP use fully-qualified syntax
 }
@@ -0,0 +1,58 @@
+// run-pass
+// Checks that paths to inherent associated types are resolved to the types they are defined
+// as, with the generic arguments of the self type.
+
+#![feature(inherent_associated_types)]
+#![allow(incomplete_features)]
+
+struct Register<T>(T);
+
+impl Register<u8> {
+    type Raw = u8;
+}
+
+impl Register<u16> {
+    type Raw = u16;
+}
+
+struct Buffer<T, const N: usize>([T; N]);
+
+impl<T, const N: usize> Buffer<T, N> {
+    type Array = [T; N];
+    type Item = T;
+
+    fn into_array(self) -> Self::Array {
+        self.0
+    }
+}
+
+struct Wrapper<'a>(&'a str);
+
+impl<'a> Wrapper<'a> {
+    type Inner = &'a str;
+
+    fn get(&self) -> Self::Inner {
+        self.0
+    }
+}
+
+fn read_u8(register: Register<u8>) -> Register<u8>::Raw {
+    register.0
+}
+
+fn first<T: Copy, const N: usize>(buffer: Buffer<T, N>) -> Buffer<T, N>::Item {
+    buffer.into_array()[0]
+}
+
+fn main() {
+    let byte: Register<u8>::Raw = read_u8(Register(1));
+    let word: Register<u16>::Raw = 0x1234;
+    assert_eq!(byte as u16 + word, 0x1235);
+
+    let array: Buffer<char, 2>::Array = Buffer(['a', 'b']).into_array();
+    assert_eq!(array, ['a', 'b']);
+    assert_eq!(first(Buffer([3, 4, 5])), 3);
+
+    let inner: Wrapper<'_>::Inner = Wrapper("inner").get();
+    assert_eq!(inner, "inner");
+}
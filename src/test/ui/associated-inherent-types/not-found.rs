@@ -0,0 +1,17 @@
+#![feature(inherent_associated_types)]
+#![allow(incomplete_features)]
+
+struct Register<T>(T);
+
+impl Register<u8> {
+    type Raw = u8;
+}
+
+impl Register<u16> {
+    type Raw = u16;
+}
+
+fn main() {
+    let _: Register<u32>::Raw = 0;
+    //~^ ERROR associated type `Raw` not found for `Register<u32>`
+}
@@ -0,0 +1,17 @@
+#![feature(inherent_associated_types)]
+#![allow(incomplete_features)]
+
+mod registers {
+    pub struct Register;
+
+    impl Register {
+        type Raw = u8;
+        pub type Public = u16;
+    }
+}
+
+fn main() {
+    let _: registers::Register::Public = 0;
+    let _: registers::Register::Raw = 0;
+    //~^ ERROR associated type `Raw` is private
+}
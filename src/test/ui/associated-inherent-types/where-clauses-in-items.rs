@@ -0,0 +1,38 @@
+#![feature(inherent_associated_types)]
+#![allow(incomplete_features)]
+
+// Inherent associated types in the where clauses of items, which are lowered without the param
+// env of the item: the where clauses of the impls that mention the generic parameters of the
+// item are assumed to hold, the others are checked.
+
+struct Foo<T>(T);
+
+impl<T: Copy> Foo<T> {
+    type X = T;
+}
+
+fn generic<T: Copy>()
+where
+    Foo<T>::X: Clone,
+{
+}
+
+impl<T: Copy> Foo<T> {
+    fn in_impl()
+    where
+        Self::X: Copy,
+    {
+    }
+}
+
+fn not_copy()
+where
+    Foo<String>::X: Clone,
+    //~^ ERROR associated type `X` not found for `Foo<String>`
+{
+}
+
+fn main() {
+    generic::<u8>();
+    Foo::<u8>::in_impl();
+}
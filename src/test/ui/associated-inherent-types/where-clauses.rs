@@ -0,0 +1,31 @@
+#![feature(inherent_associated_types)]
+#![allow(incomplete_features)]
+
+struct Foo<T>(T);
+
+impl<T: Copy> Foo<T> {
+    type X = T;
+}
+
+struct Bar<T>(T);
+
+impl<T: Copy> Bar<T> {
+    type X = T;
+}
+
+impl Bar<String> {
+    type X = &'static str;
+}
+
+type NotCopy = Foo<Vec<u8>>::X;
+//~^ ERROR associated type `X` not found for `Foo<Vec<u8>>`
+
+fn main() {
+    let _: Foo<u8>::X = 0u8;
+    let _: Foo<String>::X = String::new();
+    //~^ ERROR associated type `X` not found for `Foo<String>`
+
+    // Only the impl for `Bar<String>` applies, as `String` isn't `Copy`.
+    let _: Bar<u8>::X = 0u8;
+    let _: Bar<String>::X = "not ambiguous";
+}
@@ -0,0 +1,28 @@
+// edition:2018
+
+// Checks that every value held across an await that makes a future `!Send` is explained once,
+// under the error for its type, and that the values that aren't used after the await are suggested
+// to be dropped before it.
+
+use std::rc::Rc;
+use std::sync::Mutex;
+
+fn is_send<T: Send>(_: T) {}
+
+async fn baz() {}
+
+async fn values(mutex: &Mutex<u32>) {
+    let guard = mutex.lock().unwrap();
+    let total = Rc::new(1);
+    let counter = Rc::new(0);
+    is_send(*counter);
+    baz().await;
+    drop(guard);
+    drop(total);
+}
+
+fn main() {
+    is_send(values(&Mutex::new(0)));
+    //~^ ERROR future cannot be sent between threads safely
+    //~| ERROR future cannot be sent between threads safely
+}
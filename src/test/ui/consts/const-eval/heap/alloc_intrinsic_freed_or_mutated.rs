@@ -0,0 +1,44 @@
+// stderr-per-bitwidth
+// Heap allocations in the final value of a global must never be freed or mutated at runtime.
+#![feature(core_intrinsics)]
+#![feature(const_heap)]
+#![feature(const_raw_ptr_deref)]
+#![feature(const_mut_refs)]
+use std::cell::Cell;
+use std::intrinsics;
+use std::mem;
+
+const fn alloc(value: i32) -> *mut i32 {
+    unsafe {
+        let ptr = intrinsics::const_allocate(4, 4) as *mut i32;
+        *ptr = value;
+        ptr
+    }
+}
+
+// Every use of the constant would free the allocation when dropping the box.
+const BOX: Box<i32> = unsafe { mem::transmute(alloc(1)) };
+//~^ ERROR it is undefined behavior to use this value
+
+struct Owned(*mut i32);
+
+impl Drop for Owned {
+    fn drop(&mut self) {}
+}
+
+const OWNED: Owned = Owned(alloc(2));
+//~^ ERROR it is undefined behavior to use this value
+
+struct SyncCell(Cell<i32>);
+
+unsafe impl Sync for SyncCell {}
+
+static CELL: &SyncCell = unsafe { &*(alloc(3) as *const SyncCell) };
+//~^ ERROR it is undefined behavior to use this value
+
+// The heap memory of another constant can't be owned either.
+const RAW: *const i32 = alloc(4);
+const B: Box<i32> = unsafe { mem::transmute(RAW) };
+//~^ ERROR it is undefined behavior to use this value
+
+fn main() {}
@@ -0,0 +1,56 @@
+// run-pass
+// Heap allocations made during const-eval can be part of the final value of a global, e.g. as a
+// lookup table, and become immutable static memory.
+#![feature(core_intrinsics)]
+#![feature(const_heap)]
+#![feature(const_raw_ptr_deref)]
+#![feature(const_mut_refs)]
+#![feature(const_ptr_offset)]
+#![feature(const_slice_from_raw_parts)]
+use std::intrinsics;
+use std::slice;
+
+const fn squares(len: usize) -> *const u32 {
+    unsafe {
+        let ptr = intrinsics::const_allocate(len * 4, 4) as *mut u32;
+        let mut i = 0;
+        while i < len {
+            *ptr.add(i) = (i * i) as u32;
+            i += 1;
+        }
+        ptr
+    }
+}
+
+// Owns its allocation, which is fine in a `static`, as it is never dropped.
+struct Table {
+    ptr: *const u32,
+    len: usize,
+}
+
+unsafe impl Sync for Table {}
+
+impl Table {
+    fn get(&self, i: usize) -> u32 {
+        assert!(i < self.len);
+        unsafe { *self.ptr.add(i) }
+    }
+}
+
+impl Drop for Table {
+    fn drop(&mut self) {
+        unreachable!()
+    }
+}
+
+static TABLE: Table = Table { ptr: squares(16), len: 16 };
+
+const SLICE: &[u32] = unsafe { slice::from_raw_parts(squares(4), 4) };
+
+const RAW: *const u32 = squares(2);
+
+fn main() {
+    assert_eq!(TABLE.get(15), 225);
+    assert_eq!(SLICE, [0, 1, 4, 9]);
+    assert_eq!(unsafe { *RAW.add(1) }, 1);
+}
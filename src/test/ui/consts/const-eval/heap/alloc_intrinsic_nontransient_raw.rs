@@ -1,3 +1,4 @@
+// run-pass
 #![feature(core_intrinsics)]
 #![feature(const_heap)]
 #![feature(const_raw_ptr_deref)]
@@ -5,7 +6,6 @@
 use std::intrinsics;
 
 const FOO: *const i32 = foo();
-//~^ ERROR untyped pointers are not allowed in constant
 
 const fn foo() -> &'static i32 {
     let t = unsafe {
@@ -16,4 +16,5 @@ const fn foo() -> &'static i32 {
     unsafe { &*t }
 }
 fn main() {
+    assert_eq!(unsafe { *FOO }, 20)
 }
@@ -0,0 +1,25 @@
+// Checks that the stacktrace of a const evaluation error shows the values of the arguments and
+// the initialized variables of the frames.
+
+#![crate_type = "lib"]
+
+pub const fn squares(len: usize) -> [u32; 16] {
+    let mut table = [0; 16];
+    let mut i = 0;
+    while i < len {
+        table[i] = (i * i) as u32; //~ ERROR could not evaluate static initializer
+        i += 1;
+    }
+    table
+}
+
+pub struct Config {
+    pub name: &'static str,
+    pub len: usize,
+}
+
+pub const fn build(config: Config) -> [u32; 16] {
+    squares(config.len)
+}
+
+pub static TABLE: [u32; 16] = build(Config { name: "squares", len: 17 });
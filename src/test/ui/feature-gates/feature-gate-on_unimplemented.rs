@@ -0,0 +1,5 @@
+#![crate_type = "lib"]
+
+#[on_unimplemented(message = "not implemented")]
+//~^ ERROR the `#[on_unimplemented]` attribute is an experimental feature
+pub trait Foo {}
@@ -6,7 +6,7 @@ struct E<T> {
 }
 
 trait TestMut {
-    type Output<'a>;
+    type Output<'a> where Self: 'a;
     fn test_mut<'a>(&'a mut self) -> Self::Output<'a>;
 }
 
@@ -0,0 +1,80 @@
+// run-pass
+
+#![feature(generic_associated_types)]
+
+// A lending iterator, whose items borrow from the iterator itself, like the records of a
+// zero-copy parser.
+
+trait LendingIterator {
+    type Item<'a> where Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>>;
+}
+
+/// The mutable windows of a slice, which can't be a regular iterator since they overlap.
+struct WindowsMut<'t, T> {
+    slice: &'t mut [T],
+    start: usize,
+    size: usize,
+}
+
+impl<'t, T> LendingIterator for WindowsMut<'t, T> {
+    type Item<'a> where Self: 'a = &'a mut [T];
+
+    fn next<'a>(&'a mut self) -> Option<&'a mut [T]> {
+        let window = self.slice[self.start..].get_mut(..self.size)?;
+        self.start += 1;
+        Some(window)
+    }
+}
+
+/// The fields of the records of a buffer, which are parsed in place.
+struct Records {
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl LendingIterator for Records {
+    type Item<'a> where Self: 'a = Vec<&'a [u8]>;
+
+    fn next<'a>(&'a mut self) -> Option<Vec<&'a [u8]>> {
+        let rest = &self.buf[self.pos..];
+        if rest.is_empty() {
+            return None;
+        }
+        let len = rest.iter().position(|&b| b == b'\n').unwrap_or(rest.len());
+        self.pos = (self.pos + len + 1).min(self.buf.len());
+        Some(rest[..len].split(|&b| b == b',').collect())
+    }
+}
+
+/// Counts the fields of all records, which can't be collected since they borrow from `records`.
+fn count_fields<I>(mut records: I) -> usize
+where
+    I: LendingIterator,
+    for<'a> I::Item<'a>: AsRef<[&'a [u8]]>,
+{
+    let mut count = 0;
+    while let Some(fields) = records.next() {
+        count += fields.as_ref().len();
+    }
+    count
+}
+
+fn main() {
+    let mut values = [1, 2, 3, 4];
+    let mut windows = WindowsMut { slice: &mut values, start: 0, size: 2 };
+    while let Some(window) = windows.next() {
+        window[1] += window[0];
+    }
+    assert_eq!(values, [1, 3, 6, 10]);
+
+    let mut records = Records { buf: b"a,bb\nccc\nd,e,f".to_vec(), pos: 0 };
+    assert_eq!(records.next(), Some(vec![&b"a"[..], b"bb"]));
+    assert_eq!(records.next(), Some(vec![&b"ccc"[..]]));
+    assert_eq!(records.next(), Some(vec![&b"d"[..], b"e", b"f"]));
+    assert_eq!(records.next(), None);
+
+    let records = Records { buf: b"a,bb\nccc\nd,e,f".to_vec(), pos: 0 };
+    assert_eq!(count_fields(records), 6);
+}
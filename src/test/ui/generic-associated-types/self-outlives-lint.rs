@@ -0,0 +1,51 @@
+// run-rustfix
+
+#![feature(generic_associated_types)]
+
+// Checks that generic associated types have to declare the outlives bounds that are implied by
+// the methods that return them.
+
+trait Iterable {
+    type Item<'x>;
+    //~^ ERROR missing required bound on `Item`
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>>;
+}
+
+trait Deserializer<T> {
+    type Out<'x>;
+    //~^ ERROR missing required bound on `Out`
+    fn deserialize<'a>(&self, input: &'a T) -> Self::Out<'a>;
+}
+
+trait Pair {
+    type Both<'x, 'y>;
+    //~^ ERROR missing required bounds on `Both`
+    fn both<'a, 'b>(&'a mut self, other: &'a &'b ()) -> Self::Both<'a, 'b>;
+}
+
+trait Lookup<K> {
+    type Value<'x> where K: 'x;
+    //~^ ERROR missing required bound on `Value`
+    fn get<'a>(&'a self, key: &'a K) -> Option<Self::Value<'a>>;
+}
+
+// The bounds are declared.
+trait Declared {
+    type Item<'x> where Self: 'x;
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>>;
+}
+
+// `first` doesn't imply that `Self: 'x` for all lifetimes `'x`.
+trait Static {
+    type Item<'x>;
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>>;
+    fn first(&mut self) -> Self::Item<'static>;
+}
+
+// Only the methods that return the associated type require bounds.
+trait Input {
+    type Item<'x>;
+    fn push<'a>(&'a mut self, item: Self::Item<'a>);
+}
+
+fn main() {}
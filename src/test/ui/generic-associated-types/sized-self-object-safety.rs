@@ -0,0 +1,40 @@
+// check-pass
+
+#![feature(generic_associated_types)]
+
+// Checks that generic associated types that require `Self: Sized` don't make their trait object
+// unsafe, and don't have to be specified by its trait objects.
+
+trait LendingIterator {
+    type Item<'a> where Self: 'a, Self: Sized;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> where Self: Sized;
+
+    fn size_hint(&self) -> (usize, Option<usize>);
+}
+
+struct Lines<'s>(&'s str);
+
+impl<'s> LendingIterator for Lines<'s> {
+    type Item<'a> where Self: 'a = &'a str;
+
+    fn next<'a>(&'a mut self) -> Option<&'a str> {
+        let (line, rest) = self.0.split_once('\n')?;
+        self.0 = rest;
+        Some(line)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.lines().count(), None)
+    }
+}
+
+fn min_size(iter: &dyn LendingIterator) -> usize {
+    iter.size_hint().0
+}
+
+fn main() {
+    let mut lines = Lines("a\nb\n");
+    assert_eq!(lines.next(), Some("a"));
+    assert_eq!(min_size(&lines), 1);
+}
@@ -0,0 +1,23 @@
+// run-rustfix
+#![deny(unused_imports)]
+
+// Check that removing unused imports doesn't leave a single import in a pair of braces.
+
+use std::collections::{HashMap, HashSet};
+//~^ ERROR unused import: `HashSet`
+use std::fmt::{self, Display, Write};
+//~^ ERROR unused imports: `Display`, `Write`
+use std::sync::{atomic::{AtomicBool, AtomicUsize}, Arc, Mutex};
+//~^ ERROR unused imports: `AtomicUsize`, `Mutex`
+use std::{cell::{Cell, RefCell}, rc::Rc};
+//~^ ERROR unused imports: `Cell`, `RefCell`
+use std::io::{self, Read};
+//~^ ERROR unused import: `Read`
+
+fn main() {
+    let _: HashMap<u8, u8> = HashMap::new();
+    let _: Option<&dyn fmt::Debug> = None;
+    let _ = Arc::new(AtomicBool::new(false));
+    let _ = Rc::new(());
+    let _: io::Result<()> = Ok(());
+}
@@ -0,0 +1,39 @@
+// Checks that `#[assert_layout]` reports the assertions that don't hold, together with the
+// computed layout.
+
+#![feature(assert_layout)]
+#![crate_type = "lib"]
+
+#[repr(C)]
+#[assert_layout(size = 8, align = 4, offset(tag = 0, len = 4), niche = false)]
+pub struct Header {
+    pub tag: u32,
+    pub len: u32,
+}
+
+#[assert_layout(size = 1, align = 1, niche = true)]
+pub enum Kind {
+    Request,
+    Response,
+}
+
+#[assert_layout(size = 4, align = 2, niche = true)]
+pub type Pair = (u16, bool);
+
+#[repr(C)]
+#[assert_layout(size = 4, align = 2)]
+pub struct Packet { pub flags: u8, pub len: u32 }
+//~^ ERROR the layout of `Packet` doesn't match its assertions
+
+#[assert_layout(offset(tag = 0, value = 2), niche = false)]
+pub struct Tagged { pub tag: bool, pub value: u16 }
+//~^ ERROR the layout of `Tagged` doesn't match its assertions
+
+#[assert_layout(size = 1, sise = 1, offset(flag = 0))]
+//~^ ERROR unrecognized layout assertion `sise`
+//~| ERROR `Flag` has no field named `flag`
+pub struct Flag(bool);
+
+#[assert_layout(size = 8)]
+pub struct Wrapper<T>(T);
+//~^ ERROR cannot assert the layout of `Wrapper<T>`
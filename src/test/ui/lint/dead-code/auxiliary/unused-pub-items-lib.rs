@@ -0,0 +1,18 @@
+// compile-flags: -Z unused-pub-items
+
+pub struct Used;
+pub struct Unused;
+
+pub fn used_fn() -> Used {
+    Used
+}
+pub fn unused_fn() {}
+
+pub trait UsedTrait {
+    fn method(&self) {}
+}
+impl UsedTrait for Used {}
+
+pub mod inner {
+    pub const UNUSED_CONST: u32 = 0;
+}
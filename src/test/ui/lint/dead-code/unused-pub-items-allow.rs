@@ -0,0 +1,12 @@
+// Check that the `unused_pub_items` lint can be allowed at the root of the crate that is
+// being linked.
+//
+// aux-build:unused-pub-items-lib.rs
+// compile-flags: -Z unused-pub-items
+// check-pass
+
+#![allow(unused_pub_items)]
+
+extern crate unused_pub_items_lib;
+
+fn main() {}
@@ -0,0 +1,14 @@
+// Check that `-Z unused-pub-items` reports public items of upstream crates
+// that no crate in the crate graph uses.
+//
+// aux-build:unused-pub-items-lib.rs
+// compile-flags: -Z unused-pub-items
+// check-pass
+
+extern crate unused_pub_items_lib;
+
+use unused_pub_items_lib::UsedTrait;
+
+fn main() {
+    unused_pub_items_lib::used_fn().method();
+}
@@ -0,0 +1,3 @@
+// Stands in for generated code; `dead_code` is allowed here by the lint configuration file.
+
+fn generated_but_unused() {}
@@ -0,0 +1,7 @@
+// Check that problems with a `-Z lint-config` file are reported at their
+// location within it.
+//
+// compile-flags: -Z lint-config={{src-base}}/lint/lint-config/lint-config-errors.toml
+// error-pattern: invalid lint level `loud`
+
+fn main() {}
@@ -0,0 +1,7 @@
+// Check that unknown lints in a `-Z lint-config` file are reported at their
+// location within it.
+//
+// check-pass
+// compile-flags: -Z lint-config={{src-base}}/lint/lint-config/lint-config-unknown.toml
+
+fn main() {}
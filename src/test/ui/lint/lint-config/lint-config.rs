@@ -0,0 +1,14 @@
+// Check that lint levels can be set from a `-Z lint-config` file, and that the path
+// overrides in it apply to items defined in matching files.
+//
+// compile-flags: -Z lint-config={{src-base}}/lint/lint-config/lint-config.toml
+
+#[path = "auxiliary/generated.rs"]
+mod generated;
+
+fn unused() {} //~ ERROR function is never used: `unused`
+
+#[allow(dead_code)]
+fn allowed() {}
+
+fn main() {}
@@ -0,0 +1,12 @@
+// Test that the `on_unimplemented` feature, which was removed when `#[on_unimplemented]` was
+// renamed to `#[rustc_on_unimplemented]`, can be enabled again without E0557, and that it does
+// not ungate `#[rustc_on_unimplemented]`.
+
+#![feature(on_unimplemented)]
+
+#[rustc_on_unimplemented = "test error `{Self}` with `{Bar}`"]
+//~^ ERROR this is an internal attribute that will never be stable
+trait Foo<Bar>
+{}
+
+fn main() {}
@@ -0,0 +1,34 @@
+// Checks the errors of misused `#[on_unimplemented]` attributes.
+
+#![feature(on_unimplemented)]
+
+#[on_unimplemented(message = "not a trait")]
+struct NotATrait;
+//~^^ ERROR `on_unimplemented` attribute should be applied to a trait
+
+// The options and parameters that describe the obligation are internal to the standard library.
+#[on_unimplemented(enclosing_scope = "in this scope")]
+trait EnclosingScope {}
+//~^^ ERROR this attribute must have a valid value
+
+#[on_unimplemented(on(_Self = "std::string::String", label = "a string"))]
+trait OnClause {}
+//~^^ ERROR this attribute must have a valid value
+
+#[on_unimplemented(on(crate_local, label = "a local type"))]
+trait CrateLocal {}
+//~^^ ERROR this attribute must have a valid value
+
+#[on_unimplemented(label = "required {from_desugaring}")]
+trait FromDesugaring {}
+//~^^ ERROR there is no parameter `from_desugaring` on trait `FromDesugaring`
+
+#[on_unimplemented(label = "required in {ItemContext}")]
+trait InItemContext {}
+//~^^ ERROR there is no parameter `ItemContext` on trait `InItemContext`
+
+#[on_unimplemented(message = "`{T}` is missing")]
+trait NoParameter {}
+//~^^ ERROR there is no parameter `T` on trait `NoParameter`
+
+fn main() {}
@@ -0,0 +1,25 @@
+// Checks that `#[on_unimplemented]` customizes the errors of unsatisfied bounds on the traits of
+// user crates.
+
+#![feature(on_unimplemented)]
+
+#[on_unimplemented(
+    message = "`{Self}` is not a request handler",
+    label = "expected a request handler",
+    note = "derive `Handler` on your struct",
+    note = "handlers are registered with `serve`"
+)]
+trait Handler {}
+
+struct Index;
+struct About;
+
+impl Handler for Index {}
+
+fn serve<H: Handler>(_handler: H) {}
+
+fn main() {
+    serve(Index);
+    serve(About);
+    //~^ ERROR `About` is not a request handler
+}
@@ -0,0 +1,11 @@
+pub enum Shape {
+    Circle(f32),
+    Rect { w: f32, h: f32 },
+    Empty,
+}
+
+pub enum Color {
+    Red,
+    Green,
+    Blue,
+}
@@ -0,0 +1,32 @@
+// run-rustfix
+// edition:2018
+// aux-crate:match_arms_lib=match-arms-lib.rs
+
+// Check that non-exhaustive matches get a machine-applicable suggestion that adds one arm per
+// missing pattern.
+
+#![allow(dead_code)]
+
+use match_arms_lib::{Color, Shape};
+
+fn area(shape: &Shape) -> f32 {
+    match shape {
+        //~^ ERROR non-exhaustive patterns: `&Rect { .. }` and `&Empty` not covered
+        Shape::Circle(r) => r * r,
+    }
+}
+
+fn brightness(color: Option<Color>) -> u8 {
+    match color {
+        //~^ ERROR non-exhaustive patterns: `Some(Green)` and `Some(Blue)` not covered
+        Some(Color::Red) => 1,
+        None => 0,
+    }
+}
+
+fn name(color: Color) -> &'static str {
+    match color {}
+    //~^ ERROR non-exhaustive patterns: `Red`, `Green` and `Blue` not covered
+}
+
+fn main() {}
@@ -0,0 +1,59 @@
+// build-fail
+// compile-flags:-Zpolymorphize=on
+#![feature(rustc_attrs)]
+
+// This test checks that the polymorphization analysis considers the generic parameters of a
+// closure as used where its type can be observed, e.g. by `TypeId::of` or `type_name`, but
+// still shares the instances of the body of the closure.
+
+use std::any::{type_name, Any, TypeId};
+
+fn name_of<F>(_: &F) -> &'static str {
+    type_name::<F>()
+}
+
+fn call_with_three<F: Fn(u32) -> u32>(f: F) -> u32 {
+    f(3)
+}
+
+// Closure is only called.
+#[rustc_polymorphize_error]
+pub fn called<T>() -> u32 {
+    //~^ ERROR item has unused generic parameters
+    let add_one = |x: u32| x + 1;
+    //~^ ERROR item has unused generic parameters
+    add_one(3)
+}
+
+// Closure is passed to a generic function which observes its type.
+#[rustc_polymorphize_error]
+pub fn passed<T>() -> &'static str {
+    let add_one = |x: u32| x + 1;
+    //~^ ERROR item has unused generic parameters
+    name_of(&add_one)
+}
+
+// Closure is passed to a generic function which only calls it.
+#[rustc_polymorphize_error]
+pub fn passed_to_call<T>() -> u32 {
+    //~^ ERROR item has unused generic parameters
+    let add_one = |x: u32| x + 1;
+    //~^ ERROR item has unused generic parameters
+    call_with_three(add_one)
+}
+
+// Closure is unsized into a trait object whose vtable observes its type.
+#[rustc_polymorphize_error]
+pub fn unsized_to_dyn<T: 'static>() -> TypeId {
+    let add_one = |x: u32| x + 1;
+    //~^ ERROR item has unused generic parameters
+    let any: &dyn Any = &add_one;
+    any.type_id()
+}
+
+fn main() {
+    called::<u32>();
+    passed::<u32>();
+    passed_to_call::<u32>();
+    unsized_to_dyn::<u32>();
+}
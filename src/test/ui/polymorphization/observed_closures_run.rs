@@ -0,0 +1,42 @@
+// run-pass
+// compile-flags: -O
+// Check that polymorphization, which is enabled by default when optimizing, keeps a distinct
+// closure type for each instantiation of its parent when the type of the closure is observed.
+
+use std::any::{type_name, Any, TypeId};
+
+fn id_of<F: 'static>(_: &F) -> TypeId {
+    TypeId::of::<F>()
+}
+
+fn name_of<F>(_: &F) -> &'static str {
+    type_name::<F>()
+}
+
+fn passed<T: 'static>() -> TypeId {
+    let add_one = |x: u32| x + 1;
+    id_of(&add_one)
+}
+
+fn named<T>() -> &'static str {
+    let add_one = |x: u32| x + 1;
+    name_of(&add_one)
+}
+
+fn unsized_to_any<T: 'static>() -> TypeId {
+    let add_one = |x: u32| x + 1;
+    let any: Box<dyn Any> = Box::new(add_one);
+    (*any).type_id()
+}
+
+fn called<T>() -> u32 {
+    let add_one = |x: u32| x + 1;
+    add_one(3)
+}
+
+fn main() {
+    assert_ne!(passed::<u32>(), passed::<u64>());
+    assert_ne!(named::<u32>(), named::<u64>());
+    assert_ne!(unsized_to_any::<u32>(), unsized_to_any::<u64>());
+    assert_eq!(called::<u32>() + called::<u64>(), 8);
+}
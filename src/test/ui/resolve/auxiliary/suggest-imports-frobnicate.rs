@@ -0,0 +1,5 @@
+pub trait Frobnicate {
+    fn frobnicate(&self) {}
+}
+
+impl Frobnicate for u32 {}
@@ -0,0 +1,11 @@
+pub mod a {
+    pub mod b {
+        pub mod c {
+            pub struct Gadget;
+        }
+    }
+}
+
+pub mod prelude {
+    pub use crate::a::b::c;
+}
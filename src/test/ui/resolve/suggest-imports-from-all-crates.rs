@@ -0,0 +1,14 @@
+// Check that `-Z suggest-imports-from-all-crates` suggests the shortest path to an item,
+// and suggests traits from `--extern` crates that are not used anywhere else.
+
+// edition:2018
+// aux-crate:gadgets=suggest-imports-gadgets.rs
+// aux-crate:frobnicate=suggest-imports-frobnicate.rs
+// compile-flags: -Z suggest-imports-from-all-crates
+
+fn main() {
+    let _ = Gadget;
+    //~^ ERROR cannot find value `Gadget` in this scope
+    1u32.frobnicate();
+    //~^ ERROR no method named `frobnicate` found for type `u32` in the current scope
+}
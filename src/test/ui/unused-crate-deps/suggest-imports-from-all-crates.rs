@@ -0,0 +1,12 @@
+// Check that loading every `--extern` crate for `-Z suggest-imports-from-all-crates` does not
+// count as using them.
+
+// edition:2018
+// check-pass
+// aux-crate:bar=bar.rs
+// compile-flags: -Z suggest-imports-from-all-crates
+
+#![warn(unused_crate_dependencies)]
+//~^ WARNING external crate `bar` unused in
+
+fn main() {}
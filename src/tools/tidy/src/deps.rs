@@ -203,6 +203,7 @@
     "thread_local",
     "time",
     "tinyvec",
+    "toml",
     "tracing",
     "tracing-attributes",
     "tracing-core",